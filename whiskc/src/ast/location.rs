@@ -97,7 +97,7 @@ impl Location {
     pub fn front(&self) -> Self {
         Self {
             line: self.line,
-            col: if self.col >= 1 { self.col - 1 } else { 0 },
+            col: self.col.saturating_sub(1),
         }
     }
 
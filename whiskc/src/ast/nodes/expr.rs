@@ -1,15 +1,17 @@
 use crate::ast::{
     location::{Locatable, Located, Span},
-    parsing::token::{Delimiter, Keyword, Operator},
+    parsing::token::{Delimiter, IntLiteral, Keyword, Operator},
 };
 
-use super::{punctuate::Punctuated, stmt::Stmt};
+use super::{pat::Pattern, punctuate::Punctuated, stmt::Stmt, ty::Type};
 
 #[derive(Debug, Clone)]
 pub enum Expr {
     Unit(Span),
-    Integer(Located<i64>),
+    Integer(Located<IntLiteral>),
+    Float(Located<f64>),
     Bool(Located<bool>),
+    Str(Located<String>),
     Ident(Located<String>),
     Unary(UnaryExpr),
     Binary(BinaryExpr),
@@ -19,25 +21,41 @@ pub enum Expr {
     Return(ReturnExpr),
     If(IfExpr),
     Loop(LoopExpr),
+    While(WhileExpr),
+    For(ForExpr),
+    Break(BreakExpr),
+    Continue(ContinueExpr),
     StructInit(StructInitExpr),
     MemberAccess(MemberAccessExpr),
+    Match(MatchExpr),
+    Path(PathExpr),
+    Array(ArrayExpr),
+    ArrayRepeat(ArrayRepeatExpr),
+    Index(IndexExpr),
+    Cast(CastExpr),
 }
 impl Expr {
     pub fn is_block(&self) -> bool {
-        matches!(self, Self::Block(_) | Self::If(_) | Self::Loop(_))
+        matches!(
+            self,
+            Self::Block(_)
+                | Self::If(_)
+                | Self::Loop(_)
+                | Self::While(_)
+                | Self::For(_)
+                | Self::Match(_)
+        )
     }
 
     pub fn has_eval_expr(&self) -> bool {
         match self {
             Self::Block(v) => v.eval_expr.is_some(),
-            Self::If(v) => {
-                v.then.eval_expr.is_some()
-                    && v.else_expr
-                        .as_ref()
-                        .map(|v| v.body.eval_expr.is_some())
-                        .unwrap_or(false)
-            }
+            Self::If(v) => v.has_eval_expr(),
             Self::Loop(v) => v.body.eval_expr.is_some(),
+            Self::Match(v) => v
+                .arms
+                .iter()
+                .any(|v| !v.expr.is_block() || v.expr.has_eval_expr()),
             _ => false,
         }
     }
@@ -47,7 +65,9 @@ impl Locatable for Expr {
         match self {
             Expr::Unit(loc) => *loc,
             Expr::Integer(located) => located.1,
+            Expr::Float(located) => located.1,
             Expr::Bool(located) => located.1,
+            Expr::Str(located) => located.1,
             Expr::Ident(located) => located.1,
             Expr::Unary(unary_expr) => unary_expr.get_location(),
             Expr::Binary(binary_expr) => binary_expr.get_location(),
@@ -57,8 +77,18 @@ impl Locatable for Expr {
             Expr::Return(expr) => expr.get_location(),
             Expr::If(expr) => expr.get_location(),
             Expr::Loop(expr) => expr.get_location(),
+            Expr::While(expr) => expr.get_location(),
+            Expr::For(expr) => expr.get_location(),
+            Expr::Match(expr) => expr.get_location(),
+            Expr::Break(expr) => expr.get_location(),
+            Expr::Continue(expr) => expr.get_location(),
             Expr::StructInit(expr) => expr.get_location(),
             Expr::MemberAccess(expr) => expr.get_location(),
+            Expr::Path(expr) => expr.get_location(),
+            Expr::Array(expr) => expr.get_location(),
+            Expr::ArrayRepeat(expr) => expr.get_location(),
+            Expr::Index(expr) => expr.get_location(),
+            Expr::Cast(expr) => expr.get_location(),
         }
     }
 }
@@ -147,6 +177,20 @@ pub struct IfExpr {
     pub then: BlockExpr,
     pub else_expr: Option<ElseExpr>,
 }
+impl IfExpr {
+    /// Returns whether any branch evaluates to an expression, which requires an `else`.
+    /// The other branches may diverge instead, such as `if c { 1 } else { return 0; }`.
+    pub fn has_eval_expr(&self) -> bool {
+        let Some(else_expr) = &self.else_expr else {
+            return false;
+        };
+        self.then.eval_expr.is_some()
+            || match &else_expr.body {
+                ElseBody::Block(block) => block.eval_expr.is_some(),
+                ElseBody::If(if_expr) => if_expr.has_eval_expr(),
+            }
+    }
+}
 impl Locatable for IfExpr {
     fn get_location(&self) -> Span {
         if let Some(else_expr) = &self.else_expr {
@@ -160,7 +204,23 @@ impl Locatable for IfExpr {
 #[derive(Debug, Clone)]
 pub struct ElseExpr {
     pub else_tok: Located<Keyword>,
-    pub body: BlockExpr,
+    pub body: ElseBody,
+}
+
+/// Branch taken when the condition is false, which is either a block or another `if` in an
+/// `else if` chain.
+#[derive(Debug, Clone)]
+pub enum ElseBody {
+    Block(BlockExpr),
+    If(Box<IfExpr>),
+}
+impl Locatable for ElseBody {
+    fn get_location(&self) -> Span {
+        match self {
+            ElseBody::Block(v) => v.get_location(),
+            ElseBody::If(v) => v.get_location(),
+        }
+    }
 }
 impl Locatable for ElseExpr {
     fn get_location(&self) -> Span {
@@ -168,20 +228,95 @@ impl Locatable for ElseExpr {
     }
 }
 
+/// Label of a loop, such as `'outer:` in `'outer: loop { }`.
+#[derive(Debug, Clone)]
+pub struct LoopLabel {
+    pub name: Located<String>,
+    pub colon_tok: Located<Delimiter>,
+}
+
 #[derive(Debug, Clone)]
 pub struct LoopExpr {
+    pub label: Option<LoopLabel>,
     pub loop_tok: Located<Keyword>,
     pub body: BlockExpr,
 }
 impl Locatable for LoopExpr {
     fn get_location(&self) -> Span {
-        Span::combine(self.loop_tok.1, self.body.get_location())
+        let start_loc = self.label.as_ref().map_or(self.loop_tok.1, |v| v.name.1);
+        Span::combine(start_loc, self.body.get_location())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WhileExpr {
+    pub label: Option<LoopLabel>,
+    pub while_tok: Located<Keyword>,
+    pub cond: Box<Expr>,
+    pub body: BlockExpr,
+}
+impl Locatable for WhileExpr {
+    fn get_location(&self) -> Span {
+        let start_loc = self.label.as_ref().map_or(self.while_tok.1, |v| v.name.1);
+        Span::combine(start_loc, self.body.get_location())
+    }
+}
+
+/// Loop over the elements of an array or a vector, such as `for x in v { .. }`.
+#[derive(Debug, Clone)]
+pub struct ForExpr {
+    pub label: Option<LoopLabel>,
+    pub for_tok: Located<Keyword>,
+    pub var: Located<String>,
+    pub in_tok: Located<Keyword>,
+    pub iter: Box<Expr>,
+    pub body: BlockExpr,
+}
+impl Locatable for ForExpr {
+    fn get_location(&self) -> Span {
+        let start_loc = self.label.as_ref().map_or(self.for_tok.1, |v| v.name.1);
+        Span::combine(start_loc, self.body.get_location())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BreakExpr {
+    pub break_tok: Located<Keyword>,
+    /// label of the loop to break out of, or the innermost loop if `None`.
+    pub label: Option<Located<String>>,
+    pub expr: Option<Box<Expr>>,
+}
+impl Locatable for BreakExpr {
+    fn get_location(&self) -> Span {
+        let end_loc = if let Some(expr) = &self.expr {
+            expr.get_location()
+        } else if let Some(label) = &self.label {
+            label.1
+        } else {
+            self.break_tok.1
+        };
+        Span::combine(self.break_tok.1, end_loc)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContinueExpr {
+    pub continue_tok: Located<Keyword>,
+    /// label of the loop to continue, or the innermost loop if `None`.
+    pub label: Option<Located<String>>,
+}
+impl Locatable for ContinueExpr {
+    fn get_location(&self) -> Span {
+        let end_loc = self.label.as_ref().map_or(self.continue_tok.1, |v| v.1);
+        Span::combine(self.continue_tok.1, end_loc)
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct StructInitExpr {
     pub ty_name: Located<String>,
+    /// variant of the enum named by `ty_name`, such as `Rect` in `Shape::Rect { w: 1, h: 2 }`.
+    pub variant: Option<Located<String>>,
     pub brace_open_tok: Located<Delimiter>,
     pub fields: Punctuated<FieldInit>,
     pub brace_close_tok: Located<Delimiter>,
@@ -215,3 +350,106 @@ impl Locatable for MemberAccessExpr {
         Span::combine(self.expr.get_location(), self.field_name.1)
     }
 }
+
+/// Variant of an enum, such as `Shape::Empty`.
+#[derive(Debug, Clone)]
+pub struct PathExpr {
+    pub ty_name: Located<String>,
+    pub sep_tok: Located<Operator>,
+    pub name: Located<String>,
+}
+impl Locatable for PathExpr {
+    fn get_location(&self) -> Span {
+        Span::combine(self.ty_name.1, self.name.1)
+    }
+}
+
+/// Array of the listed elements, such as `[1, 2, 3]`.
+#[derive(Debug, Clone)]
+pub struct ArrayExpr {
+    pub bracket_open_tok: Located<Delimiter>,
+    pub elements: Punctuated<Expr>,
+    pub bracket_close_tok: Located<Delimiter>,
+}
+impl Locatable for ArrayExpr {
+    fn get_location(&self) -> Span {
+        Span::combine(self.bracket_open_tok.1, self.bracket_close_tok.1)
+    }
+}
+
+/// Array of copies of a value, such as `[0; 4]`.
+#[derive(Debug, Clone)]
+pub struct ArrayRepeatExpr {
+    pub bracket_open_tok: Located<Delimiter>,
+    pub expr: Box<Expr>,
+    pub semi_tok: Located<Delimiter>,
+    pub len: Located<usize>,
+    pub bracket_close_tok: Located<Delimiter>,
+}
+impl Locatable for ArrayRepeatExpr {
+    fn get_location(&self) -> Span {
+        Span::combine(self.bracket_open_tok.1, self.bracket_close_tok.1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IndexExpr {
+    pub expr: Box<Expr>,
+    pub bracket_open_tok: Located<Delimiter>,
+    pub index: Box<Expr>,
+    pub bracket_close_tok: Located<Delimiter>,
+}
+impl Locatable for IndexExpr {
+    fn get_location(&self) -> Span {
+        Span::combine(self.expr.get_location(), self.bracket_close_tok.1)
+    }
+}
+
+/// Cast of the value to the type, such as `flag as int`.
+#[derive(Debug, Clone)]
+pub struct CastExpr {
+    pub expr: Box<Expr>,
+    pub as_tok: Located<Operator>,
+    pub ty: Type,
+}
+impl Locatable for CastExpr {
+    fn get_location(&self) -> Span {
+        Span::combine(self.expr.get_location(), self.ty.get_location())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchExpr {
+    pub match_tok: Located<Keyword>,
+    pub expr: Box<Expr>,
+    pub brace_open_tok: Located<Delimiter>,
+    pub arms: Vec<MatchArm>,
+    pub brace_close_tok: Located<Delimiter>,
+}
+impl Locatable for MatchExpr {
+    fn get_location(&self) -> Span {
+        Span::combine(self.match_tok.1, self.brace_close_tok.1)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pat: Pattern,
+    pub guard: Option<MatchGuard>,
+    pub arrow_tok: Located<Operator>,
+    pub expr: Expr,
+    /// may be omitted after a block, or after the last arm.
+    pub comma_tok: Option<Located<Delimiter>>,
+}
+impl Locatable for MatchArm {
+    fn get_location(&self) -> Span {
+        Span::combine(self.pat.get_location(), self.expr.get_location())
+    }
+}
+
+/// Condition of a `match` arm, such as `if n > 0` in `n if n > 0 => ...`.
+#[derive(Debug, Clone)]
+pub struct MatchGuard {
+    pub if_tok: Located<Keyword>,
+    pub cond: Box<Expr>,
+}
@@ -3,6 +3,7 @@ pub mod attributes;
 pub mod expr;
 pub mod func;
 pub mod item;
+pub mod pat;
 pub mod punctuate;
 pub mod stmt;
 pub mod ty;
@@ -0,0 +1,107 @@
+use crate::ast::{
+    location::{Locatable, Located, Span},
+    parsing::token::{Delimiter, Operator, TypeKeyword},
+};
+
+use super::punctuate::Punctuated;
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// `_`, which matches any value.
+    Wildcard(Span),
+    /// matches any value, binding it to a variable with the name.
+    Ident(Located<String>),
+    Integer(Located<IntBound>),
+    Bool(Located<bool>),
+    Range(RangePattern),
+    Variant(VariantPattern),
+}
+impl Locatable for Pattern {
+    fn get_location(&self) -> Span {
+        match self {
+            Pattern::Wildcard(loc) => *loc,
+            Pattern::Ident(v) => v.1,
+            Pattern::Integer(v) => v.1,
+            Pattern::Bool(v) => v.1,
+            Pattern::Range(v) => v.get_location(),
+            Pattern::Variant(v) => v.get_location(),
+        }
+    }
+}
+
+/// Integer of a pattern, which may be negated, such as `-1`, and have the suffix of its type,
+/// such as `255u8`.
+#[derive(Debug, Clone, Copy)]
+pub struct IntBound {
+    pub value: i128,
+    pub suffix: Option<TypeKeyword>,
+}
+
+/// Integer range such as `0..10`, or `0..=9` with an inclusive end.
+/// Either bound may be left out, such as `10..` or `..=0`, to extend the range to the limit of the
+/// type.
+#[derive(Debug, Clone)]
+pub struct RangePattern {
+    pub start: Option<Located<IntBound>>,
+    pub op: Located<Operator>,
+    pub end: Option<Located<IntBound>>,
+}
+impl RangePattern {
+    pub fn is_inclusive(&self) -> bool {
+        self.op.0 == Operator::RangeInclusive
+    }
+}
+impl Locatable for RangePattern {
+    fn get_location(&self) -> Span {
+        Span::combine(
+            self.start.as_ref().map(|v| v.1).unwrap_or(self.op.1),
+            self.end.as_ref().map(|v| v.1).unwrap_or(self.op.1),
+        )
+    }
+}
+
+/// Variant of an enum, such as `Shape::Empty`, `Shape::Circle(r)` or `Shape::Rect { w, h: 0 }`.
+#[derive(Debug, Clone)]
+pub struct VariantPattern {
+    pub ty_name: Located<String>,
+    pub sep_tok: Located<Operator>,
+    pub name: Located<String>,
+    pub fields: VariantPatternFields,
+}
+impl Locatable for VariantPattern {
+    fn get_location(&self) -> Span {
+        let end_loc = match &self.fields {
+            VariantPatternFields::Unit => self.name.1,
+            VariantPatternFields::Tuple {
+                paren_close_tok, ..
+            } => paren_close_tok.1,
+            VariantPatternFields::Struct {
+                brace_close_tok, ..
+            } => brace_close_tok.1,
+        };
+        Span::combine(self.ty_name.1, end_loc)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum VariantPatternFields {
+    Unit,
+    Tuple {
+        paren_open_tok: Located<Delimiter>,
+        pats: Punctuated<Pattern>,
+        paren_close_tok: Located<Delimiter>,
+    },
+    /// fields which are left out match any value.
+    Struct {
+        brace_open_tok: Located<Delimiter>,
+        fields: Punctuated<FieldPattern>,
+        brace_close_tok: Located<Delimiter>,
+    },
+}
+
+/// Field of a variant with named fields, such as `h: 0`, or `w` which is short for `w: w`.
+#[derive(Debug, Clone)]
+pub struct FieldPattern {
+    pub field_name: Located<String>,
+    pub pat: Option<(Located<Delimiter>, Pattern)>,
+}
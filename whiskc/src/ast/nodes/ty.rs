@@ -1,6 +1,6 @@
 use crate::ast::{
     location::{Locatable, Located, Span},
-    parsing::token::{Delimiter, Keyword, Operator},
+    parsing::token::{Delimiter, Keyword, Operator, TypeKeyword},
 };
 
 use super::{attributes::Attributes, punctuate::Punctuated};
@@ -19,12 +19,14 @@ pub struct TypeDecl {
 pub enum TypeDeclKind {
     Type(Type),
     Struct(Struct),
+    Enum(Enum),
 }
 impl Locatable for TypeDeclKind {
     fn get_location(&self) -> Span {
         match self {
             TypeDeclKind::Type(v) => v.get_location(),
             TypeDeclKind::Struct(v) => v.get_location(),
+            TypeDeclKind::Enum(v) => v.get_location(),
         }
     }
 }
@@ -33,12 +35,16 @@ impl Locatable for TypeDeclKind {
 pub enum Type {
     Primitive(Located<PrimType>),
     Ident(Located<String>),
+    Array(ArrayType),
+    Vec(VecType),
 }
 impl Locatable for Type {
     fn get_location(&self) -> Span {
         match self {
             Type::Primitive(ty) => ty.1,
             Type::Ident(ty) => ty.1,
+            Type::Array(ty) => ty.get_location(),
+            Type::Vec(ty) => ty.get_location(),
         }
     }
 }
@@ -48,11 +54,67 @@ impl From<Type> for TypeDeclKind {
     }
 }
 
+/// Fixed-size array type, such as `[int; 4]`.
+#[derive(Debug, Clone)]
+pub struct ArrayType {
+    pub bracket_open_tok: Located<Delimiter>,
+    pub elem_ty: Box<Type>,
+    pub semi_tok: Located<Delimiter>,
+    pub len: Located<usize>,
+    pub bracket_close_tok: Located<Delimiter>,
+}
+impl Locatable for ArrayType {
+    fn get_location(&self) -> Span {
+        Span::combine(self.bracket_open_tok.1, self.bracket_close_tok.1)
+    }
+}
+
+/// Growable vector type, such as `[int]`.
+#[derive(Debug, Clone)]
+pub struct VecType {
+    pub bracket_open_tok: Located<Delimiter>,
+    pub elem_ty: Box<Type>,
+    pub bracket_close_tok: Located<Delimiter>,
+}
+impl Locatable for VecType {
+    fn get_location(&self) -> Span {
+        Span::combine(self.bracket_open_tok.1, self.bracket_close_tok.1)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PrimType {
     Unit,
     Int,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Float,
     Bool,
+    Str,
+}
+impl From<TypeKeyword> for PrimType {
+    fn from(value: TypeKeyword) -> Self {
+        match value {
+            TypeKeyword::Bool => Self::Bool,
+            TypeKeyword::Int => Self::Int,
+            TypeKeyword::I8 => Self::I8,
+            TypeKeyword::I16 => Self::I16,
+            TypeKeyword::I32 => Self::I32,
+            TypeKeyword::I64 => Self::I64,
+            TypeKeyword::U8 => Self::U8,
+            TypeKeyword::U16 => Self::U16,
+            TypeKeyword::U32 => Self::U32,
+            TypeKeyword::U64 => Self::U64,
+            TypeKeyword::Float => Self::Float,
+            TypeKeyword::Str => Self::Str,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -83,3 +145,43 @@ impl Locatable for Field {
         Span::combine(self.name.1, self.ty.get_location())
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct Enum {
+    pub enum_tok: Located<Keyword>,
+    pub brace_open_tok: Located<Delimiter>,
+    pub variants: Punctuated<Variant>,
+    pub brace_close_tok: Located<Delimiter>,
+}
+impl Locatable for Enum {
+    fn get_location(&self) -> Span {
+        Span::combine(self.enum_tok.1, self.brace_close_tok.1)
+    }
+}
+impl From<Enum> for TypeDeclKind {
+    fn from(value: Enum) -> Self {
+        Self::Enum(value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub name: Located<String>,
+    pub kind: VariantKind,
+}
+
+/// Payload of a variant, such as `Empty`, `Circle(int)` or `Rect { w int, h int }`.
+#[derive(Debug, Clone)]
+pub enum VariantKind {
+    Unit,
+    Tuple {
+        paren_open_tok: Located<Delimiter>,
+        types: Punctuated<Type>,
+        paren_close_tok: Located<Delimiter>,
+    },
+    Struct {
+        brace_open_tok: Located<Delimiter>,
+        fields: Punctuated<Field>,
+        brace_close_tok: Located<Delimiter>,
+    },
+}
@@ -1,25 +1,46 @@
 use std::{fs, path::Path, str::FromStr};
 
-use crate::ast::location::{Location, Span};
+use crate::ast::location::{Located, Location, Span};
 
 use super::token::{
-    Delimiter, Identifier, Keyword, Literal, LiteralKeyword, Operator, OperatorChar, Token,
-    TokenKind, TypeKeyword,
+    Delimiter, Identifier, IntLiteral, Keyword, Label, Literal, LiteralKeyword, Operator,
+    OperatorChar, Token, TokenKind, TypeKeyword,
 };
 
+#[derive(Debug, Clone)]
+pub enum LexError {
+    UnterminatedString,
+    InvalidEscapeSequence(char),
+    /// suffix of an integer literal which is not an integer type of a fixed size.
+    InvalidIntegerSuffix(String),
+    /// suffix of a float literal, which cannot have any.
+    InvalidFloatSuffix(String),
+    /// integer literal larger than the largest `u64`.
+    IntegerTooLarge,
+    /// prefix of the radix of an integer literal, such as `0x`, without any digit after it.
+    MissingIntegerDigits(String),
+    InvalidDigit {
+        digit: char,
+        radix: u32,
+    },
+    /// `_` in a number literal which is not followed by a digit, such as in `1__0` or `1_`.
+    MisplacedSeparator,
+}
+
 #[derive(Debug, Default)]
 pub struct Lexer {
-    source: String,
+    source: Vec<char>,
     index: usize,
     tokens: Vec<Token>,
     current_loc: Location,
     prev_loc: Span,
+    errors: Vec<Located<LexError>>,
 }
 impl Lexer {
     pub fn new(source_path: &Path) -> Self {
         let source = fs::read_to_string(source_path).expect("valid path to source file");
         Lexer {
-            source,
+            source: source.chars().collect(),
             current_loc: Location { line: 1, col: 1 },
             ..Default::default()
         }
@@ -45,6 +66,11 @@ impl Lexer {
         &self.prev_loc
     }
 
+    /// Takes the errors found while making the tokens so far.
+    pub fn take_errors(&mut self) -> Vec<Located<LexError>> {
+        std::mem::take(&mut self.errors)
+    }
+
     pub fn peek_token(&mut self, ahead: usize) -> &Token {
         while self.tokens.len() <= ahead {
             let token = self.make_token();
@@ -75,11 +101,41 @@ impl Lexer {
                 kind: TokenKind::EndOfFile,
                 loc: start.into(),
             }
-        } else if self.is_peek_char_f(0, char::is_numeric) {
-            let value = self.get_str_while(char::is_numeric).unwrap();
-            let value = value.parse::<i64>().unwrap();
+        } else if self.is_peek_char_f(0, |c| c.is_ascii_digit()) {
+            let kind = self.get_number_literal();
+            Token {
+                kind: TokenKind::Literal(kind),
+                loc: Span {
+                    start,
+                    end: self.current_loc.front(),
+                },
+            }
+        } else if self.is_peek_char(0, '"') {
+            let value = self.get_str_literal();
+            Token {
+                kind: TokenKind::Literal(Literal::Str(value)),
+                loc: Span {
+                    start,
+                    end: self.current_loc.front(),
+                },
+            }
+        } else if self.is_peek_char(0, '\'')
+            && self.is_peek_char_f(1, |c| char::is_alphabetic(c) || c == '_')
+        {
+            self.next_char();
+            let name = self
+                .get_str_while(|c| char::is_alphanumeric(c) || c == '_')
+                .unwrap();
             Token {
-                kind: TokenKind::Literal(Literal::Int(value)),
+                kind: TokenKind::Label(Label(name)),
+                loc: Span {
+                    start,
+                    end: self.current_loc.front(),
+                },
+            }
+        } else if self.match_str("::") {
+            Token {
+                kind: TokenKind::Operator(Operator::PathSep),
                 loc: Span {
                     start,
                     end: self.current_loc.front(),
@@ -108,6 +164,7 @@ impl Lexer {
                 peek_op_chars.pop();
             }
 
+            self.next_char();
             Token {
                 kind: TokenKind::Unknown,
                 loc: start.into(),
@@ -150,6 +207,15 @@ impl Lexer {
                         end: self.current_loc.front(),
                     },
                 }
+            } else if let Ok(op) = Operator::from_str(&ident) {
+                // operators spelled as words, such as `as`.
+                Token {
+                    kind: TokenKind::Operator(op),
+                    loc: Span {
+                        start,
+                        end: self.current_loc.front(),
+                    },
+                }
             } else {
                 Token {
                     kind: TokenKind::Identifier(Identifier(ident)),
@@ -168,6 +234,63 @@ impl Lexer {
         }
     }
 
+    /// Reads a string literal starting at the opening quote, returning its unescaped value.
+    /// The literal ends at the closing quote, or at the end of line if it is unterminated.
+    fn get_str_literal(&mut self) -> String {
+        let start = self.current_loc;
+        self.next_char();
+
+        let mut value = String::new();
+        loop {
+            match self.peek_char(0) {
+                Some('"') => {
+                    self.next_char();
+                    break;
+                }
+                None | Some('\n') => {
+                    self.errors.push(Located(
+                        LexError::UnterminatedString,
+                        Span {
+                            start,
+                            end: self.current_loc.front(),
+                        },
+                    ));
+                    break;
+                }
+                Some('\\') => {
+                    let esc_start = self.current_loc;
+                    self.next_char();
+                    let Some(esc) = self.next_char() else {
+                        continue;
+                    };
+                    value.push(match esc {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        '\\' => '\\',
+                        '"' => '"',
+                        _ => {
+                            self.errors.push(Located(
+                                LexError::InvalidEscapeSequence(esc),
+                                Span {
+                                    start: esc_start,
+                                    end: self.current_loc.front(),
+                                },
+                            ));
+                            continue;
+                        }
+                    });
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.next_char();
+                }
+            }
+        }
+        value
+    }
+
     fn skip_comment_and_whitespace(&mut self) {
         loop {
             let loc: Location = self.current_loc;
@@ -234,6 +357,137 @@ impl Lexer {
         Some(value)
     }
 
+    /// Reads an integer literal with its suffix, or a float literal if the digits are followed by
+    /// a fraction or an exponent. Digits may be separated by `_`, and integers may be written in
+    /// hexadecimal, octal or binary with the prefixes `0x`, `0o` and `0b`.
+    fn get_number_literal(&mut self) -> Literal {
+        let radix = match (self.peek_char(0), self.peek_char(1)) {
+            (Some('0'), Some('x')) => 16,
+            (Some('0'), Some('o')) => 8,
+            (Some('0'), Some('b')) => 2,
+            _ => 10,
+        };
+        if radix != 10 {
+            return Literal::Int(self.get_prefixed_int_literal(radix));
+        }
+
+        let start = self.current_loc;
+        let is_digit = |c: char| c.is_ascii_digit() || c == '_';
+        let mut value = self.get_str_while(is_digit).unwrap();
+        let mut is_float = false;
+        // the point must be followed by a digit, so that `0..5` is still a range.
+        if self.is_peek_char(0, '.') && self.is_peek_char_f(1, |c| c.is_ascii_digit()) {
+            value.push(self.next_char().unwrap());
+            value += &self.get_str_while(is_digit).unwrap();
+            is_float = true;
+        }
+        let sign_len = self.is_peek_char_f(1, |c| c == '+' || c == '-') as usize;
+        if self.is_peek_char_f(0, |c| c == 'e' || c == 'E')
+            && self.is_peek_char_f(1 + sign_len, |c| c.is_ascii_digit())
+        {
+            for _ in 0..=sign_len {
+                value.push(self.next_char().unwrap());
+            }
+            value += &self.get_str_while(is_digit).unwrap();
+            is_float = true;
+        }
+        let loc = Span {
+            start,
+            end: self.current_loc.front(),
+        };
+        self.check_separators(&value, loc);
+        let value = value.replace('_', "");
+
+        if is_float {
+            let suffix_start = self.current_loc;
+            if let Some(suffix) = self.get_str_while(|c| char::is_alphanumeric(c) || c == '_') {
+                let loc = Span {
+                    start: suffix_start,
+                    end: self.current_loc.front(),
+                };
+                self.errors
+                    .push(Located(LexError::InvalidFloatSuffix(suffix), loc));
+            }
+            return Literal::Float(value.parse::<f64>().unwrap());
+        }
+
+        let value = self.parse_int_digits(&value, 10, loc);
+        let suffix = self.get_int_suffix();
+        Literal::Int(IntLiteral { value, suffix })
+    }
+
+    /// Reads an integer literal starting with the prefix of its radix, such as `0xff`.
+    fn get_prefixed_int_literal(&mut self, radix: u32) -> IntLiteral {
+        let start = self.current_loc;
+        let prefix: String = [self.next_char().unwrap(), self.next_char().unwrap()]
+            .iter()
+            .collect();
+        // the suffix starts at `i` or `u`, which are not digits in any radix.
+        let digits = self
+            .get_str_while(|c| (c.is_ascii_alphanumeric() && c != 'i' && c != 'u') || c == '_')
+            .unwrap_or_default();
+        let loc = Span {
+            start,
+            end: self.current_loc.front(),
+        };
+
+        self.check_separators(&digits, loc);
+        let digits = digits.replace('_', "");
+        let value = if digits.is_empty() {
+            self.errors
+                .push(Located(LexError::MissingIntegerDigits(prefix), loc));
+            0
+        } else if let Some(digit) = digits.chars().find(|c| !c.is_digit(radix)) {
+            self.errors
+                .push(Located(LexError::InvalidDigit { digit, radix }, loc));
+            0
+        } else {
+            self.parse_int_digits(&digits, radix, loc)
+        };
+        let suffix = self.get_int_suffix();
+        IntLiteral { value, suffix }
+    }
+
+    /// Reports the literal at the location if one of its `_` separators is not followed by a
+    /// digit.
+    fn check_separators(&mut self, digits: &str, loc: Span) {
+        let mut chars = digits.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '_' && !chars.peek().is_some_and(char::is_ascii_alphanumeric) {
+                self.errors.push(Located(LexError::MisplacedSeparator, loc));
+                return;
+            }
+        }
+    }
+
+    /// Returns the value of the digits in the radix, reporting the literal at the location if it
+    /// does not fit into `u64`.
+    fn parse_int_digits(&mut self, digits: &str, radix: u32, loc: Span) -> u64 {
+        u64::from_str_radix(digits, radix).unwrap_or_else(|_| {
+            self.errors.push(Located(LexError::IntegerTooLarge, loc));
+            0
+        })
+    }
+
+    /// Reads the suffix of an integer literal, if any, reporting it if it is not an integer type
+    /// of a fixed size.
+    fn get_int_suffix(&mut self) -> Option<TypeKeyword> {
+        let suffix_start = self.current_loc;
+        let suffix = self.get_str_while(|c| char::is_alphanumeric(c) || c == '_')?;
+        match TypeKeyword::from_str(&suffix) {
+            Ok(kw) if kw.is_sized_int() => Some(kw),
+            _ => {
+                let loc = Span {
+                    start: suffix_start,
+                    end: self.current_loc.front(),
+                };
+                self.errors
+                    .push(Located(LexError::InvalidIntegerSuffix(suffix), loc));
+                None
+            }
+        }
+    }
+
     fn match_str(&mut self, s: &str) -> bool {
         for (i, c) in s.chars().enumerate() {
             if !self.is_peek_char(i, c) {
@@ -247,7 +501,7 @@ impl Lexer {
     }
 
     fn peek_char(&mut self, ahead: usize) -> Option<char> {
-        self.source.chars().nth(self.index + ahead)
+        self.source.get(self.index + ahead).copied()
     }
 
     fn is_peek_char(&mut self, ahead: usize, c: char) -> bool {
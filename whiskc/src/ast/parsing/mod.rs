@@ -5,9 +5,12 @@ use crate::ast::parsing::nodes::ty::TypeParseError;
 use super::{
     location::Located,
     parsing::{
-        lexer::Lexer,
-        nodes::{expr::ExprParseError, item::ItemParseError, stmt::StmtParseError},
-        token::{Delimiter, Keyword, Operator},
+        lexer::{LexError, Lexer},
+        nodes::{
+            expr::ExprParseError, item::ItemParseError, pat::PatternParseError,
+            stmt::StmtParseError,
+        },
+        token::{Delimiter, Keyword, Operator, TokenKind},
     },
 };
 
@@ -21,15 +24,38 @@ pub mod token;
 pub struct ParseContext {
     errors: Vec<Located<ParseError>>,
     pub lexer: Lexer,
+    /// whether a `{` after an expression starts a struct init. It is not allowed in the condition
+    /// of `if` and `while`, where the `{` starts the body instead.
+    allow_struct_init: bool,
 }
 impl ParseContext {
     pub fn new(lexer: Lexer) -> Self {
         Self {
             errors: Vec::new(),
             lexer,
+            allow_struct_init: true,
         }
     }
 
+    /// Runs the parse function with struct inits allowed or not, restoring the previous state
+    /// afterwards.
+    pub fn with_struct_init<T>(
+        &mut self,
+        allow: bool,
+        parse: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let prev = std::mem::replace(&mut self.allow_struct_init, allow);
+        let result = parse(self);
+        self.allow_struct_init = prev;
+        result
+    }
+
+    /// Returns whether an expression must end before the token, rather than continuing with the
+    /// operator it starts.
+    pub fn is_expr_end(&self, kind: &TokenKind) -> bool {
+        !self.allow_struct_init && *kind == TokenKind::Delimiter(Delimiter::BraceOpen)
+    }
+
     //pub fn new_block(&mut self) -> BlockID {
     //    let id = self.block_id_counter;
     //    let parent_id = *self.block_id_stack.last().unwrap();
@@ -54,7 +80,40 @@ impl ParseContext {
         self.errors.push(e);
     }
 
-    pub fn finalize(self) -> Result<(), Vec<Located<ParseError>>> {
+    /// Panic mode recovery, skips tokens until a synchronization token accepted by `is_sync` is
+    /// found outside of any bracket pair, or until the start of the next item.
+    /// The synchronization token itself is not consumed.
+    pub fn synchronize<F>(&mut self, is_sync: F)
+    where
+        F: Fn(&TokenKind) -> bool,
+    {
+        let mut depth: usize = 0;
+        loop {
+            let kind = self.lexer.peek_token_kind(0);
+            if *kind == TokenKind::EndOfFile
+                || (depth == 0 && (is_sync(kind) || is_item_start(kind)))
+            {
+                break;
+            }
+            match kind {
+                TokenKind::Delimiter(
+                    Delimiter::ParenOpen | Delimiter::BraceOpen | Delimiter::BracketOpen,
+                ) => depth += 1,
+                TokenKind::Delimiter(
+                    Delimiter::ParenClose | Delimiter::BraceClose | Delimiter::BracketClose,
+                ) => depth = depth.saturating_sub(1),
+                _ => (),
+            }
+            self.lexer.next_token();
+        }
+    }
+
+    pub fn finalize(mut self) -> Result<(), Vec<Located<ParseError>>> {
+        for Located(e, loc) in self.lexer.take_errors() {
+            self.errors.push(Located(ParseError::LexError(e), loc));
+        }
+        self.errors.sort_by_key(|v| (v.1.start.line, v.1.start.col));
+
         if self.errors.is_empty() {
             Ok(())
         } else {
@@ -65,6 +124,7 @@ impl ParseContext {
 
 #[derive(Debug, Clone)]
 pub enum ParseError {
+    LexError(LexError),
     MissingKeyword(Keyword),
     MissingDelimiter(Delimiter),
     MissingIdentifier(String),
@@ -74,6 +134,16 @@ pub enum ParseError {
     TypeParseError(TypeParseError),
     StmtParseError(StmtParseError),
     ExprParseError(ExprParseError),
+    PatternParseError(PatternParseError),
+}
+
+/// Returns whether the token may start an item, which is where the parser synchronizes after
+/// failing to parse an item.
+pub fn is_item_start(kind: &TokenKind) -> bool {
+    matches!(
+        kind,
+        TokenKind::Keyword(Keyword::Pub | Keyword::Type | Keyword::Extern | Keyword::Func)
+    )
 }
 
 pub type ParseResult<T> = Option<T>;
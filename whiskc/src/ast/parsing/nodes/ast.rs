@@ -3,7 +3,7 @@ use std::path::Path;
 use crate::ast::{
     location::Located,
     nodes::item::Item,
-    parsing::{lexer::Lexer, Parse, ParseContext, ParseError},
+    parsing::{lexer::Lexer, token::TokenKind, Parse, ParseContext, ParseError},
     AST,
 };
 
@@ -11,13 +11,18 @@ pub fn parse(source: &Path) -> Result<AST, Vec<Located<ParseError>>> {
     let mut ctx = ParseContext::new(Lexer::new(source));
 
     let mut items = Vec::new();
-    while !ctx.lexer.is_eof() {
-        let item = Item::parse(&mut ctx);
-        if let Some(item) = item {
+    while *ctx.lexer.peek_token_kind(0) != TokenKind::EndOfFile {
+        let prev_loc = *ctx.lexer.get_prev_loc();
+        if let Some(item) = Item::parse(&mut ctx) {
             items.push(item);
-        } else {
+            continue;
+        }
+
+        // panic mode: skip the rest of the broken item, and resume at the start of the next item.
+        if *ctx.lexer.get_prev_loc() == prev_loc {
             ctx.lexer.next_token();
         }
+        ctx.synchronize(|_| false);
     }
 
     ctx.finalize()?;
@@ -1,16 +1,22 @@
 use once_cell::sync::Lazy;
+use strum::IntoEnumIterator;
 
 use crate::ast::{
     location::{Locatable, Located, Span},
     nodes::{
         expr::*,
+        pat::Pattern,
         punctuate::Punctuated,
         stmt::{ExprStmt, Stmt},
+        ty::Type,
     },
     parsing::{
+        is_item_start,
+        nodes::ty::parse_array_length,
         parsers::pratt_parser::{self, PrattParseError, PrattParseResult, PrattParser},
         token::{
-            Delimiter, Identifier, Keyword, Literal, LiteralKeyword, Operator, Token, TokenKind,
+            Delimiter, Identifier, IntLiteral, Keyword, Label, Literal, LiteralKeyword, Operator,
+            Token, TokenKind, TypeKeyword,
         },
         Parse, ParseContext, ParseError, ParseResult,
     },
@@ -21,6 +27,10 @@ pub enum ExprParseError {
     UnexpectedToken(TokenKind),
     UnexpectedInfixOperator(TokenKind),
     ExpectedIdentForStructInit(Expr),
+    /// a label is not followed by `loop`, `while` or `for`.
+    ExpectedLoopAfterLabel(TokenKind),
+    /// the left of `::` is not a type name.
+    ExpectedTypeNameForPath(Expr),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -30,13 +40,19 @@ enum BindingPower {
     LogicalAdditive,
     LogicalMultiplicative,
     Comparative,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
     Modulus,
     Additive,
     Multiplicative,
+    Cast,
     Unary,
     Call,
     MemberAccess,
     StructInit,
+    Path,
     Primary,
 }
 
@@ -49,14 +65,20 @@ impl pratt_parser::Handlers<Expr, BindingPower> for ExprHandlers {
         let primaries = [
             TokenKind::LiteralKeyword(LiteralKeyword::True),
             TokenKind::LiteralKeyword(LiteralKeyword::False),
-            TokenKind::Literal(Literal::Int(0)),
+            TokenKind::Literal(Literal::Int(IntLiteral::default())),
+            TokenKind::Literal(Literal::Float(0.0)),
+            TokenKind::Literal(Literal::Str(String::new())),
             TokenKind::Identifier(Identifier("".into())),
         ];
         for primary in primaries {
             nud(primary, parse_primary_expr);
         }
+        // primitive type names are only valid as the callee of a conversion, such as `int(v)`.
+        for kw in TypeKeyword::iter() {
+            nud(TokenKind::TypeKeyword(kw), parse_primary_expr);
+        }
 
-        let prefix_unary_ops = [Operator::Sub, Operator::Not];
+        let prefix_unary_ops = [Operator::Sub, Operator::Not, Operator::BitNot];
         for op in prefix_unary_ops {
             nud(TokenKind::Operator(op), parse_prefix_unary_expr);
         }
@@ -68,9 +90,23 @@ impl pratt_parser::Handlers<Expr, BindingPower> for ExprHandlers {
 
         nud(TokenKind::Delimiter(Delimiter::BraceOpen), parse_block_expr);
 
+        nud(
+            TokenKind::Delimiter(Delimiter::BracketOpen),
+            parse_array_expr,
+        );
+
         nud(TokenKind::Keyword(Keyword::Return), parse_return_expr);
         nud(TokenKind::Keyword(Keyword::If), parse_if_expr);
         nud(TokenKind::Keyword(Keyword::Loop), parse_loop_expr);
+        nud(TokenKind::Keyword(Keyword::While), parse_while_expr);
+        nud(TokenKind::Keyword(Keyword::For), parse_for_expr);
+        nud(TokenKind::Keyword(Keyword::Break), parse_break_expr);
+        nud(TokenKind::Keyword(Keyword::Continue), parse_continue_expr);
+        nud(
+            TokenKind::Label(Label(String::new())),
+            parse_labeled_loop_expr,
+        );
+        nud(TokenKind::Keyword(Keyword::Match), parse_match_expr);
     }
 
     fn leds<F>(&self, mut led: F)
@@ -84,6 +120,10 @@ impl pratt_parser::Handlers<Expr, BindingPower> for ExprHandlers {
                 vec![Operator::Mul, Operator::Div],
             ),
             (BindingPower::Modulus, vec![Operator::Mod]),
+            (BindingPower::Shift, vec![Operator::Shl, Operator::Shr]),
+            (BindingPower::BitAnd, vec![Operator::BitAnd]),
+            (BindingPower::BitXor, vec![Operator::BitXor]),
+            (BindingPower::BitOr, vec![Operator::BitOr]),
             (BindingPower::LogicalMultiplicative, vec![Operator::And]),
             (BindingPower::LogicalAdditive, vec![Operator::Or]),
             (
@@ -105,12 +145,24 @@ impl pratt_parser::Handlers<Expr, BindingPower> for ExprHandlers {
             }
         }
 
+        led(
+            TokenKind::Operator(Operator::As),
+            BindingPower::Cast,
+            parse_cast_expr,
+        );
+
         led(
             TokenKind::Delimiter(Delimiter::ParenOpen),
             BindingPower::Call,
             parse_call_expr,
         );
 
+        led(
+            TokenKind::Delimiter(Delimiter::BracketOpen),
+            BindingPower::Call,
+            parse_index_expr,
+        );
+
         led(
             TokenKind::Delimiter(Delimiter::BraceOpen),
             BindingPower::StructInit,
@@ -122,6 +174,12 @@ impl pratt_parser::Handlers<Expr, BindingPower> for ExprHandlers {
             BindingPower::MemberAccess,
             parse_member_access_expr,
         );
+
+        led(
+            TokenKind::Operator(Operator::PathSep),
+            BindingPower::Path,
+            parse_path_expr,
+        );
     }
 }
 
@@ -154,12 +212,15 @@ fn parse_primary_expr(
     Some(match tok.kind {
         TokenKind::Literal(lit) => match lit {
             Literal::Int(v) => Expr::Integer(Located(v, tok.loc)),
+            Literal::Float(v) => Expr::Float(Located(v, tok.loc)),
+            Literal::Str(v) => Expr::Str(Located(v, tok.loc)),
         },
         TokenKind::LiteralKeyword(kw) => match kw {
             LiteralKeyword::True => Expr::Bool(Located(true, tok.loc)),
             LiteralKeyword::False => Expr::Bool(Located(false, tok.loc)),
         },
         TokenKind::Identifier(Identifier(ident)) => Expr::Ident(Located(ident, tok.loc)),
+        TokenKind::TypeKeyword(kw) => Expr::Ident(Located(kw.to_string(), tok.loc)),
         _ => unimplemented!("{:#?}", tok),
     })
 }
@@ -205,6 +266,21 @@ fn parse_binary_expr(
     }))
 }
 
+fn parse_cast_expr(
+    _pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+    left: Expr,
+    _bp: BindingPower,
+) -> ParseResult<Expr> {
+    let as_tok = match_operator!(parser, Operator::As =>);
+    let ty = Type::parse(parser)?;
+    Some(Expr::Cast(CastExpr {
+        expr: Box::new(left),
+        as_tok,
+        ty,
+    }))
+}
+
 fn parse_call_expr(
     _pratt_parser: &PrattParser<Expr, BindingPower>,
     parser: &mut ParseContext,
@@ -222,6 +298,73 @@ fn parse_call_expr(
     }))
 }
 
+fn parse_index_expr(
+    _pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+    left: Expr,
+    _bp: BindingPower,
+) -> ParseResult<Expr> {
+    let bracket_open_tok = match_delimiter!(parser, Delimiter::BracketOpen =>);
+    let index = Expr::parse(parser)?;
+    let bracket_close_tok = match_delimiter!(parser, Delimiter::BracketClose =>);
+    Some(Expr::Index(IndexExpr {
+        expr: Box::new(left),
+        bracket_open_tok,
+        index: Box::new(index),
+        bracket_close_tok,
+    }))
+}
+
+/// Parses an array literal, or an array repeat expression if the first element is followed by
+/// `;`.
+fn parse_array_expr(
+    _pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+) -> ParseResult<Expr> {
+    let bracket_open_tok = match_delimiter!(parser, Delimiter::BracketOpen =>);
+    let mut items = Vec::new();
+    if !matches!(
+        parser.lexer.peek_token_kind(0),
+        TokenKind::Delimiter(Delimiter::BracketClose)
+    ) {
+        let first = Expr::parse(parser)?;
+        if let Ok(semi_tok) = match_delimiter!(parser, Delimiter::Semicolon) {
+            let len = parse_array_length(parser)?;
+            let bracket_close_tok = match_delimiter!(parser, Delimiter::BracketClose =>);
+            return Some(Expr::ArrayRepeat(ArrayRepeatExpr {
+                bracket_open_tok,
+                expr: Box::new(first),
+                semi_tok,
+                len,
+                bracket_close_tok,
+            }));
+        }
+        items.push(first);
+        if !matches!(
+            parser.lexer.peek_token_kind(0),
+            TokenKind::Delimiter(Delimiter::BracketClose)
+        ) {
+            match_delimiter!(parser, Delimiter::Comma =>);
+        }
+    }
+    let rest = Punctuated::parse(
+        parser,
+        Delimiter::Comma,
+        Delimiter::BracketClose,
+        Expr::parse,
+    )?;
+    items.extend(rest.items);
+    let bracket_close_tok = match_delimiter!(parser, Delimiter::BracketClose =>);
+    Some(Expr::Array(ArrayExpr {
+        bracket_open_tok,
+        elements: Punctuated {
+            items,
+            sep: Delimiter::Comma,
+        },
+        bracket_close_tok,
+    }))
+}
+
 fn parse_block_expr(
     _pratt_parser: &PrattParser<Expr, BindingPower>,
     parser: &mut ParseContext,
@@ -233,7 +376,9 @@ fn parse_block_expr(
         if let Ok(b) = match_delimiter!(parser, Delimiter::BraceClose) {
             break b;
         }
-        if match_token_kind!(parser, TokenKind::EndOfFile).is_some() {
+        if match_token_kind!(parser, TokenKind::EndOfFile).is_some()
+            || is_item_start(parser.lexer.peek_token_kind(0))
+        {
             break match_delimiter!(parser, Delimiter::BraceClose =>);
         }
 
@@ -243,14 +388,13 @@ fn parse_block_expr(
             continue;
         }
 
-        // panic mode: skip until next semicolon or brace_close or EOF
-        while !matches!(
-            parser.lexer.peek_token_kind(0),
-            TokenKind::EndOfFile
-                | TokenKind::Delimiter(Delimiter::Semicolon | Delimiter::BraceClose)
-        ) {
-            parser.lexer.next_token();
-        }
+        // panic mode: skip until next semicolon or brace_close or the next item or EOF
+        parser.synchronize(|kind| {
+            matches!(
+                kind,
+                TokenKind::Delimiter(Delimiter::Semicolon | Delimiter::BraceClose)
+            )
+        });
 
         // panic mode: found semicolon, proceed to next stmt
         if matches!(
@@ -302,7 +446,53 @@ fn parse_return_expr(
     parser: &mut ParseContext,
 ) -> ParseResult<Expr> {
     let return_tok = match_keyword!(parser, Keyword::Return =>);
-    let expr = if matches!(
+    let expr = parse_optional_operand(parser)?;
+    Some(Expr::Return(ReturnExpr { return_tok, expr }))
+}
+
+fn parse_break_expr(
+    _pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+) -> ParseResult<Expr> {
+    let break_tok = match_keyword!(parser, Keyword::Break =>);
+    let label = parse_optional_label(parser);
+    let expr = parse_optional_operand(parser)?;
+    Some(Expr::Break(BreakExpr {
+        break_tok,
+        label,
+        expr,
+    }))
+}
+
+fn parse_continue_expr(
+    _pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+) -> ParseResult<Expr> {
+    let continue_tok = match_keyword!(parser, Keyword::Continue =>);
+    let label = parse_optional_label(parser);
+    Some(Expr::Continue(ContinueExpr {
+        continue_tok,
+        label,
+    }))
+}
+
+/// Parses the label following `break` or `continue`, if any.
+fn parse_optional_label(parser: &mut ParseContext) -> Option<Located<String>> {
+    if let Some(Token {
+        kind: TokenKind::Label(Label(name)),
+        loc,
+    }) = match_token_kind!(parser, TokenKind::Label(_))
+    {
+        Some(Located(name, loc))
+    } else {
+        None
+    }
+}
+
+/// Parses the operand of `return` or `break`, which is absent if the expression ends right after
+/// the keyword.
+fn parse_optional_operand(parser: &mut ParseContext) -> ParseResult<Option<Box<Expr>>> {
+    if matches!(
         parser.lexer.peek_token_kind(0),
         TokenKind::Delimiter(
             Delimiter::Semicolon
@@ -312,12 +502,10 @@ fn parse_return_expr(
                 | Delimiter::Comma
         )
     ) {
-        None
+        Some(None)
     } else {
-        Some(Box::new(Expr::parse(parser)?))
-    };
-
-    Some(Expr::Return(ReturnExpr { return_tok, expr }))
+        Some(Some(Box::new(Expr::parse(parser)?)))
+    }
 }
 
 fn parse_if_expr(
@@ -325,7 +513,7 @@ fn parse_if_expr(
     parser: &mut ParseContext,
 ) -> ParseResult<Expr> {
     let if_tok = match_keyword!(parser, Keyword::If =>);
-    let cond = Expr::parse(parser)?;
+    let cond = Expr::parse_cond(parser)?;
     let Expr::Block(block) = parse_block_expr(pratt_parser, parser)? else {
         unreachable!();
     };
@@ -348,13 +536,19 @@ fn parse_else_expr(
     parser: &mut ParseContext,
 ) -> ParseResult<ElseExpr> {
     let else_tok = match_keyword!(parser, Keyword::Else =>);
-    let Expr::Block(block) = parse_block_expr(pratt_parser, parser)? else {
-        unreachable!()
+    let body = if matches!(parser.lexer.peek_token_kind(0), TokenKind::Keyword(kw) if *kw == Keyword::If)
+    {
+        let Expr::If(if_expr) = parse_if_expr(pratt_parser, parser)? else {
+            unreachable!()
+        };
+        ElseBody::If(Box::new(if_expr))
+    } else {
+        let Expr::Block(block) = parse_block_expr(pratt_parser, parser)? else {
+            unreachable!()
+        };
+        ElseBody::Block(block)
     };
-    Some(ElseExpr {
-        else_tok,
-        body: block,
-    })
+    Some(ElseExpr { else_tok, body })
 }
 
 fn parse_loop_expr(
@@ -366,24 +560,167 @@ fn parse_loop_expr(
         unreachable!()
     };
     Some(Expr::Loop(LoopExpr {
+        label: None,
         loop_tok,
         body: block,
     }))
 }
 
+fn parse_while_expr(
+    pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+) -> ParseResult<Expr> {
+    let while_tok = match_keyword!(parser, Keyword::While =>);
+    let cond = Expr::parse_cond(parser)?;
+    let Expr::Block(block) = parse_block_expr(pratt_parser, parser)? else {
+        unreachable!()
+    };
+    Some(Expr::While(WhileExpr {
+        label: None,
+        while_tok,
+        cond: Box::new(cond),
+        body: block,
+    }))
+}
+
+fn parse_for_expr(
+    pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+) -> ParseResult<Expr> {
+    let for_tok = match_keyword!(parser, Keyword::For =>);
+    let var = match_identifier!(parser, "loop variable".to_owned() =>)?;
+    let in_tok = match_keyword!(parser, Keyword::In =>);
+    let iter = Expr::parse_cond(parser)?;
+    let Expr::Block(block) = parse_block_expr(pratt_parser, parser)? else {
+        unreachable!()
+    };
+    Some(Expr::For(ForExpr {
+        label: None,
+        for_tok,
+        var,
+        in_tok,
+        iter: Box::new(iter),
+        body: block,
+    }))
+}
+
+fn parse_labeled_loop_expr(
+    pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+) -> ParseResult<Expr> {
+    let Token {
+        kind: TokenKind::Label(Label(name)),
+        loc,
+    } = parser.lexer.next_token()
+    else {
+        unreachable!()
+    };
+    let colon_tok = match_delimiter!(parser, Delimiter::Colon =>);
+    let label = Some(LoopLabel {
+        name: Located(name, loc),
+        colon_tok,
+    });
+
+    match parser.lexer.peek_token_kind(0) {
+        TokenKind::Keyword(Keyword::Loop) => {
+            let Expr::Loop(expr) = parse_loop_expr(pratt_parser, parser)? else {
+                unreachable!()
+            };
+            Some(Expr::Loop(LoopExpr { label, ..expr }))
+        }
+        TokenKind::Keyword(Keyword::While) => {
+            let Expr::While(expr) = parse_while_expr(pratt_parser, parser)? else {
+                unreachable!()
+            };
+            Some(Expr::While(WhileExpr { label, ..expr }))
+        }
+        TokenKind::Keyword(Keyword::For) => {
+            let Expr::For(expr) = parse_for_expr(pratt_parser, parser)? else {
+                unreachable!()
+            };
+            Some(Expr::For(ForExpr { label, ..expr }))
+        }
+        _ => {
+            let tok = parser.lexer.peek_token(0).clone();
+            parser.push_error(Located(
+                ParseError::ExprParseError(ExprParseError::ExpectedLoopAfterLabel(tok.kind)),
+                tok.loc,
+            ));
+            None
+        }
+    }
+}
+
+fn parse_match_expr(
+    _pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+) -> ParseResult<Expr> {
+    let match_tok = match_keyword!(parser, Keyword::Match =>);
+    let expr = Expr::parse_cond(parser)?;
+    let brace_open_tok = match_delimiter!(parser, Delimiter::BraceOpen =>);
+
+    let mut arms = Vec::new();
+    let brace_close_tok = loop {
+        if let Ok(b) = match_delimiter!(parser, Delimiter::BraceClose) {
+            break b;
+        }
+        let arm = parse_match_arm(parser)?;
+        // the comma between arms may only be omitted after a block.
+        let ends_arm = arm.comma_tok.is_some() || arm.expr.is_block();
+        arms.push(arm);
+        if !ends_arm {
+            break match_delimiter!(parser, Delimiter::BraceClose =>);
+        }
+    };
+
+    Some(Expr::Match(MatchExpr {
+        match_tok,
+        expr: Box::new(expr),
+        brace_open_tok,
+        arms,
+        brace_close_tok,
+    }))
+}
+
+fn parse_match_arm(parser: &mut ParseContext) -> ParseResult<MatchArm> {
+    let pat = Pattern::parse(parser)?;
+    let guard = if let Ok(if_tok) = match_keyword!(parser, Keyword::If) {
+        Some(MatchGuard {
+            if_tok,
+            cond: Box::new(Expr::parse(parser)?),
+        })
+    } else {
+        None
+    };
+    let arrow_tok = match_operator!(parser, Operator::FatArrow =>);
+    let expr = Expr::parse(parser)?;
+    let comma_tok = match_delimiter!(parser, Delimiter::Comma).ok();
+    Some(MatchArm {
+        pat,
+        guard,
+        arrow_tok,
+        expr,
+        comma_tok,
+    })
+}
+
 fn parse_struct_init_expr(
     _pratt_parser: &PrattParser<Expr, BindingPower>,
     parser: &mut ParseContext,
     left: Expr,
     _bp: BindingPower,
 ) -> ParseResult<Expr> {
-    let Expr::Ident(ident) = left else {
-        let loc = left.get_location();
-        parser.push_error(Located(
-            ParseError::ExprParseError(ExprParseError::ExpectedIdentForStructInit(left)),
-            loc,
-        ));
-        return None;
+    let (ty_name, variant) = match left {
+        Expr::Ident(ident) => (ident, None),
+        Expr::Path(path) => (path.ty_name, Some(path.name)),
+        _ => {
+            let loc = left.get_location();
+            parser.push_error(Located(
+                ParseError::ExprParseError(ExprParseError::ExpectedIdentForStructInit(left)),
+                loc,
+            ));
+            return None;
+        }
     };
 
     let brace_open_tok = match_delimiter!(parser, Delimiter::BraceOpen =>);
@@ -396,13 +733,39 @@ fn parse_struct_init_expr(
     let brace_close_tok = match_delimiter!(parser, Delimiter::BraceClose =>);
 
     Some(Expr::StructInit(StructInitExpr {
-        ty_name: ident,
+        ty_name,
+        variant,
         brace_open_tok,
         fields,
         brace_close_tok,
     }))
 }
 
+fn parse_path_expr(
+    _pratt_parser: &PrattParser<Expr, BindingPower>,
+    parser: &mut ParseContext,
+    left: Expr,
+    _bp: BindingPower,
+) -> ParseResult<Expr> {
+    let Expr::Ident(ty_name) = left else {
+        let loc = left.get_location();
+        parser.push_error(Located(
+            ParseError::ExprParseError(ExprParseError::ExpectedTypeNameForPath(left)),
+            loc,
+        ));
+        return None;
+    };
+
+    let sep_tok = match_operator!(parser, Operator::PathSep =>);
+    let name = match_identifier!(parser, "variant name".to_owned() =>)?;
+
+    Some(Expr::Path(PathExpr {
+        ty_name,
+        sep_tok,
+        name,
+    }))
+}
+
 impl Parse for FieldInit {
     fn parse(ctx: &mut ParseContext) -> ParseResult<Self> {
         let field_name = match_identifier!(ctx, "field name".to_owned() =>)?;
@@ -434,6 +797,16 @@ fn parse_member_access_expr(
 
 impl Expr {
     pub fn parse(parser: &mut ParseContext) -> ParseResult<Expr> {
+        parser.with_struct_init(true, Self::parse_any)
+    }
+
+    /// Parses the condition of `if` or `while`, where a struct init must be grouped in parentheses
+    /// so that the `{` of the body is not taken as the start of its fields.
+    pub fn parse_cond(parser: &mut ParseContext) -> ParseResult<Expr> {
+        parser.with_struct_init(false, Self::parse_any)
+    }
+
+    fn parse_any(parser: &mut ParseContext) -> ParseResult<Expr> {
         static EXPR_PARSER: Lazy<PrattParser<Expr, BindingPower>> =
             Lazy::new(|| PrattParser::new(&ExprHandlers));
         Self::handle_err(EXPR_PARSER.parse(parser, BindingPower::Zero), parser)
@@ -56,9 +56,11 @@ impl Parse for FunctionSig {
         })?;
         let paren_close_tok = match_delimiter!(ctx, Delimiter::ParenClose =>);
 
+        // the return type is omitted for functions returning unit, before the body of functions
+        // or the semicolon of extern functions.
         let ret_ty = if matches!(
             ctx.lexer.peek_token_kind(0),
-            TokenKind::Delimiter(Delimiter::BraceOpen)
+            TokenKind::Delimiter(Delimiter::BraceOpen | Delimiter::Semicolon)
         ) {
             Type::Primitive(Located::new_temp(PrimType::Unit))
         } else {
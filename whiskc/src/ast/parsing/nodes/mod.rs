@@ -1,10 +1,10 @@
 pub mod ast;
 pub(super) mod attributes;
-pub(super) mod expr;
-pub(super) mod func;
-pub(super) mod item;
+pub(crate) mod expr;
+pub(crate) mod func;
+pub(crate) mod item;
+pub(crate) mod pat;
 pub(super) mod punctuate;
-pub(super) mod stmt;
-pub(super) mod ty;
+pub(crate) mod stmt;
+pub(crate) mod ty;
 pub(super) mod ty_decl;
-
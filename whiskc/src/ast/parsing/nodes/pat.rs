@@ -0,0 +1,179 @@
+use crate::ast::{
+    location::{Located, Span},
+    nodes::{
+        pat::{
+            FieldPattern, IntBound, Pattern, RangePattern, VariantPattern, VariantPatternFields,
+        },
+        punctuate::Punctuated,
+    },
+    parsing::{
+        token::{Delimiter, Identifier, Literal, LiteralKeyword, Operator, Token, TokenKind},
+        Parse, ParseContext, ParseError, ParseResult,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub enum PatternParseError {
+    UnexpectedToken(TokenKind),
+    /// a negated value, or the end of a range, is not an integer.
+    ExpectedInteger(TokenKind),
+}
+
+impl Parse for Pattern {
+    fn parse(parser: &mut ParseContext) -> ParseResult<Self> {
+        match parser.lexer.peek_token_kind(0) {
+            TokenKind::Identifier(_) => {
+                let Token {
+                    kind: TokenKind::Identifier(Identifier(ident)),
+                    loc,
+                } = parser.lexer.next_token()
+                else {
+                    unreachable!()
+                };
+                if let Ok(sep_tok) = match_operator!(parser, Operator::PathSep) {
+                    return parse_variant_pattern(parser, Located(ident, loc), sep_tok);
+                }
+                Some(if ident == "_" {
+                    Pattern::Wildcard(loc)
+                } else {
+                    Pattern::Ident(Located(ident, loc))
+                })
+            }
+            TokenKind::LiteralKeyword(kw) => {
+                let value = *kw == LiteralKeyword::True;
+                let loc = parser.lexer.next_token().loc;
+                Some(Pattern::Bool(Located(value, loc)))
+            }
+            TokenKind::Operator(Operator::Range | Operator::RangeInclusive) => {
+                let op = parse_range_op(parser).expect("range operator");
+                let end = parse_int_bound(parser)?;
+                Some(Pattern::Range(RangePattern {
+                    start: None,
+                    op,
+                    end: Some(end),
+                }))
+            }
+            TokenKind::Literal(Literal::Int(_)) | TokenKind::Operator(Operator::Sub) => {
+                let start = parse_int_bound(parser)?;
+                let Some(op) = parse_range_op(parser) else {
+                    return Some(Pattern::Integer(start));
+                };
+                // the end may only be left out of an exclusive range.
+                let end = if op.0 == Operator::Range
+                    && !matches!(
+                        parser.lexer.peek_token_kind(0),
+                        TokenKind::Literal(Literal::Int(_)) | TokenKind::Operator(Operator::Sub)
+                    ) {
+                    None
+                } else {
+                    Some(parse_int_bound(parser)?)
+                };
+                Some(Pattern::Range(RangePattern {
+                    start: Some(start),
+                    op,
+                    end,
+                }))
+            }
+            _ => {
+                let tok = parser.lexer.peek_token(0).clone();
+                parser.push_error(Located(
+                    ParseError::PatternParseError(PatternParseError::UnexpectedToken(tok.kind)),
+                    tok.loc,
+                ));
+                None
+            }
+        }
+    }
+}
+
+fn parse_variant_pattern(
+    parser: &mut ParseContext,
+    ty_name: Located<String>,
+    sep_tok: Located<Operator>,
+) -> ParseResult<Pattern> {
+    let name = match_identifier!(parser, "variant name".to_owned() =>)?;
+    let fields = if let Ok(paren_open_tok) = match_delimiter!(parser, Delimiter::ParenOpen) {
+        let pats = Punctuated::parse(
+            parser,
+            Delimiter::Comma,
+            Delimiter::ParenClose,
+            Pattern::parse,
+        )?;
+        let paren_close_tok = match_delimiter!(parser, Delimiter::ParenClose =>);
+        VariantPatternFields::Tuple {
+            paren_open_tok,
+            pats,
+            paren_close_tok,
+        }
+    } else if let Ok(brace_open_tok) = match_delimiter!(parser, Delimiter::BraceOpen) {
+        let fields = Punctuated::parse(
+            parser,
+            Delimiter::Comma,
+            Delimiter::BraceClose,
+            FieldPattern::parse,
+        )?;
+        let brace_close_tok = match_delimiter!(parser, Delimiter::BraceClose =>);
+        VariantPatternFields::Struct {
+            brace_open_tok,
+            fields,
+            brace_close_tok,
+        }
+    } else {
+        VariantPatternFields::Unit
+    };
+    Some(Pattern::Variant(VariantPattern {
+        ty_name,
+        sep_tok,
+        name,
+        fields,
+    }))
+}
+
+impl Parse for FieldPattern {
+    fn parse(parser: &mut ParseContext) -> ParseResult<Self> {
+        let field_name = match_identifier!(parser, "field name".to_owned() =>)?;
+        let pat = if let Ok(colon_tok) = match_delimiter!(parser, Delimiter::Colon) {
+            Some((colon_tok, Pattern::parse(parser)?))
+        } else {
+            None
+        };
+        Some(Self { field_name, pat })
+    }
+}
+
+fn parse_range_op(parser: &mut ParseContext) -> Option<Located<Operator>> {
+    let op = match_token_kind!(
+        parser,
+        TokenKind::Operator(Operator::Range | Operator::RangeInclusive)
+    )?;
+    let TokenKind::Operator(op_kind) = op.kind else {
+        unreachable!()
+    };
+    Some(Located(op_kind, op.loc))
+}
+
+/// Parses an integer literal, which may be negated, as the value of a pattern.
+fn parse_int_bound(parser: &mut ParseContext) -> ParseResult<Located<IntBound>> {
+    let neg_tok = match_token_kind!(parser, TokenKind::Operator(Operator::Sub));
+    let tok = parser.lexer.peek_token(0).clone();
+    let TokenKind::Literal(Literal::Int(lit)) = tok.kind else {
+        parser.push_error(Located(
+            ParseError::PatternParseError(PatternParseError::ExpectedInteger(tok.kind)),
+            tok.loc,
+        ));
+        return None;
+    };
+    parser.lexer.next_token();
+
+    let (value, loc) = match neg_tok {
+        Some(neg_tok) => (-(lit.value as i128), Span::combine(neg_tok.loc, tok.loc)),
+        None => (lit.value as i128, tok.loc),
+    };
+    Some(Located(
+        IntBound {
+            value,
+            suffix: lit.suffix,
+        },
+        loc,
+    ))
+}
@@ -1,10 +1,15 @@
+use crate::ast::location::Located;
 use crate::ast::nodes::punctuate::Punctuated;
 use crate::ast::parsing::{
+    is_item_start,
     token::{Delimiter, TokenKind},
-    ParseContext, ParseResult,
+    ParseContext, ParseError, ParseResult,
 };
 
 impl<T> Punctuated<T> {
+    /// Parses the items up to the closing delimiter, which is left for the caller to match.
+    /// Returns `None` if the list is given up on before the closing delimiter, in which case the
+    /// error has already been reported.
     pub fn parse<F>(
         parser: &mut ParseContext,
         sep: Delimiter,
@@ -14,12 +19,44 @@ impl<T> Punctuated<T> {
     where
         F: FnMut(&mut ParseContext) -> ParseResult<T>,
     {
+        let is_delim = |kind: &TokenKind| matches!(kind, TokenKind::Delimiter(d) if *d == delim);
         let mut items = Vec::new();
-        while !matches!(parser.lexer.peek_token_kind(0), TokenKind::Delimiter(d) if *d == delim) {
-            items.push(parse_fn(parser)?);
+        while !is_delim(parser.lexer.peek_token_kind(0)) {
+            if let Some(item) = parse_fn(parser) {
+                items.push(item);
+                if is_delim(parser.lexer.peek_token_kind(0))
+                    || match_delimiter!(parser, sep).is_ok()
+                {
+                    continue;
+                }
 
-            if !matches!(parser.lexer.peek_token_kind(0), TokenKind::Delimiter(d) if *d == delim) {
-                match_delimiter!(parser, sep =>);
+                // a token which cannot continue the list means it is missing its closing
+                // delimiter, rather than a separator between items.
+                let missing = match parser.lexer.peek_token_kind(0) {
+                    TokenKind::Delimiter(
+                        Delimiter::Semicolon
+                        | Delimiter::ParenClose
+                        | Delimiter::BraceClose
+                        | Delimiter::BracketClose,
+                    )
+                    | TokenKind::EndOfFile => delim,
+                    kind if is_item_start(kind) => delim,
+                    _ => sep,
+                };
+                let loc = parser.lexer.get_prev_loc().next().into();
+                parser.push_error(Located(ParseError::MissingDelimiter(missing), loc));
+            }
+
+            // panic mode: skip to the next separator and resume, or give up on the rest of the
+            // list if the end of the statement is found first.
+            parser.synchronize(|kind| {
+                matches!(kind, TokenKind::Delimiter(d) if *d == sep || *d == delim || *d == Delimiter::Semicolon)
+            });
+            if match_delimiter!(parser, sep).is_ok() {
+                continue;
+            }
+            if !is_delim(parser.lexer.peek_token_kind(0)) {
+                return None;
             }
         }
 
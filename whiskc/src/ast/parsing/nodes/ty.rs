@@ -5,11 +5,11 @@ use crate::ast::{
     location::{Located, Span},
     nodes::{
         punctuate::Punctuated,
-        ty::{Field, PrimType, Struct, Type},
+        ty::{ArrayType, Enum, Field, PrimType, Struct, Type, Variant, VariantKind, VecType},
     },
     parsing::{
         parsers::pratt_parser::{self, PrattParseError, PrattParser},
-        token::{Delimiter, Identifier, Keyword, Token, TokenKind, TypeKeyword},
+        token::{Delimiter, Identifier, Keyword, Literal, Operator, Token, TokenKind, TypeKeyword},
         Parse, ParseContext, ParseError, ParseResult, TryParse,
     },
 };
@@ -20,7 +20,7 @@ pub enum TypeParseError {
     UnexpectedInfixOperator(TokenKind),
     IntegerSizeOutOfRange(u16),
     ExpectedArrayLength,
-    InvalidArrayLength(i64),
+    InvalidArrayLength(i128),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -49,6 +49,10 @@ impl pratt_parser::Handlers<Type, BindingPower> for TypeHandlers {
         nud(TokenKind::Delimiter(Delimiter::ParenOpen), |_, parser| {
             parse_unit_type(parser)
         });
+
+        nud(TokenKind::Delimiter(Delimiter::BracketOpen), |_, parser| {
+            parse_array_type(parser)
+        });
     }
 
     fn leds<F>(&self, _led: F)
@@ -67,6 +71,54 @@ fn parse_unit_type(parser: &mut ParseContext) -> ParseResult<Type> {
     )))
 }
 
+fn parse_array_type(parser: &mut ParseContext) -> ParseResult<Type> {
+    let bracket_open_tok = match_delimiter!(parser, Delimiter::BracketOpen =>);
+    let elem_ty = Type::parse(parser)?;
+    if let Ok(bracket_close_tok) = match_delimiter!(parser, Delimiter::BracketClose) {
+        return Some(Type::Vec(VecType {
+            bracket_open_tok,
+            elem_ty: Box::new(elem_ty),
+            bracket_close_tok,
+        }));
+    }
+    let semi_tok = match_delimiter!(parser, Delimiter::Semicolon =>);
+    let len = parse_array_length(parser)?;
+    let bracket_close_tok = match_delimiter!(parser, Delimiter::BracketClose =>);
+    Some(Type::Array(ArrayType {
+        bracket_open_tok,
+        elem_ty: Box::new(elem_ty),
+        semi_tok,
+        len,
+        bracket_close_tok,
+    }))
+}
+
+/// Parses the length of an array, which must be an integer literal. It is shared with array
+/// repeat expressions such as `[0; 4]`.
+/// An invalid length is reported and parsed as 0, so that the rest of the array is still parsed.
+pub fn parse_array_length(parser: &mut ParseContext) -> ParseResult<Located<usize>> {
+    let negative = match_operator!(parser, Operator::Sub).ok();
+    let tok = parser.lexer.peek_token(0).clone();
+    let TokenKind::Literal(Literal::Int(len)) = tok.kind else {
+        parser.push_error(Located(
+            ParseError::TypeParseError(TypeParseError::ExpectedArrayLength),
+            tok.loc,
+        ));
+        parser.synchronize(|kind| *kind == TokenKind::Delimiter(Delimiter::BracketClose));
+        return Some(Located(0, tok.loc));
+    };
+    parser.lexer.next_token();
+    if let Some(minus_tok) = negative {
+        let loc = Span::combine(minus_tok.1, tok.loc);
+        parser.push_error(Located(
+            ParseError::TypeParseError(TypeParseError::InvalidArrayLength(-(len.value as i128))),
+            loc,
+        ));
+        return Some(Located(0, loc));
+    }
+    Some(Located(len.value as usize, tok.loc))
+}
+
 fn parse_keyword_type(parser: &mut ParseContext) -> ParseResult<Type> {
     let Token {
         kind: TokenKind::TypeKeyword(kw),
@@ -76,12 +128,7 @@ fn parse_keyword_type(parser: &mut ParseContext) -> ParseResult<Type> {
         panic!("token should be a type keyword");
     };
 
-    let prim_ty = match kw {
-        TypeKeyword::Bool => PrimType::Bool,
-        TypeKeyword::Int => PrimType::Int,
-    };
-
-    Some(Type::Primitive(Located(prim_ty, loc)))
+    Some(Type::Primitive(Located(PrimType::from(kw), loc)))
 }
 
 fn parse_ident_type(parser: &mut ParseContext) -> ParseResult<Type> {
@@ -114,6 +161,52 @@ pub fn parse_struct_type(parser: &mut ParseContext) -> ParseResult<Struct> {
     })
 }
 
+pub fn parse_enum_type(parser: &mut ParseContext) -> ParseResult<Enum> {
+    let enum_tok = match_keyword!(parser, Keyword::Enum =>);
+    let brace_open_tok = match_delimiter!(parser, Delimiter::BraceOpen =>);
+    let variants = Punctuated::parse(
+        parser,
+        Delimiter::Comma,
+        Delimiter::BraceClose,
+        Variant::parse,
+    )?;
+    let brace_close_tok = match_delimiter!(parser, Delimiter::BraceClose =>);
+    Some(Enum {
+        enum_tok,
+        brace_open_tok,
+        variants,
+        brace_close_tok,
+    })
+}
+
+impl Parse for Variant {
+    fn parse(ctx: &mut ParseContext) -> ParseResult<Self> {
+        let name = match_identifier!(ctx, "variant's name".to_owned() =>)?;
+        let kind = if let Ok(paren_open_tok) = match_delimiter!(ctx, Delimiter::ParenOpen) {
+            let types =
+                Punctuated::parse(ctx, Delimiter::Comma, Delimiter::ParenClose, Type::parse)?;
+            let paren_close_tok = match_delimiter!(ctx, Delimiter::ParenClose =>);
+            VariantKind::Tuple {
+                paren_open_tok,
+                types,
+                paren_close_tok,
+            }
+        } else if let Ok(brace_open_tok) = match_delimiter!(ctx, Delimiter::BraceOpen) {
+            let fields =
+                Punctuated::parse(ctx, Delimiter::Comma, Delimiter::BraceClose, Field::parse)?;
+            let brace_close_tok = match_delimiter!(ctx, Delimiter::BraceClose =>);
+            VariantKind::Struct {
+                brace_open_tok,
+                fields,
+                brace_close_tok,
+            }
+        } else {
+            VariantKind::Unit
+        };
+        Some(Variant { name, kind })
+    }
+}
+
 impl Parse for Field {
     fn parse(ctx: &mut ParseContext) -> ParseResult<Self> {
         let name = match_identifier!(ctx, "field's name".to_owned() =>)?;
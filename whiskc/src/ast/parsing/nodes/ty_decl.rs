@@ -9,7 +9,7 @@ use crate::ast::{
     },
 };
 
-use super::ty::parse_struct_type;
+use super::ty::{parse_enum_type, parse_struct_type};
 
 impl Parse for TypeDecl {
     fn parse(ctx: &mut ParseContext) -> ParseResult<Self> {
@@ -17,13 +17,10 @@ impl Parse for TypeDecl {
         let name = match_identifier!(ctx, "type's name".to_owned() =>)?;
         let assign_tok = match_operator!(ctx, Operator::Assign =>);
 
-        let kind: TypeDeclKind = if matches!(
-            ctx.lexer.peek_token_kind(0),
-            TokenKind::Keyword(Keyword::Struct)
-        ) {
-            parse_struct_type(ctx)?.into()
-        } else {
-            Type::parse(ctx)?.into()
+        let kind: TypeDeclKind = match ctx.lexer.peek_token_kind(0) {
+            TokenKind::Keyword(Keyword::Struct) => parse_struct_type(ctx)?.into(),
+            TokenKind::Keyword(Keyword::Enum) => parse_enum_type(ctx)?.into(),
+            _ => Type::parse(ctx)?.into(),
         };
 
         let semi_tok = match_delimiter!(ctx, Delimiter::Semicolon =>);
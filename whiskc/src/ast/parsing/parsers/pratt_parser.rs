@@ -42,19 +42,19 @@ impl<R, BP: PartialOrd + BindingPower + Clone + fmt::Debug> PrattParser<R, BP> {
         let mut left = nud_fn(self, parser).ok_or(PrattParseError::ParseError)?;
 
         loop {
-            let tt = parser.lexer.peek_token_kind(0);
+            let tt = parser.lexer.peek_token_kind(0).clone();
 
-            let cur_bp = if let Some(bp) = self.bp_lookup.get(tt) {
+            let cur_bp = if let Some(bp) = self.bp_lookup.get(&tt) {
                 bp
             } else {
                 &BP::zero()
             };
 
-            if *cur_bp <= bp {
+            if *cur_bp <= bp || parser.is_expr_end(&tt) {
                 break;
             }
 
-            let Some(led_fn) = self.led_lookup.get(tt) else {
+            let Some(led_fn) = self.led_lookup.get(&tt) else {
                 return Err(PrattParseError::NoLedHandlerFound(
                     parser.lexer.peek_token(0).clone(),
                 ));
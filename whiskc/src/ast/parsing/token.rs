@@ -31,6 +31,7 @@ pub enum TokenKind {
     LiteralKeyword(LiteralKeyword),
     TypeKeyword(TypeKeyword),
     Identifier(Identifier),
+    Label(Label),
     Delimiter(Delimiter),
     Operator(Operator),
 }
@@ -44,6 +45,7 @@ impl fmt::Debug for TokenKind {
             Self::Keyword(kw) => write!(f, "{:?}", kw),
             Self::TypeKeyword(kw) => write!(f, "{:?}", kw),
             Self::Identifier(id) => write!(f, "{:?}", id),
+            Self::Label(label) => write!(f, "{:?}", label),
             Self::Delimiter(de) => write!(f, "{:?}", de),
             Self::Operator(op) => write!(f, "{:?}", op),
         }
@@ -62,6 +64,7 @@ impl fmt::Display for TokenKind {
                 Self::Keyword(kw) => format!("'{}' keyword", kw),
                 Self::TypeKeyword(kw) => format!("'{}' type", kw),
                 Self::Identifier(ident) => format!("'{}'", ident.0),
+                Self::Label(label) => format!("label '{}", label.0),
                 Self::Delimiter(delim) => format!("'{}'", delim),
                 Self::Operator(op) => format!("'{}' operator", op),
             }
@@ -82,15 +85,32 @@ impl Hash for Identifier {
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq)]
+/// Name of a loop, written with a leading `'` such as `'outer`. The name excludes the `'`.
+#[derive(Debug, Clone, Eq)]
+pub struct Label(pub String);
+impl PartialEq for Label {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+impl Hash for Label {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        discriminant(&TokenKind::Label(Label(String::new()))).hash(state)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum Literal {
-    Int(i64),
+    Int(IntLiteral),
+    Float(f64),
+    Str(String),
 }
 impl PartialEq for Literal {
     fn eq(&self, other: &Self) -> bool {
         discriminant(self) == discriminant(other)
     }
 }
+impl Eq for Literal {}
 impl fmt::Display for Literal {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -98,6 +118,8 @@ impl fmt::Display for Literal {
             "{}",
             match self {
                 Self::Int(i) => i.to_string(),
+                Self::Float(v) => format!("{:?}", v),
+                Self::Str(s) => format!("\"{}\"", s.escape_debug()),
             }
         )
     }
@@ -108,6 +130,22 @@ impl Hash for Literal {
     }
 }
 
+/// Integer literal, whose suffix, such as `u8` in `255u8`, gives its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IntLiteral {
+    pub value: u64,
+    pub suffix: Option<TypeKeyword>,
+}
+impl fmt::Display for IntLiteral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)?;
+        if let Some(suffix) = self.suffix {
+            write!(f, "{}", suffix)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(EnumIter, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LiteralKeyword {
     True,
@@ -150,6 +188,13 @@ pub enum Keyword {
     Else,
     Return,
     Loop,
+    While,
+    For,
+    In,
+    Break,
+    Continue,
+    Match,
+    Enum,
 }
 impl fmt::Display for Keyword {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -167,6 +212,13 @@ impl fmt::Display for Keyword {
                 Self::Else => "else",
                 Self::Return => "return",
                 Self::Loop => "loop",
+                Self::While => "while",
+                Self::For => "for",
+                Self::In => "in",
+                Self::Break => "break",
+                Self::Continue => "continue",
+                Self::Match => "match",
+                Self::Enum => "enum",
             }
         )
     }
@@ -188,6 +240,23 @@ impl FromStr for Keyword {
 pub enum TypeKeyword {
     Bool,
     Int,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    Float,
+    Str,
+}
+impl TypeKeyword {
+    /// Returns whether the keyword is an integer type of a fixed size, which may be the suffix
+    /// of an integer literal.
+    pub fn is_sized_int(&self) -> bool {
+        !matches!(self, Self::Bool | Self::Int | Self::Float | Self::Str)
+    }
 }
 impl fmt::Display for TypeKeyword {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -197,6 +266,16 @@ impl fmt::Display for TypeKeyword {
             match self {
                 Self::Bool => "bool",
                 Self::Int => "int",
+                Self::I8 => "i8",
+                Self::I16 => "i16",
+                Self::I32 => "i32",
+                Self::I64 => "i64",
+                Self::U8 => "u8",
+                Self::U16 => "u16",
+                Self::U32 => "u32",
+                Self::U64 => "u64",
+                Self::Float => "float",
+                Self::Str => "str",
             }
         )
     }
@@ -271,6 +350,8 @@ pub enum OperatorChar {
     Exclaimation,
     Ampersand,
     Pipe,
+    Caret,
+    Tilde,
     Dot,
 }
 impl fmt::Display for OperatorChar {
@@ -290,6 +371,8 @@ impl fmt::Display for OperatorChar {
                 Self::Exclaimation => '!',
                 Self::Ampersand => '&',
                 Self::Pipe => '|',
+                Self::Caret => '^',
+                Self::Tilde => '~',
                 Self::Dot => '.',
             }
         )
@@ -319,6 +402,12 @@ pub enum Operator {
     And,
     Or,
     Not,
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    Shl,
+    Shr,
     Equal,
     NotEqual,
     Less,
@@ -326,6 +415,14 @@ pub enum Operator {
     Greater,
     GreaterEqual,
     MemberAccess,
+    /// separates the pattern of a `match` arm from its expression.
+    FatArrow,
+    Range,
+    RangeInclusive,
+    /// separates the name of an enum from the name of its variant.
+    PathSep,
+    /// casts the value on the left to the type on the right.
+    As,
 }
 impl fmt::Display for Operator {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -342,6 +439,12 @@ impl fmt::Display for Operator {
                 Self::And => "&&",
                 Self::Or => "||",
                 Self::Not => "!",
+                Self::BitAnd => "&",
+                Self::BitOr => "|",
+                Self::BitXor => "^",
+                Self::BitNot => "~",
+                Self::Shl => "<<",
+                Self::Shr => ">>",
                 Self::Equal => "==",
                 Self::NotEqual => "!=",
                 Self::Less => "<",
@@ -349,6 +452,11 @@ impl fmt::Display for Operator {
                 Self::Greater => ">",
                 Self::GreaterEqual => ">=",
                 Self::MemberAccess => ".",
+                Self::FatArrow => "=>",
+                Self::Range => "..",
+                Self::RangeInclusive => "..=",
+                Self::PathSep => "::",
+                Self::As => "as",
             }
         )
     }
@@ -1,14 +1,17 @@
-use wsk_vm::{Cmp, Inst};
+use std::collections::HashMap;
+
+use wsk_vm::{inst::ArithOp, value::IntType, Cmp, Inst, Value};
 
 use crate::{
     ast::parsing::token::Operator,
-    module::nodes::{
-        expr::{
-            BinaryExpr, BlockExpr, CallExpr, Expr, ExprKind, FuncIdentExpr, IfExpr, LoopExpr,
-            ReturnExpr, UnaryExpr, VarIdentExpr,
-        },
-        stmt::{ExprStmt, Stmt},
+    module::nodes::expr::{
+        ArrayExpr, ArrayRepeatExpr, BinaryExpr, BlockExpr, BreakExpr, BuiltinMethod, CallExpr,
+        CastExpr, ContinueExpr, ConvertExpr, EnumInitExpr, Expr, ExprKind, ForExpr, FuncIdentExpr,
+        IfExpr, IndexExpr, LoopExpr, MatchExpr, MemberAccessExpr, MethodCallExpr, ReturnExpr,
+        StructInitExpr, UnaryExpr, VarIdentExpr, WhileExpr,
     },
+    module::nodes::pat::Pattern,
+    symbol::{ty::CastKind, TypeId},
 };
 
 use super::{Codegen, CodegenError, Context};
@@ -17,8 +20,17 @@ impl Codegen for Expr {
     fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
         match &self.kind {
             ExprKind::Unit => Ok(()),
-            ExprKind::Integer(v) => v.codegen(ctx),
+            ExprKind::Integer(v) => {
+                let value = ctx.int_value(*v, self.ty);
+                ctx.get_current_fi_mut().push_inst(Inst::Push(value));
+                Ok(())
+            }
+            ExprKind::Float(v) => {
+                ctx.get_current_fi_mut().push_inst(Inst::Push((*v).into()));
+                Ok(())
+            }
             ExprKind::Bool(v) => v.codegen(ctx),
+            ExprKind::Str(v) => v.codegen(ctx),
             ExprKind::VarIdent(v) => v.codegen(ctx),
             ExprKind::FuncIdent(v) => v.codegen(ctx),
             ExprKind::Unary(v) => v.codegen(ctx),
@@ -28,12 +40,25 @@ impl Codegen for Expr {
             ExprKind::Return(v) => v.codegen(ctx),
             ExprKind::If(v) => v.codegen(ctx),
             ExprKind::Loop(v) => v.codegen(ctx),
-            _ => Err(CodegenError::UnsupportedItem),
+            ExprKind::While(v) => v.codegen(ctx),
+            ExprKind::For(v) => v.codegen(ctx),
+            ExprKind::Break(v) => v.codegen(ctx),
+            ExprKind::Continue(v) => v.codegen(ctx),
+            ExprKind::StructInit(v) => v.codegen(ctx),
+            ExprKind::EnumInit(v) => v.codegen(ctx),
+            ExprKind::MemberAccess(v) => v.codegen(ctx),
+            ExprKind::Convert(v) => v.codegen(ctx),
+            ExprKind::Cast(v) => codegen_cast(ctx, v, self.ty),
+            ExprKind::Match(v) => v.codegen(ctx),
+            ExprKind::Array(v) => v.codegen(ctx),
+            ExprKind::ArrayRepeat(v) => v.codegen(ctx),
+            ExprKind::Index(v) => v.codegen(ctx),
+            ExprKind::MethodCall(v) => v.codegen(ctx),
         }
     }
 }
 
-impl ExprCodegen for i64 {
+impl ExprCodegen for bool {
     fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
         ctx.get_current_fi_mut()
             .push_inst(Inst::Push((*self).into()));
@@ -41,16 +66,19 @@ impl ExprCodegen for i64 {
     }
 }
 
-impl ExprCodegen for bool {
+impl ExprCodegen for String {
     fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
-        ctx.get_current_fi_mut()
-            .push_inst(Inst::Push((*self).into()));
+        let idx = ctx.prog.add_const(Value::from(self.as_str()));
+        ctx.get_current_fi_mut().push_inst(Inst::PushConst(idx));
         Ok(())
     }
 }
 
 impl ExprCodegen for VarIdentExpr {
     fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        if !ctx.has_value(self.id.sym(ctx.sym_table).ty) {
+            return Ok(());
+        }
         let id = ctx.get_local(self.id);
         ctx.get_current_fi_mut().push_inst(Inst::Load(id));
         Ok(())
@@ -71,6 +99,7 @@ impl ExprCodegen for UnaryExpr {
         func.push_inst(match self.op {
             Operator::Sub => Inst::Neg,
             Operator::Not => Inst::Not,
+            Operator::BitNot => Inst::BitNot,
             _ => unimplemented!("codegen unary op {}", self.op),
         });
 
@@ -81,25 +110,15 @@ impl ExprCodegen for UnaryExpr {
 impl ExprCodegen for BinaryExpr {
     fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
         if self.op == Operator::Assign {
-            self.right.codegen(ctx)?;
-
-            // dont evaluate identifier
-            // self.target.codegen(ctx)?;
-
-            return if let ExprKind::VarIdent(VarIdentExpr { id }) = &self.left.kind {
-                let id = ctx.get_local(*id);
-                ctx.get_current_fi_mut().push_inst(Inst::Store(id));
-                Ok(())
-            } else {
-                unimplemented!("unsupported assignment type")
-            };
+            return codegen_assign(ctx, &self.left, &self.right);
         }
 
-        self.left.codegen(ctx)?;
-        self.right.codegen(ctx)?;
+        ctx.codegen_operands([self.left.as_ref(), self.right.as_ref()])?;
 
+        let is_str = ctx.sym_table.underlying_type(self.left.ty) == ctx.sym_table.common_type().str;
         let func = ctx.get_current_fi_mut();
         match self.op {
+            Operator::Add if is_str => func.push_inst(Inst::Concat),
             Operator::Add => func.push_inst(Inst::Add),
             Operator::Sub => func.push_inst(Inst::Sub),
             Operator::Mul => func.push_inst(Inst::Mul),
@@ -107,12 +126,17 @@ impl ExprCodegen for BinaryExpr {
             Operator::Mod => func.push_inst(Inst::Mod),
             Operator::And => func.push_inst(Inst::And),
             Operator::Or => func.push_inst(Inst::Or),
+            Operator::BitAnd => func.push_inst(Inst::BitAnd),
+            Operator::BitOr => func.push_inst(Inst::BitOr),
+            Operator::BitXor => func.push_inst(Inst::BitXor),
+            Operator::Shl => func.push_inst(Inst::Shl),
+            Operator::Shr => func.push_inst(Inst::Shr),
             Operator::Equal => func.push_inst(Cmp::Equal),
             Operator::NotEqual => func.push_insts([Cmp::Equal.into(), Inst::Not]),
             Operator::Less => func.push_inst(Cmp::Less),
-            Operator::LessEqual => func.push_insts([Cmp::Greater.into(), Inst::Not]),
+            Operator::LessEqual => func.push_inst(Cmp::LessEqual),
             Operator::Greater => func.push_inst(Cmp::Greater),
-            Operator::GreaterEqual => func.push_insts([Cmp::Less.into(), Inst::Not]),
+            Operator::GreaterEqual => func.push_inst(Cmp::GreaterEqual),
             _ => unimplemented!("codegen binary op {}", self.op),
         };
 
@@ -120,6 +144,387 @@ impl ExprCodegen for BinaryExpr {
     }
 }
 
+/// Step from a container to one of its parts in an assigned place.
+enum PlaceStep<'e> {
+    Field(&'e MemberAccessExpr),
+    /// element of an array, whose index is evaluated into the local.
+    Index(usize),
+}
+
+/// Outermost place which is replaced by an assignment.
+enum PlaceRoot {
+    /// variable in the local.
+    Local(usize),
+    /// element of a vector, whose vector and index are evaluated into the locals.
+    Element { vec: usize, index: usize },
+}
+
+/// Generates the assignment of the value to the variable, or to a field or an element within a
+/// variable. A part is assigned by replacing it in a copy of each enclosing struct or array,
+/// from the innermost, and storing the outermost one back to the variable. Vectors are shared
+/// rather than copied, so an element of a vector is the outermost place, which is replaced
+/// in the vector itself.
+fn codegen_assign(ctx: &mut Context, place: &Expr, value: &Expr) -> Result<(), CodegenError> {
+    // elements without values are still stored, so that their index is checked.
+    if !ctx.has_value(place.ty) && !matches!(place.kind, ExprKind::Index(_)) {
+        return value.codegen(ctx);
+    }
+
+    // `a.b[i].c` is split into the root `a` and the steps of `.b`, `[i]` and `.c` from the root.
+    let mut places = Vec::new();
+    let mut root = place;
+    loop {
+        match &root.kind {
+            ExprKind::MemberAccess(access) => {
+                places.push(root);
+                root = &access.expr;
+            }
+            ExprKind::Index(index) => {
+                places.push(root);
+                root = &index.expr;
+            }
+            _ => break,
+        }
+    }
+
+    // with `v[i].c` of a vector `v`, the root is the element `v[i]` and `.c` is the only step.
+    let vec_element = places.iter().position(|place| {
+        matches!(&place.kind, ExprKind::Index(index)
+            if ctx.sym_table.get_vec(index.expr.ty).is_some())
+    });
+    let root = match vec_element {
+        Some(pos) => {
+            let ExprKind::Index(element) = &places[pos].kind else {
+                unreachable!()
+            };
+            places.truncate(pos);
+            ctx.codegen_operands([element.expr.as_ref(), element.index.as_ref()])?;
+            if !ctx.has_value(element.expr.ty) || !ctx.has_value(element.index.ty) {
+                // the vector or the index diverges, so the assignment is never reached.
+                return Ok(());
+            }
+            let index = ctx.alloc_temp();
+            let vec = ctx.alloc_temp();
+            ctx.get_current_fi_mut()
+                .push_insts([Inst::Store(index), Inst::Store(vec)]);
+            PlaceRoot::Element { vec, index }
+        }
+        None => {
+            let ExprKind::VarIdent(VarIdentExpr { id }) = &root.kind else {
+                unimplemented!("unsupported assignment type")
+            };
+            PlaceRoot::Local(ctx.get_local(*id))
+        }
+    };
+
+    // the indices are evaluated once, from the root outwards, before the enclosing containers
+    // are pushed.
+    let mut steps = Vec::new();
+    for place in places.iter().rev() {
+        match &place.kind {
+            ExprKind::MemberAccess(access) => steps.push(PlaceStep::Field(access)),
+            ExprKind::Index(index) => {
+                index.index.codegen(ctx)?;
+                if !ctx.has_value(index.index.ty) {
+                    // the index diverges, so the assignment is never reached.
+                    return Ok(());
+                }
+                let temp = ctx.alloc_temp();
+                ctx.get_current_fi_mut().push_inst(Inst::Store(temp));
+                steps.push(PlaceStep::Index(temp));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // push the enclosing containers, each of which is below the index or the value of its part
+    // to be replaced.
+    let pending = ctx.pending;
+    if let PlaceRoot::Element { vec, index } = root {
+        ctx.get_current_fi_mut()
+            .push_insts([Inst::Load(vec), Inst::Load(index)]);
+        ctx.pending += 2;
+    }
+    for (i, step) in steps.iter().enumerate() {
+        let load_root = match root {
+            PlaceRoot::Local(id) => vec![Inst::Load(id)],
+            PlaceRoot::Element { vec, index } => {
+                vec![Inst::Load(vec), Inst::Load(index), Inst::VecGet]
+            }
+        };
+        ctx.get_current_fi_mut().push_insts(load_root);
+        for step in &steps[..i] {
+            match step {
+                PlaceStep::Field(access) => {
+                    let idx = ctx
+                        .field_index(access.struct_ty, &access.field_name)
+                        .expect("field with value");
+                    ctx.get_current_fi_mut().push_inst(Inst::GetField(idx));
+                }
+                PlaceStep::Index(temp) => ctx
+                    .get_current_fi_mut()
+                    .push_insts([Inst::Load(*temp), Inst::GetIndex]),
+            }
+        }
+        ctx.pending += 1;
+        if let PlaceStep::Index(temp) = step {
+            ctx.get_current_fi_mut().push_inst(Inst::Load(*temp));
+            ctx.pending += 1;
+        }
+    }
+    let is_element = match steps.last() {
+        Some(step) => matches!(step, PlaceStep::Index(_)),
+        None => matches!(root, PlaceRoot::Element { .. }),
+    };
+    if is_element {
+        codegen_element(ctx, value)?;
+    } else {
+        value.codegen(ctx)?;
+    }
+    ctx.pending = pending;
+
+    for step in steps.iter().rev() {
+        let inst = match step {
+            PlaceStep::Field(access) => Inst::SetField(
+                ctx.field_index(access.struct_ty, &access.field_name)
+                    .expect("field with value"),
+            ),
+            PlaceStep::Index(_) => Inst::SetIndex,
+        };
+        ctx.get_current_fi_mut().push_inst(inst);
+    }
+    let inst = match root {
+        PlaceRoot::Local(id) => Inst::Store(id),
+        PlaceRoot::Element { .. } => Inst::VecSet,
+    };
+    ctx.get_current_fi_mut().push_inst(inst);
+    Ok(())
+}
+
+/// Generates the element of an array or a vector. Elements without values are pushed as
+/// placeholders, so that the length of the array is kept for bounds checks.
+fn codegen_element(ctx: &mut Context, expr: &Expr) -> Result<(), CodegenError> {
+    expr.codegen(ctx)?;
+    if !ctx.has_value(expr.ty) {
+        ctx.get_current_fi_mut().push_inst(Inst::Push(false.into()));
+    }
+    Ok(())
+}
+
+impl ExprCodegen for ArrayExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        let pending = ctx.pending;
+        for element in &self.elements {
+            codegen_element(ctx, element)?;
+            ctx.pending += 1;
+        }
+        ctx.pending = pending;
+        ctx.get_current_fi_mut()
+            .push_inst(Inst::MakeArray(self.elements.len()));
+        Ok(())
+    }
+}
+
+impl ExprCodegen for ArrayRepeatExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        codegen_element(ctx, &self.expr)?;
+        ctx.get_current_fi_mut()
+            .push_inst(Inst::RepeatArray(self.len));
+        Ok(())
+    }
+}
+
+impl ExprCodegen for IndexExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        ctx.codegen_operands([self.expr.as_ref(), self.index.as_ref()])?;
+        let common_ty = *ctx.sym_table.common_type();
+        let (inst, elem_ty) = match ctx.sym_table.get_array(self.expr.ty) {
+            Some(array_ty) => (Inst::GetIndex, array_ty.elem_ty),
+            None if ctx.sym_table.underlying_type(self.expr.ty) == common_ty.str => {
+                (Inst::StrIndex, common_ty.int)
+            }
+            None => {
+                let vec_ty = ctx.sym_table.get_vec(self.expr.ty).expect("vector type");
+                (Inst::VecGet, vec_ty.elem_ty)
+            }
+        };
+        ctx.get_current_fi_mut().push_inst(inst);
+        if !ctx.has_value(elem_ty) {
+            ctx.get_current_fi_mut().push_inst(Inst::Pop);
+        }
+        Ok(())
+    }
+}
+
+impl ExprCodegen for MethodCallExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        let pending = ctx.pending;
+        self.receiver.codegen(ctx)?;
+        ctx.pending += 1;
+        for arg in &self.args {
+            codegen_element(ctx, arg)?;
+            ctx.pending += 1;
+        }
+        ctx.pending = pending;
+
+        let insts = match self.method {
+            BuiltinMethod::ArrayLen => {
+                let len = ctx
+                    .sym_table
+                    .get_array(self.receiver.ty)
+                    .expect("array type")
+                    .len;
+                vec![Inst::Pop, Inst::Push(Value::Int(len as i64))]
+            }
+            BuiltinMethod::ArrayToVec => vec![Inst::ToVec],
+            BuiltinMethod::VecPush => vec![Inst::VecPush],
+            BuiltinMethod::VecPop => {
+                let vec_ty = ctx
+                    .sym_table
+                    .get_vec(self.receiver.ty)
+                    .expect("vector type");
+                match ctx.has_value(vec_ty.elem_ty) {
+                    true => vec![Inst::VecPop],
+                    false => vec![Inst::VecPop, Inst::Pop],
+                }
+            }
+            BuiltinMethod::VecLen => vec![Inst::VecLen],
+            BuiltinMethod::StrLen => vec![Inst::StrLen],
+            BuiltinMethod::WrappingAdd => vec![Inst::Wrapping(ArithOp::Add)],
+            BuiltinMethod::WrappingSub => vec![Inst::Wrapping(ArithOp::Sub)],
+            BuiltinMethod::WrappingMul => vec![Inst::Wrapping(ArithOp::Mul)],
+            BuiltinMethod::SaturatingAdd => vec![Inst::Saturating(ArithOp::Add)],
+            BuiltinMethod::SaturatingSub => vec![Inst::Saturating(ArithOp::Sub)],
+            BuiltinMethod::SaturatingMul => vec![Inst::Saturating(ArithOp::Mul)],
+        };
+        ctx.get_current_fi_mut().push_insts(insts);
+        Ok(())
+    }
+}
+
+impl ExprCodegen for StructInitExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        let layout = ctx.struct_layout(self.struct_ty);
+        let in_layout_order = self
+            .fields
+            .iter()
+            .filter(|(_, expr)| ctx.has_value(expr.ty))
+            .map(|(name, _)| name.as_str())
+            .eq(layout.iter().copied());
+
+        if in_layout_order {
+            ctx.codegen_operands(self.fields.iter().map(|(_, expr)| expr))?;
+        } else {
+            // the fields are evaluated in the written order, and kept in locals until all of them
+            // can be pushed in the layout order.
+            let mut temps = HashMap::new();
+            for (name, expr) in &self.fields {
+                expr.codegen(ctx)?;
+                if ctx.has_value(expr.ty) {
+                    let temp = ctx.alloc_temp();
+                    ctx.get_current_fi_mut().push_inst(Inst::Store(temp));
+                    temps.insert(name.as_str(), temp);
+                }
+            }
+            for name in &layout {
+                if let Some(temp) = temps.get(name) {
+                    ctx.get_current_fi_mut().push_inst(Inst::Load(*temp));
+                }
+            }
+        }
+
+        ctx.get_current_fi_mut()
+            .push_inst(Inst::MakeStruct(layout.len()));
+        Ok(())
+    }
+}
+
+impl ExprCodegen for EnumInitExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        let layout = ctx.variant_layout(self.enum_ty, self.variant);
+        let in_layout_order = self
+            .fields
+            .iter()
+            .filter(|(_, expr)| ctx.has_value(expr.ty))
+            .map(|(i, _)| *i)
+            .eq(layout.iter().copied());
+
+        if in_layout_order {
+            ctx.codegen_operands(self.fields.iter().map(|(_, expr)| expr))?;
+        } else {
+            let mut temps = HashMap::new();
+            for (i, expr) in &self.fields {
+                expr.codegen(ctx)?;
+                if ctx.has_value(expr.ty) {
+                    let temp = ctx.alloc_temp();
+                    ctx.get_current_fi_mut().push_inst(Inst::Store(temp));
+                    temps.insert(*i, temp);
+                }
+            }
+            for i in &layout {
+                if let Some(temp) = temps.get(i) {
+                    ctx.get_current_fi_mut().push_inst(Inst::Load(*temp));
+                }
+            }
+        }
+
+        ctx.get_current_fi_mut()
+            .push_inst(Inst::MakeEnum(self.variant, layout.len()));
+        Ok(())
+    }
+}
+
+impl ExprCodegen for MemberAccessExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        self.expr.codegen(ctx)?;
+        let inst = match ctx.field_index(self.struct_ty, &self.field_name) {
+            Some(idx) => Inst::GetField(idx),
+            None => Inst::Pop,
+        };
+        ctx.get_current_fi_mut().push_inst(inst);
+        Ok(())
+    }
+}
+
+impl ExprCodegen for ConvertExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        // an alias has the same representation as its underlying type.
+        self.expr.codegen(ctx)
+    }
+}
+
+/// Generates the cast of the expression to the type.
+fn codegen_cast(ctx: &mut Context, cast: &CastExpr, ty: TypeId) -> Result<(), CodegenError> {
+    cast.expr.codegen(ctx)?;
+    let from_int_ty = match cast.kind {
+        CastKind::Identity => return Ok(()),
+        CastKind::IntToBool => {
+            ctx.get_current_fi_mut().push_inst(Inst::IntToBool);
+            return Ok(());
+        }
+        CastKind::BoolToInt => {
+            ctx.get_current_fi_mut().push_inst(Inst::BoolToInt);
+            IntType::Int
+        }
+        CastKind::IntToInt => ctx.int_type(cast.expr.ty).expect("integer type"),
+        CastKind::IntToFloat => {
+            ctx.get_current_fi_mut().push_inst(Inst::IntToFloat);
+            return Ok(());
+        }
+        CastKind::FloatToInt => {
+            let int_ty = ctx.int_type(ty).expect("integer type");
+            ctx.get_current_fi_mut().push_inst(Inst::FloatToInt(int_ty));
+            return Ok(());
+        }
+    };
+    // integers of the same representation need no conversion.
+    let int_ty = ctx.int_type(ty).expect("integer type");
+    if int_ty != from_int_ty {
+        ctx.get_current_fi_mut().push_inst(Inst::IntCast(int_ty));
+    }
+    Ok(())
+}
+
 impl ExprCodegen for CallExpr {
     fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
         let ExprKind::FuncIdent(FuncIdentExpr { id: fid }) = self.caller.kind else {
@@ -128,12 +533,23 @@ impl ExprCodegen for CallExpr {
 
         // self.caller.codegen(ctx)?;
 
-        for arg in &self.args {
-            arg.codegen(ctx)?;
+        ctx.codegen_operands(&self.args)?;
+
+        if let Some(import) = ctx.builtin_import(fid, &self.args) {
+            let idx = ctx.prog.add_import(import);
+            ctx.get_current_fi_mut().push_inst(Inst::CallNative(idx));
+            if fid == ctx.sym_table.common_func().exit {
+                // the host stops the program, so the path ends here.
+                ctx.get_current_fi_mut().push_inst(Inst::Halt);
+            }
+            return Ok(());
         }
 
-        let fi = ctx.get_fi(fid).expect("codegen fi");
-        ctx.get_current_fi_mut().push_inst(Inst::Call(fi));
+        let inst = match ctx.get_import(fid) {
+            Some(idx) => Inst::CallNative(idx),
+            None => Inst::Call(ctx.get_fi(fid).expect("codegen fi")),
+        };
+        ctx.get_current_fi_mut().push_inst(inst);
 
         Ok(())
     }
@@ -170,50 +586,370 @@ impl ExprCodegen for IfExpr {
     fn codegen(&self, ctx: &mut super::Context) -> Result<(), super::CodegenError> {
         self.cond.codegen(ctx)?;
 
+        // jumps are pushed as placeholders and patched once their targets are known, so that
+        // the jumps already generated within the branches are not shifted.
         let branch_point = ctx.get_current_fi_mut().len();
+        ctx.get_current_fi_mut().push_inst(Inst::JmpFalse(0));
 
         self.then.codegen(ctx)?;
 
-        let then_to_merge_point = ctx.get_current_fi_mut().len() + 1;
+        let Some(else_) = &self.else_ else {
+            let func = ctx.get_current_fi_mut();
+            let jmp_dist = func.len() - branch_point;
+            func.set_inst(branch_point, Inst::JmpFalse(jmp_dist as isize));
+            return Ok(());
+        };
 
-        if let Some(body) = &self.else_ {
-            body.codegen(ctx)?;
-        }
+        let then_to_merge_point = ctx.get_current_fi_mut().len();
+        ctx.get_current_fi_mut().push_inst(Inst::Jmp(0));
 
         let func = ctx.get_current_fi_mut();
+        let jmp_dist = func.len() - branch_point;
+        func.set_inst(branch_point, Inst::JmpFalse(jmp_dist as isize));
 
-        let jmp_dist = func.len() - branch_point + 1;
-        func.insert_inst(branch_point, Inst::JmpFalse(jmp_dist as isize));
+        else_.codegen(ctx)?;
 
-        if !matches!(
-            self.then.stmts.last(),
-            Some(Stmt::Expr(ExprStmt {
-                expr: Expr {
-                    kind: ExprKind::Return(_),
-                    ..
-                }
-            }))
-        ) && self.else_.is_some()
-        {
-            let jmp_dist = func.len() - then_to_merge_point + 1;
-            func.insert_inst(then_to_merge_point, Inst::Jmp(jmp_dist as isize));
+        let func = ctx.get_current_fi_mut();
+        let jmp_dist = func.len() - then_to_merge_point;
+        func.set_inst(then_to_merge_point, Inst::Jmp(jmp_dist as isize));
+
+        Ok(())
+    }
+}
+
+impl ExprCodegen for MatchExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        self.expr.codegen(ctx)?;
+        ctx.push_bound();
+        let value = ctx.alloc_temp();
+        ctx.get_current_fi_mut().push_inst(Inst::Store(value));
+
+        // each arm jumps to the next one when its pattern or guard fails, and to the end of the
+        // match once its body is generated.
+        let mut ends = vec![];
+        for (i, arm) in self.arms.iter().enumerate() {
+            let is_last = i == self.arms.len() - 1;
+            ctx.push_bound();
+
+            let mut nexts = vec![];
+            // the last arm always matches, since the arms are exhaustive.
+            if !is_last {
+                codegen_pattern_test(ctx, &arm.pat, self.expr.ty, value, &mut nexts);
+            }
+            codegen_pattern_bindings(ctx, &arm.pat, value);
+            if let Some(guard) = &arm.guard {
+                guard.codegen(ctx)?;
+                let func = ctx.get_current_fi_mut();
+                nexts.push(func.len());
+                func.push_inst(Inst::JmpFalse(0));
+            }
+
+            arm.expr.codegen(ctx)?;
+            ctx.pop_bound();
+
+            let func = ctx.get_current_fi_mut();
+            if !is_last {
+                ends.push(func.len());
+                func.push_inst(Inst::Jmp(0));
+            }
+            patch_jumps(ctx, &nexts);
         }
+        patch_jumps(ctx, &ends);
 
+        ctx.pop_bound();
         Ok(())
     }
 }
 
+/// Generates the test of the pattern against the value of the type in the local, pushing the
+/// positions of the placeholder jumps taken when the pattern does not match.
+fn codegen_pattern_test(
+    ctx: &mut Context,
+    pat: &Pattern,
+    ty: TypeId,
+    value: usize,
+    fails: &mut Vec<usize>,
+) {
+    let func = ctx.get_current_fi_mut();
+    match pat {
+        Pattern::Wildcard | Pattern::Binding(_) => {}
+        Pattern::Integer(v) => {
+            let v = ctx.int_value(*v, ty);
+            let func = ctx.get_current_fi_mut();
+            func.push_insts([Inst::Load(value), Inst::Push(v), Inst::Cmp(Cmp::Equal)]);
+            fails.push(func.len());
+            func.push_inst(Inst::JmpFalse(0));
+        }
+        Pattern::Bool(v) => {
+            func.push_inst(Inst::Load(value));
+            fails.push(func.len());
+            func.push_inst(if *v {
+                Inst::JmpFalse(0)
+            } else {
+                Inst::JmpTrue(0)
+            });
+        }
+        Pattern::Range { start, end } => {
+            for (bound, cmp) in [(*start, Cmp::Less), (*end, Cmp::Greater)] {
+                let bound = ctx.int_value(bound, ty);
+                let func = ctx.get_current_fi_mut();
+                func.push_insts([Inst::Load(value), Inst::Push(bound), Inst::Cmp(cmp)]);
+                fails.push(func.len());
+                func.push_inst(Inst::JmpTrue(0));
+            }
+        }
+        Pattern::Variant {
+            enum_ty,
+            variant,
+            fields,
+        } => {
+            func.push_insts([
+                Inst::Load(value),
+                Inst::GetTag,
+                Inst::Push((*variant as i64).into()),
+                Inst::Cmp(Cmp::Equal),
+            ]);
+            fails.push(func.len());
+            func.push_inst(Inst::JmpFalse(0));
+
+            let layout = ctx.variant_layout(*enum_ty, *variant);
+            let field_types = ctx.variant_field_types(*enum_ty, *variant);
+            for (i, field_pat) in fields {
+                let Some(idx) = layout.iter().position(|v| v == i) else {
+                    continue;
+                };
+                if field_pat.is_irrefutable() {
+                    continue;
+                }
+                let temp = ctx.alloc_temp();
+                ctx.get_current_fi_mut().push_insts([
+                    Inst::Load(value),
+                    Inst::GetField(idx),
+                    Inst::Store(temp),
+                ]);
+                codegen_pattern_test(ctx, field_pat, field_types[*i], temp, fails);
+            }
+        }
+    }
+}
+
+/// Generates the stores of the values bound by the pattern, which are taken from the value in the
+/// local.
+fn codegen_pattern_bindings(ctx: &mut Context, pat: &Pattern, value: usize) {
+    match pat {
+        Pattern::Binding(vid) => {
+            let local = ctx.get_local(*vid);
+            let func = ctx.get_current_fi_mut();
+            func.push_inst(Inst::Load(value));
+            func.push_inst(Inst::Store(local));
+        }
+        Pattern::Variant {
+            enum_ty,
+            variant,
+            fields,
+        } => {
+            let layout = ctx.variant_layout(*enum_ty, *variant);
+            for (i, field_pat) in fields {
+                // fields without values bind nothing to store.
+                let Some(idx) = layout.iter().position(|v| v == i) else {
+                    continue;
+                };
+                if !field_pat.has_bindings() {
+                    continue;
+                }
+                let temp = match field_pat {
+                    Pattern::Binding(vid) => ctx.get_local(*vid),
+                    _ => ctx.alloc_temp(),
+                };
+                ctx.get_current_fi_mut().push_insts([
+                    Inst::Load(value),
+                    Inst::GetField(idx),
+                    Inst::Store(temp),
+                ]);
+                if !matches!(field_pat, Pattern::Binding(_)) {
+                    codegen_pattern_bindings(ctx, field_pat, temp);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Patches the placeholder jumps to jump to the current end of the function.
+fn patch_jumps(ctx: &mut Context, jumps: &[usize]) {
+    let func = ctx.get_current_fi_mut();
+    let end = func.len();
+    for &pos in jumps {
+        let dist = end as isize - pos as isize;
+        let inst = match func.get(pos) {
+            Some(Inst::JmpFalse(_)) => Inst::JmpFalse(dist),
+            Some(Inst::JmpTrue(_)) => Inst::JmpTrue(dist),
+            _ => Inst::Jmp(dist),
+        };
+        func.set_inst(pos, inst);
+    }
+}
+
 impl ExprCodegen for LoopExpr {
     fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
         let jmp_dest = ctx.get_current_fi_mut().len();
-        self.body.codegen(ctx)?;
+        ctx.push_loop(jmp_dest, self.label.clone());
+        codegen_loop_body(ctx, &self.body)?;
         let func = ctx.get_current_fi_mut();
         let jmp_src = func.len();
         func.push_inst(Inst::Jmp(jmp_dest as isize - jmp_src as isize));
+        ctx.pop_loop();
         Ok(())
     }
 }
 
+impl ExprCodegen for WhileExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        let jmp_dest = ctx.get_current_fi_mut().len();
+        self.cond.codegen(ctx)?;
+        let exit_point = ctx.get_current_fi_mut().len();
+        ctx.get_current_fi_mut().push_inst(Inst::JmpFalse(0));
+
+        ctx.push_loop(jmp_dest, self.label.clone());
+        codegen_loop_body(ctx, &self.body)?;
+        let func = ctx.get_current_fi_mut();
+        let jmp_src = func.len();
+        func.push_inst(Inst::Jmp(jmp_dest as isize - jmp_src as isize));
+        let jmp_dist = func.len() - exit_point;
+        func.set_inst(exit_point, Inst::JmpFalse(jmp_dist as isize));
+        ctx.pop_loop();
+        Ok(())
+    }
+}
+
+impl ExprCodegen for ForExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        self.iter.codegen(ctx)?;
+        if !ctx.has_value(self.iter.ty) {
+            // the iterated value diverges, so the loop is never reached.
+            return Ok(());
+        }
+        let (iter, index) = (ctx.alloc_temp(), ctx.alloc_temp());
+        ctx.get_current_fi_mut().push_insts([
+            Inst::Store(iter),
+            Inst::Push(Value::Int(0)),
+            Inst::Store(index),
+        ]);
+
+        // the length of a vector is read on each iteration, as the body may change it.
+        let (len, get, elem_ty) = match ctx.sym_table.get_array(self.iter.ty) {
+            Some(array_ty) => (
+                vec![Inst::Push(Value::Int(array_ty.len as i64))],
+                Inst::GetIndex,
+                array_ty.elem_ty,
+            ),
+            None => {
+                let vec_ty = ctx.sym_table.get_vec(self.iter.ty).expect("vector type");
+                (
+                    vec![Inst::Load(iter), Inst::VecLen],
+                    Inst::VecGet,
+                    vec_ty.elem_ty,
+                )
+            }
+        };
+        let jmp_dest = ctx.get_current_fi_mut().len();
+        let func = ctx.get_current_fi_mut();
+        func.push_inst(Inst::Load(index));
+        func.push_insts(len);
+        func.push_inst(Cmp::Less);
+        let exit_point = func.len();
+        func.push_inst(Inst::JmpFalse(0));
+
+        // the index is advanced before the body, so that `continue` moves to the next element.
+        let store = match ctx.has_value(elem_ty) {
+            true => Inst::Store(ctx.get_local(self.var)),
+            false => Inst::Pop,
+        };
+        ctx.get_current_fi_mut().push_insts([
+            Inst::Load(iter),
+            Inst::Load(index),
+            get,
+            store,
+            Inst::Load(index),
+            Inst::Push(Value::Int(1)),
+            Inst::Add,
+            Inst::Store(index),
+        ]);
+
+        ctx.push_loop(jmp_dest, self.label.clone());
+        codegen_loop_body(ctx, &self.body)?;
+        let func = ctx.get_current_fi_mut();
+        let jmp_src = func.len();
+        func.push_inst(Inst::Jmp(jmp_dest as isize - jmp_src as isize));
+        let jmp_dist = func.len() - exit_point;
+        func.set_inst(exit_point, Inst::JmpFalse(jmp_dist as isize));
+        ctx.pop_loop();
+        Ok(())
+    }
+}
+
+/// Generates the body of a loop, discarding its value so that every iteration starts with the
+/// same stack.
+fn codegen_loop_body(ctx: &mut Context, body: &BlockExpr) -> Result<(), CodegenError> {
+    body.codegen(ctx)?;
+    if body.eval_expr.as_ref().is_some_and(|v| ctx.has_value(v.ty)) {
+        ctx.get_current_fi_mut().push_inst(Inst::Pop);
+    }
+    Ok(())
+}
+
+impl ExprCodegen for BreakExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        let has_value = match &self.expr {
+            Some(expr) => {
+                expr.codegen(ctx)?;
+                ctx.has_value(expr.ty)
+            }
+            None => false,
+        };
+        let label = self.label.as_deref();
+        codegen_loop_exit(ctx, label, has_value);
+
+        let func = ctx.get_current_fi_mut();
+        let jmp_src = func.len();
+        func.push_inst(Inst::Jmp(0));
+        ctx.get_loop_mut(label).breaks.push(jmp_src);
+        Ok(())
+    }
+}
+
+impl ExprCodegen for ContinueExpr {
+    fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError> {
+        let label = self.label.as_deref();
+        codegen_loop_exit(ctx, label, false);
+        let jmp_dest = ctx.get_loop_mut(label).start;
+        let func = ctx.get_current_fi_mut();
+        let jmp_src = func.len();
+        func.push_inst(Inst::Jmp(jmp_dest as isize - jmp_src as isize));
+        Ok(())
+    }
+}
+
+/// Discards the values pending since the target loop was entered, which the enclosing
+/// expressions would have consumed if the jump out of them was not taken.
+/// The value on the top is kept if `keep_top` is set.
+fn codegen_loop_exit(ctx: &mut Context, label: Option<&str>, keep_top: bool) {
+    let loop_pending = ctx.get_loop_mut(label).pending;
+    let extra = ctx.pending - loop_pending;
+    if extra == 0 {
+        return;
+    }
+    let temp = keep_top.then(|| ctx.alloc_temp());
+    let func = ctx.get_current_fi_mut();
+    if let Some(temp) = temp {
+        func.push_inst(Inst::Store(temp));
+    }
+    func.push_insts((0..extra).map(|_| Inst::Pop));
+    if let Some(temp) = temp {
+        func.push_inst(Inst::Load(temp));
+    }
+}
+
 pub trait ExprCodegen {
     fn codegen(&self, ctx: &mut Context) -> Result<(), CodegenError>;
 }
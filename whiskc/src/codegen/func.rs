@@ -9,25 +9,25 @@ impl Codegen for ast::func::Function {
         ctx.set_current_fi(self.func_id);
         let func_sym = self.func_id.sym(ctx.sym_table);
 
-        for param_id in &func_sym.params {
+        // arguments are pushed in order, so the last one is on the top of the stack.
+        for param_id in func_sym.params.iter().rev() {
+            if !ctx.has_value(param_id.sym(ctx.sym_table).ty) {
+                continue;
+            }
             let id = ctx.get_local(*param_id);
             ctx.get_current_fi_mut().push_inst(Inst::Store(id));
         }
 
         self.body.codegen(ctx)?;
 
-        if func_sym.ret_ty == ctx.sym_table.common_type().unit
-            || self
-                .body
-                .eval_expr
-                .as_ref()
-                .map(|v| v.ty == ctx.sym_table.common_type().never)
-                .unwrap_or(false)
-        {
-            let func = ctx.get_current_fi_mut();
-            if !matches!(func.get_insts()[..], [.., Inst::Ret]) {
-                func.push_inst(Inst::Ret);
-            }
+        let func = ctx.get_current_fi_mut();
+        let len = func.len() as isize;
+        let jumps_to_end = func.get_insts().iter().enumerate().any(|(i, inst)| {
+            matches!(inst, Inst::Jmp(offset) | Inst::JmpTrue(offset) | Inst::JmpFalse(offset)
+                if i as isize + offset == len)
+        });
+        if jumps_to_end || !matches!(func.get_insts()[..], [.., Inst::Ret]) {
+            func.push_inst(Inst::Ret);
         }
 
         ctx.unset_current_fi();
@@ -1,13 +1,22 @@
 use std::collections::HashMap;
 
 use wsk_vm::{
-    program::{Function, Program},
-    Inst,
+    native::{Import, NativeSig, ValueType},
+    program::{Arity, Function, Program},
+    value::IntType,
+    Inst, Value,
 };
 
 use crate::{
-    module::{nodes::item::Item, Module},
-    symbol::{FuncId, SymbolTable, VarId},
+    ast::location::Span,
+    module::{
+        nodes::{expr::Expr, func::ExternFunction, item::Item},
+        Module,
+    },
+    symbol::{
+        ty::{Primitive, TypeKind},
+        FuncId, SymbolTable, TypeId, VarId,
+    },
 };
 
 mod expr;
@@ -19,19 +28,41 @@ pub fn codegen_wsk_vm(module: &Module) -> Result<Program, CodegenError> {
     let mut has_entry = false;
 
     for item in &module.items {
-        let Item::Function(func) = item else {
-            return Err(CodegenError::UnsupportedItem);
+        let func = match item {
+            Item::Function(func) => func,
+            Item::ExternFunction(ExternFunction(fid)) => {
+                let import = ctx.import_of(*fid)?;
+                let idx = ctx.prog.add_import(import);
+                ctx.imports.insert(*fid, idx);
+                continue;
+            }
+            // types have no runtime representation of their own, and alias constructors are
+            // resolved into conversions.
+            Item::TypeDecl(_) => continue,
         };
-        let fi = ctx.prog.add_func(Function::default());
+        let func_sym = func.func_id.sym(ctx.sym_table);
+        let params = func_sym
+            .params
+            .iter()
+            .filter(|v| ctx.has_value(v.sym(ctx.sym_table).ty))
+            .count();
+        let rets = if ctx.has_value(func_sym.ret_ty) { 1 } else { 0 };
+        let mut vm_func = Function::default();
+        vm_func.set_name(&func_sym.name);
+        vm_func.set_arity(Arity::new(params, rets));
+        let fi = ctx.prog.add_func(vm_func);
         ctx.add_fi(func.func_id, fi);
 
-        let func_sym = func.func_id.sym(ctx.sym_table);
         if func_sym.name == "main" {
             ctx.prog.set_entry_point(fi);
             has_entry = true;
 
-            if !func_sym.params.is_empty() || func_sym.ret_ty != ctx.sym_table.common_type().int {
-                return Err(CodegenError::UnsupportedMainFunctionSig);
+            if !func_sym.params.is_empty()
+                || ctx.sym_table.underlying_type(func_sym.ret_ty) != ctx.sym_table.common_type().int
+            {
+                return Err(CodegenError::UnsupportedMainFunctionSig {
+                    origin: func_sym.origin,
+                });
             }
         }
     }
@@ -60,10 +91,15 @@ struct Context<'a> {
     pub sym_table: &'a SymbolTable,
     pub prog: Program,
     fis: HashMap<FuncId, usize>,
+    imports: HashMap<FuncId, usize>,
     cur_fi: Option<usize>,
     locals: HashMap<VarId, usize>,
     local_cnts: Vec<usize>,
     active_local_cnt: usize,
+    /// number of values pushed by the enclosing expressions which are still waiting for the
+    /// value being generated, such as the left operand of a binary expression.
+    pending: usize,
+    loops: Vec<LoopTarget>,
 }
 impl<'a> Context<'a> {
     pub fn new(sym_table: &'a SymbolTable) -> Self {
@@ -71,10 +107,13 @@ impl<'a> Context<'a> {
             sym_table,
             prog: Program::default(),
             fis: HashMap::new(),
+            imports: HashMap::new(),
             cur_fi: None,
             locals: HashMap::new(),
             local_cnts: vec![],
             active_local_cnt: 0,
+            pending: 0,
+            loops: vec![],
         }
     }
 
@@ -101,6 +140,102 @@ impl<'a> Context<'a> {
         self.fis.get(&fid).copied()
     }
 
+    pub fn get_import(&self, fid: FuncId) -> Option<usize> {
+        self.imports.get(&fid).copied()
+    }
+
+    /// Returns the import of the extern function, whose parameter and return types must be
+    /// representable as host values.
+    fn import_of(&self, fid: FuncId) -> Result<Import, CodegenError> {
+        let func_sym = fid.sym(self.sym_table);
+        let common_ty = self.sym_table.common_type();
+        let value_ty = |ty: TypeId| self.value_type(ty);
+        let unsupported = || CodegenError::UnsupportedExternSignature {
+            name: func_sym.name.clone(),
+            origin: func_sym.origin,
+        };
+
+        let params = func_sym
+            .params
+            .iter()
+            .map(|v| value_ty(v.sym(self.sym_table).ty))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(unsupported)?;
+        let ret = if func_sym.ret_ty == common_ty.unit {
+            None
+        } else {
+            Some(value_ty(func_sym.ret_ty).ok_or_else(unsupported)?)
+        };
+        Ok(Import::new(&func_sym.name, NativeSig::new(params, ret)))
+    }
+
+    /// Returns the import of the host function implementing the built-in function, or `None` if
+    /// the function is not built-in.
+    /// Print functions have a host function for each printable type, chosen by the argument.
+    pub fn builtin_import(&self, fid: FuncId, args: &[Expr]) -> Option<Import> {
+        let common_func = self.sym_table.common_func();
+        let (name, sig) = if common_func.is_print(fid) {
+            // unreachable calls with a never argument can use any of them.
+            let ty = args
+                .first()
+                .and_then(|v| self.value_type(v.ty))
+                .unwrap_or(ValueType::Str);
+            let name = format!("{}_{}", fid.sym(self.sym_table).name, ty);
+            (name, NativeSig::new([ty], None))
+        } else if fid == common_func.read_line {
+            (
+                "read_line".to_owned(),
+                NativeSig::new([], Some(ValueType::Str)),
+            )
+        } else if fid == common_func.exit {
+            (
+                "exit".to_owned(),
+                NativeSig::new([ValueType::Int(IntType::Int)], None),
+            )
+        } else {
+            return None;
+        };
+        Some(Import::new(name, sig))
+    }
+
+    /// Returns the runtime type of values of the type, if they can be passed to host functions.
+    fn value_type(&self, ty: TypeId) -> Option<ValueType> {
+        if let Some(int_ty) = self.int_type(ty) {
+            return Some(ValueType::Int(int_ty));
+        }
+        let ty = self.sym_table.underlying_type(ty);
+        let common_ty = self.sym_table.common_type();
+        match ty {
+            _ if ty == common_ty.float => Some(ValueType::Float),
+            _ if ty == common_ty.bool => Some(ValueType::Bool),
+            _ if ty == common_ty.str => Some(ValueType::Str),
+            _ => None,
+        }
+    }
+
+    /// Returns the runtime type of integers of the type, if it is an integer type.
+    pub fn int_type(&self, ty: TypeId) -> Option<IntType> {
+        Some(match self.sym_table.get_int(ty)? {
+            Primitive::Int | Primitive::I64 => IntType::Int,
+            Primitive::I8 => IntType::I8,
+            Primitive::I16 => IntType::I16,
+            Primitive::I32 => IntType::I32,
+            Primitive::U8 => IntType::U8,
+            Primitive::U16 => IntType::U16,
+            Primitive::U32 => IntType::U32,
+            Primitive::U64 => IntType::U64,
+            _ => unreachable!("integer type expected"),
+        })
+    }
+
+    /// Returns the constant of the integer type with the value, which is in range of the type.
+    pub fn int_value(&self, v: i128, ty: TypeId) -> Value {
+        let int_ty = self.int_type(ty).expect("integer type");
+        Value::Int(v as i64)
+            .int_cast(int_ty)
+            .expect("integer value")
+    }
+
     pub fn clear_locals(&mut self) {
         self.locals.clear();
         self.local_cnts.clear();
@@ -117,6 +252,110 @@ impl<'a> Context<'a> {
         id
     }
 
+    /// Returns whether values of the type are on the stack.
+    /// Unit and never have no runtime representation, so they are never pushed.
+    pub fn has_value(&self, ty: TypeId) -> bool {
+        let ty = self.sym_table.underlying_type(ty);
+        let common_ty = self.sym_table.common_type();
+        ty != common_ty.unit && ty != common_ty.never
+    }
+
+    /// Allocates a local for an intermediate value, which is freed with the current bound.
+    pub fn alloc_temp(&mut self) -> usize {
+        let id = self.active_local_cnt;
+        self.active_local_cnt += 1;
+        id
+    }
+
+    /// Returns the names of the fields of the struct in the order they are laid out.
+    /// Fields without values are left out.
+    pub fn struct_layout(&self, struct_ty: TypeId) -> Vec<&'a str> {
+        let Some(TypeKind::Struct(ty)) = &struct_ty.sym(self.sym_table).kind else {
+            panic!("struct type expected");
+        };
+        ty.fields
+            .iter()
+            .filter(|(_, ty)| self.has_value(*ty))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Returns the index of the field in the struct, or `None` if the field has no value.
+    pub fn field_index(&self, struct_ty: TypeId, field_name: &str) -> Option<usize> {
+        self.struct_layout(struct_ty)
+            .iter()
+            .position(|v| *v == field_name)
+    }
+
+    /// Returns the indices of the fields of the variant in the order they are laid out in its
+    /// payload. Fields without values are left out.
+    /// Returns the types of the fields of the variant, including the ones without values.
+    pub fn variant_field_types(&self, enum_ty: TypeId, variant: usize) -> Vec<TypeId> {
+        let Some(TypeKind::Enum(ty)) = &enum_ty.sym(self.sym_table).kind else {
+            panic!("enum type expected");
+        };
+        ty.variants[variant].field_types()
+    }
+
+    pub fn variant_layout(&self, enum_ty: TypeId, variant: usize) -> Vec<usize> {
+        let Some(TypeKind::Enum(ty)) = &enum_ty.sym(self.sym_table).kind else {
+            panic!("enum type expected");
+        };
+        ty.variants[variant]
+            .field_types()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, ty)| self.has_value(*ty))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Generates the expressions in order, each of which stays on the stack while the following
+    /// ones are generated.
+    pub fn codegen_operands<'e>(
+        &mut self,
+        exprs: impl IntoIterator<Item = &'e Expr>,
+    ) -> Result<(), CodegenError> {
+        let pending = self.pending;
+        for expr in exprs {
+            expr.codegen(self)?;
+            if self.has_value(expr.ty) {
+                self.pending += 1;
+            }
+        }
+        self.pending = pending;
+        Ok(())
+    }
+
+    pub fn push_loop(&mut self, start: usize, label: Option<String>) {
+        self.loops.push(LoopTarget {
+            label,
+            start,
+            pending: self.pending,
+            breaks: vec![],
+        });
+    }
+
+    /// Pops the innermost loop, patching its `break`s to jump to the current end of the function.
+    pub fn pop_loop(&mut self) {
+        let target = self.loops.pop().expect("no loop to pop");
+        let func = self.get_current_fi_mut();
+        let end = func.len();
+        for pos in target.breaks {
+            func.set_inst(pos, Inst::Jmp(end as isize - pos as isize));
+        }
+    }
+
+    /// Returns the target loop of `break` and `continue`, which is the innermost loop with the
+    /// label, or the innermost loop if `label` is `None`.
+    fn get_loop_mut(&mut self, label: Option<&str>) -> &mut LoopTarget {
+        self.loops
+            .iter_mut()
+            .rev()
+            .find(|v| label.is_none() || v.label.as_deref() == label)
+            .expect("target loop")
+    }
+
     pub fn push_bound(&mut self) {
         self.local_cnts.push(self.active_local_cnt);
     }
@@ -127,11 +366,23 @@ impl<'a> Context<'a> {
     }
 }
 
+/// A loop enclosing the code being generated.
+struct LoopTarget {
+    label: Option<String>,
+    /// where `continue` jumps to.
+    start: usize,
+    /// values pending when the loop is entered, which `break` and `continue` discard down to.
+    pending: usize,
+    /// placeholder jumps of the `break`s, which are patched to the end of the loop.
+    breaks: Vec<usize>,
+}
+
 #[derive(Debug)]
 pub enum CodegenError {
     UnsupportedItem,
     NoMainFunction,
-    UnsupportedMainFunctionSig,
+    UnsupportedMainFunctionSig { origin: Span },
+    UnsupportedExternSignature { name: String, origin: Span },
 }
 
 trait Codegen {
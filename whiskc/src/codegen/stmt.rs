@@ -31,9 +31,7 @@ impl Codegen for Block {
 impl Codegen for ExprStmt {
     fn codegen(&self, ctx: &mut super::Context) -> Result<(), super::CodegenError> {
         self.expr.codegen(ctx)?;
-        if self.expr.ty != ctx.sym_table.common_type().unit
-            && self.expr.ty != ctx.sym_table.common_type().never
-        {
+        if ctx.has_value(self.expr.ty) {
             ctx.get_current_fi_mut().push_inst(Inst::Pop);
         }
         Ok(())
@@ -63,8 +61,10 @@ impl Codegen for LetStmt {
     fn codegen(&self, ctx: &mut super::Context) -> Result<(), super::CodegenError> {
         self.value.codegen(ctx)?;
 
-        let id = ctx.get_local(self.var_id);
-        ctx.get_current_fi_mut().push_inst(Inst::Store(id));
+        if ctx.has_value(self.var_id.sym(ctx.sym_table).ty) {
+            let id = ctx.get_local(self.var_id);
+            ctx.get_current_fi_mut().push_inst(Inst::Store(id));
+        }
 
         Ok(())
     }
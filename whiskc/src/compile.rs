@@ -1,6 +1,11 @@
 use std::{fs, path::PathBuf};
 
-use crate::{ast, codegen::codegen_wsk_vm, module};
+use crate::{
+    ast,
+    codegen::codegen_wsk_vm,
+    diagnostics::{ColorMode, Diagnostic, Severity, SourceFile},
+    module,
+};
 
 pub struct CompileSwitch {
     pub do_parse_ast: bool,
@@ -8,6 +13,7 @@ pub struct CompileSwitch {
     pub do_resolve_module: bool,
     pub print_module: bool,
     pub do_codegen: bool,
+    pub color_mode: ColorMode,
 }
 impl Default for CompileSwitch {
     fn default() -> Self {
@@ -17,11 +23,13 @@ impl Default for CompileSwitch {
             do_resolve_module: true,
             print_module: false,
             do_codegen: true,
+            color_mode: ColorMode::Colored,
         }
     }
 }
 
-pub fn compile(source_path: PathBuf, switches: CompileSwitch) {
+/// Compiles the source file with the switches, returning whether it compiled without errors.
+pub fn compile(source_path: PathBuf, switches: CompileSwitch) -> bool {
     let source_name = source_path
         .file_stem()
         .expect("valid file name")
@@ -29,14 +37,20 @@ pub fn compile(source_path: PathBuf, switches: CompileSwitch) {
         .expect("valid file name enc")
         .to_owned();
 
+    let source = SourceFile::new(
+        source_path.display().to_string(),
+        &fs::read_to_string(&source_path).unwrap_or_default(),
+    );
+
     if !switches.do_parse_ast {
-        return;
+        return true;
     }
     let ast = match ast::parse(&source_path) {
         Ok(ast) => ast,
         Err(errors) => {
-            dbg!(&errors);
-            return;
+            let diags = errors.iter().map(Diagnostic::from);
+            report_diagnostics(diags, &source, &source_name, switches.color_mode);
+            return false;
         }
     };
     if switches.debug_ast {
@@ -44,13 +58,16 @@ pub fn compile(source_path: PathBuf, switches: CompileSwitch) {
     }
 
     if !switches.do_resolve_module {
-        return;
+        return true;
     }
-    let module = match module::resolve(&ast, source_name) {
+    let module = match module::resolve(&ast, source_name.clone()) {
         Ok(module) => module,
-        Err(errs) => {
-            dbg!(&errs);
-            return;
+        Err((errors, table)) => {
+            let diags = errors
+                .iter()
+                .map(|e| Diagnostic::from_resolve_error(e, &table));
+            report_diagnostics(diags, &source, &source_name, switches.color_mode);
+            return false;
         }
     };
     if switches.print_module {
@@ -60,13 +77,14 @@ pub fn compile(source_path: PathBuf, switches: CompileSwitch) {
     }
 
     if !switches.do_codegen {
-        return;
+        return true;
     }
     let prog = match codegen_wsk_vm(&module) {
         Ok(prog) => prog,
         Err(e) => {
-            eprintln!("{:?}", e);
-            return;
+            let diags = [Diagnostic::from(&e)];
+            report_diagnostics(diags, &source, &source_name, switches.color_mode);
+            return false;
         }
     };
 
@@ -74,4 +92,33 @@ pub fn compile(source_path: PathBuf, switches: CompileSwitch) {
     out_path.set_extension("wc");
     println!("wrote binary to {}", out_path.display());
     fs::write(out_path, prog.to_bin()).unwrap();
+    true
+}
+
+fn report_diagnostics(
+    diags: impl IntoIterator<Item = Diagnostic>,
+    source: &SourceFile,
+    source_name: &str,
+    color_mode: ColorMode,
+) {
+    let mut error_cnt = 0;
+    for diag in diags {
+        if diag.severity == Severity::Error {
+            error_cnt += 1;
+        }
+        eprintln!("{}", diag.render(source, color_mode));
+    }
+
+    if error_cnt > 0 {
+        let summary = Diagnostic::error(
+            "",
+            format!(
+                "could not compile `{}` due to {} previous error{}",
+                source_name,
+                error_cnt,
+                if error_cnt == 1 { "" } else { "s" }
+            ),
+        );
+        eprintln!("{}", summary.render(source, color_mode));
+    }
 }
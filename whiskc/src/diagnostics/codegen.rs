@@ -0,0 +1,28 @@
+use crate::codegen::CodegenError;
+
+use super::{Diagnostic, Label};
+
+impl From<&CodegenError> for Diagnostic {
+    fn from(e: &CodegenError) -> Self {
+        match e {
+            CodegenError::UnsupportedItem => Diagnostic::error(
+                "E0901",
+                "this program uses an item the code generator does not support yet",
+            ),
+            CodegenError::NoMainFunction => Diagnostic::error("E0902", "`main` function not found")
+                .with_note("the program entry point must be declared as `func main() int { ... }`"),
+            CodegenError::UnsupportedMainFunctionSig { origin } => {
+                Diagnostic::error("E0903", "`main` function has an unsupported signature")
+                    .with_label(Label::primary(*origin, None))
+                    .with_note("`main` must take no parameters and return `int`")
+            }
+            CodegenError::UnsupportedExternSignature { name, origin } => Diagnostic::error(
+                "E0904",
+                format!("extern function `{}` has an unsupported signature", name),
+            )
+            .with_label(Label::primary(*origin, None))
+            .with_note("parameters of extern functions must be `int`, `bool` or `str`")
+            .with_note("extern functions must return `int`, `bool`, `str` or nothing"),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use core::fmt;
+
+use crate::ast::location::Span;
+
+mod codegen;
+mod parse;
+mod render;
+mod resolve;
+
+pub use render::{ColorMode, SourceFile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Error => "error",
+                Self::Warning => "warning",
+                Self::Note => "note",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: Option<String>,
+    /// primary labels point at the offending code, secondary labels give context to it.
+    pub is_primary: bool,
+}
+impl Label {
+    pub fn primary(span: Span, message: impl Into<Option<String>>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+            is_primary: true,
+        }
+    }
+
+    pub fn secondary(span: Span, message: impl Into<Option<String>>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+            is_primary: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: &'static str,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+}
+impl Diagnostic {
+    pub fn new(severity: Severity, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code,
+            message: message.into(),
+            labels: vec![],
+            notes: vec![],
+        }
+    }
+
+    pub fn error(code: &'static str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    pub fn with_label(mut self, label: Label) -> Self {
+        self.labels.push(label);
+        self
+    }
+
+    pub fn with_primary(self, span: Span, message: impl Into<String>) -> Self {
+        self.with_label(Label::primary(span, message.into()))
+    }
+
+    pub fn with_secondary(self, span: Span, message: impl Into<String>) -> Self {
+        self.with_label(Label::secondary(span, message.into()))
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Returns the span of the first primary label, which is the location reported in the
+    /// diagnostic's header.
+    pub fn primary_span(&self) -> Option<Span> {
+        self.labels
+            .iter()
+            .find(|v| v.is_primary)
+            .or(self.labels.first())
+            .map(|v| v.span)
+    }
+}
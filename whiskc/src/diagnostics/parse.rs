@@ -0,0 +1,154 @@
+use crate::ast::{
+    location::Located,
+    parsing::{
+        lexer::LexError,
+        nodes::{
+            expr::ExprParseError, func::FunctionParseError, item::ItemParseError,
+            pat::PatternParseError, stmt::StmtParseError, ty::TypeParseError,
+        },
+        ParseError,
+    },
+};
+
+use super::Diagnostic;
+
+impl From<&Located<ParseError>> for Diagnostic {
+    fn from(Located(e, loc): &Located<ParseError>) -> Self {
+        let loc = *loc;
+        match e {
+            ParseError::LexError(e) => match e {
+                LexError::UnterminatedString => {
+                    Diagnostic::error("E0160", "unterminated string literal")
+                        .with_primary(loc, "missing closing `\"`")
+                }
+                LexError::InvalidEscapeSequence(c) => Diagnostic::error(
+                    "E0161",
+                    format!("unknown character escape `\\{}`", c.escape_debug()),
+                )
+                .with_primary(loc, "unknown escape")
+                .with_note("supported escapes are `\\n`, `\\t`, `\\r`, `\\0`, `\\\\` and `\\\"`"),
+                LexError::InvalidIntegerSuffix(suffix) => Diagnostic::error(
+                    "E0162",
+                    format!("invalid suffix `{}` for integer literal", suffix),
+                )
+                .with_primary(loc, "invalid suffix")
+                .with_note("the suffix must be one of `i8`, `i16`, `i32`, `i64`, `u8`, `u16`, `u32` and `u64`"),
+                LexError::InvalidFloatSuffix(suffix) => Diagnostic::error(
+                    "E0163",
+                    format!("invalid suffix `{}` for float literal", suffix),
+                )
+                .with_primary(loc, "invalid suffix")
+                .with_note("float literals cannot have a suffix, use `as` to convert them"),
+                LexError::IntegerTooLarge => {
+                    Diagnostic::error("E0164", "integer literal is too large")
+                        .with_primary(loc, "this does not fit into any integer type")
+                        .with_note("the largest integer literal is `18446744073709551615`, the largest `u64`")
+                }
+                LexError::MissingIntegerDigits(prefix) => Diagnostic::error(
+                    "E0165",
+                    format!("no digits after the prefix `{}`", prefix),
+                )
+                .with_primary(loc, "expected digits after the prefix"),
+                LexError::InvalidDigit { digit, radix } => Diagnostic::error(
+                    "E0166",
+                    format!("invalid digit `{}` in a base {} literal", digit, radix),
+                )
+                .with_primary(loc, format!("`{}` is not a base {} digit", digit, radix)),
+                LexError::MisplacedSeparator => {
+                    Diagnostic::error("E0167", "`_` in a number literal must be followed by a digit")
+                        .with_primary(loc, "misplaced `_`")
+                        .with_note("`_` separates digits, as in `1_000_000`")
+                }
+            },
+            ParseError::MissingKeyword(kw) => {
+                Diagnostic::error("E0101", format!("expected `{}`", kw))
+                    .with_primary(loc, format!("expected `{}` keyword here", kw))
+            }
+            ParseError::MissingDelimiter(delim) => {
+                Diagnostic::error("E0102", format!("expected `{}`", delim))
+                    .with_primary(loc, format!("expected `{}` here", delim))
+            }
+            ParseError::MissingIdentifier(what) => {
+                Diagnostic::error("E0103", format!("expected {}", what))
+                    .with_primary(loc, "expected an identifier here")
+            }
+            ParseError::MissingOperator(op) => {
+                Diagnostic::error("E0104", format!("expected `{}`", op))
+                    .with_primary(loc, format!("expected `{}` operator here", op))
+            }
+            ParseError::ItemParseError(ItemParseError::UnexpectedToken(tok)) => {
+                Diagnostic::error("E0110", format!("expected an item, found {}", tok))
+                    .with_primary(loc, "expected `func`, `extern func` or `type`")
+            }
+            ParseError::FuncParseError(FunctionParseError::MissingFunctionBody { func_name }) => {
+                Diagnostic::error("E0120", format!("function `{}` has no body", func_name))
+                    .with_primary(loc, "expected a block after this function's signature")
+                    .with_note("use `extern func` to declare a function without a body")
+            }
+            ParseError::TypeParseError(e) => match e {
+                TypeParseError::UnexpectedToken(tok) => {
+                    Diagnostic::error("E0130", format!("expected a type, found {}", tok))
+                        .with_primary(loc, "expected a type")
+                }
+                TypeParseError::UnexpectedInfixOperator(tok) => {
+                    Diagnostic::error("E0131", format!("unexpected {} in type", tok))
+                        .with_primary(loc, "unexpected token")
+                }
+                TypeParseError::IntegerSizeOutOfRange(size) => {
+                    Diagnostic::error("E0132", format!("invalid integer size `{}`", size))
+                        .with_primary(loc, "integer size out of range")
+                }
+                TypeParseError::ExpectedArrayLength => {
+                    Diagnostic::error("E0133", "expected array length")
+                        .with_primary(loc, "expected an integer length")
+                }
+                TypeParseError::InvalidArrayLength(len) => {
+                    Diagnostic::error("E0134", format!("invalid array length `{}`", len))
+                        .with_primary(loc, "array length must not be negative")
+                }
+            },
+            ParseError::StmtParseError(StmtParseError::UnexpectedToken(tok)) => {
+                Diagnostic::error("E0140", format!("expected a statement, found {}", tok))
+                    .with_primary(loc, "expected a statement")
+            }
+            ParseError::ExprParseError(e) => match e {
+                ExprParseError::UnexpectedToken(tok) => {
+                    Diagnostic::error("E0150", format!("expected an expression, found {}", tok))
+                        .with_primary(loc, "expected an expression")
+                }
+                ExprParseError::UnexpectedInfixOperator(tok) => {
+                    Diagnostic::error("E0151", format!("unexpected {} after expression", tok))
+                        .with_primary(loc, "not an infix operator")
+                }
+                ExprParseError::ExpectedIdentForStructInit(_) => {
+                    Diagnostic::error("E0152", "expected a struct name before `{`")
+                        .with_primary(loc, "not a struct name")
+                }
+                ExprParseError::ExpectedLoopAfterLabel(tok) => Diagnostic::error(
+                    "E0153",
+                    format!(
+                        "expected `loop`, `while` or `for` after label, found {}",
+                        tok
+                    ),
+                )
+                .with_primary(loc, "expected a loop")
+                .with_note("only loops can be labeled"),
+                ExprParseError::ExpectedTypeNameForPath(_) => {
+                    Diagnostic::error("E0154", "expected a type name before `::`")
+                        .with_primary(loc, "not a type name")
+                }
+            },
+            ParseError::PatternParseError(e) => match e {
+                PatternParseError::UnexpectedToken(tok) => {
+                    Diagnostic::error("E0170", format!("expected a pattern, found {}", tok))
+                        .with_primary(loc, "expected a pattern")
+                        .with_note("patterns are `_`, names, integers, booleans and integer ranges")
+                }
+                PatternParseError::ExpectedInteger(tok) => {
+                    Diagnostic::error("E0171", format!("expected an integer, found {}", tok))
+                        .with_primary(loc, "expected an integer literal")
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::ast::location::{Location, Span};
+
+use super::{Diagnostic, Label, Severity};
+
+const TAB_WIDTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// ANSI escape sequences for terminals.
+    Colored,
+    /// no escape sequences, suitable for CI logs and files.
+    Plain,
+}
+
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    lines: Vec<String>,
+}
+impl SourceFile {
+    pub fn new(name: impl Into<String>, text: &str) -> Self {
+        Self {
+            name: name.into(),
+            lines: text.lines().map(|v| v.to_owned()).collect(),
+        }
+    }
+
+    /// Returns the source line at the 1-based `line`.
+    pub fn line(&self, line: u32) -> Option<&str> {
+        if line == 0 {
+            return None;
+        }
+        self.lines.get(line as usize - 1).map(|v| v.as_str())
+    }
+    /// Returns the span moved to just past the end of the last line if it starts after it, as the
+    /// end of the file does if the source ends with a newline.
+    pub fn clamp(&self, span: Span) -> Span {
+        let last = self.lines.len() as u32;
+        if last == 0 || span.start.line <= last {
+            return span;
+        }
+        let end = Location::new(last, line_len(self, last) + 1);
+        Span::new(end, end)
+    }
+}
+
+struct Painter(ColorMode);
+impl Painter {
+    const RESET: &'static str = "\x1b[0m";
+    const BOLD: &'static str = "\x1b[1m";
+    const RED: &'static str = "\x1b[1;31m";
+    const YELLOW: &'static str = "\x1b[1;33m";
+    const GREEN: &'static str = "\x1b[1;32m";
+    const BLUE: &'static str = "\x1b[1;34m";
+
+    fn paint(&self, color: &str, s: &str) -> String {
+        match self.0 {
+            ColorMode::Colored => format!("{}{}{}", color, s, Self::RESET),
+            ColorMode::Plain => s.to_owned(),
+        }
+    }
+
+    fn severity(&self, severity: Severity, s: &str) -> String {
+        self.paint(severity_color(severity), s)
+    }
+}
+
+struct Mark<'a> {
+    start_col: u32,
+    end_col: u32,
+    label: &'a Label,
+    show_message: bool,
+}
+
+impl Diagnostic {
+    pub fn render(&self, src: &SourceFile, mode: ColorMode) -> String {
+        let p = Painter(mode);
+        let mut out = String::new();
+
+        let header = if self.code.is_empty() {
+            self.severity.to_string()
+        } else {
+            format!("{}[{}]", self.severity, self.code)
+        };
+        writeln!(
+            out,
+            "{}{}",
+            p.severity(self.severity, &header),
+            p.paint(Painter::BOLD, &format!(": {}", self.message))
+        )
+        .unwrap();
+
+        // labels with unknown locations cannot be pointed at in the source.
+        let labels: Vec<Label> = self
+            .labels
+            .iter()
+            .filter(|v| v.span.start.line != 0)
+            .map(|v| Label {
+                span: src.clamp(v.span),
+                ..v.clone()
+            })
+            .filter(|v| src.line(v.span.start.line).is_some())
+            .collect();
+        let labels: Vec<&Label> = labels.iter().collect();
+
+        let gutter_width = labels
+            .iter()
+            .map(|v| v.span.end.line.max(v.span.start.line).to_string().len())
+            .max()
+            .unwrap_or(0);
+        let gutter = " ".repeat(gutter_width);
+
+        if let Some(span) = self
+            .primary_span()
+            .filter(|v| v.start.line != 0)
+            .map(|v| src.clamp(v))
+        {
+            writeln!(
+                out,
+                "{}{} {}:{}:{}",
+                gutter,
+                p.paint(Painter::BLUE, "-->"),
+                src.name,
+                span.start.line,
+                span.start.col
+            )
+            .unwrap();
+        }
+
+        if !labels.is_empty() {
+            writeln!(out, "{} {}", gutter, p.paint(Painter::BLUE, "|")).unwrap();
+            let marks = collect_marks(&labels, src);
+
+            let mut prev_line: Option<u32> = None;
+            for (line, marks) in &marks {
+                if prev_line.is_some_and(|v| v + 1 < *line) {
+                    writeln!(out, "{}", p.paint(Painter::BLUE, "...")).unwrap();
+                }
+                prev_line = Some(*line);
+
+                let text = src.line(*line).unwrap_or_default();
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    p.paint(Painter::BLUE, &format!("{:>w$}", line, w = gutter_width)),
+                    p.paint(Painter::BLUE, "|"),
+                    expand_tabs(text)
+                )
+                .unwrap();
+
+                for mark in marks {
+                    let pad = display_width(text, 1, mark.start_col);
+                    let width = display_width(text, mark.start_col, mark.end_col + 1).max(1);
+                    let (ch, color) = if mark.label.is_primary {
+                        ('^', severity_color(self.severity))
+                    } else {
+                        ('-', Painter::BLUE)
+                    };
+                    let mut underline = ch.to_string().repeat(width);
+                    if let Some(msg) = mark.label.message.as_ref().filter(|_| mark.show_message) {
+                        underline.push(' ');
+                        underline.push_str(msg);
+                    }
+                    writeln!(
+                        out,
+                        "{} {} {}{}",
+                        gutter,
+                        p.paint(Painter::BLUE, "|"),
+                        " ".repeat(pad),
+                        p.paint(color, &underline)
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        if !self.notes.is_empty() {
+            if !labels.is_empty() {
+                writeln!(out, "{} {}", gutter, p.paint(Painter::BLUE, "|")).unwrap();
+            }
+            for note in &self.notes {
+                writeln!(
+                    out,
+                    "{} {} {}",
+                    gutter,
+                    p.paint(Painter::BLUE, "="),
+                    p.paint(Painter::BOLD, &format!("note: {}", note))
+                )
+                .unwrap();
+            }
+        }
+
+        out
+    }
+}
+
+fn severity_color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => Painter::RED,
+        Severity::Warning => Painter::YELLOW,
+        Severity::Note => Painter::GREEN,
+    }
+}
+
+/// Splits the labels into per-line underline marks, ordered by line then column.
+/// A label spanning multiple lines underlines the rest of its first line, and its message is shown
+/// under its last line.
+fn collect_marks<'a>(labels: &[&'a Label], src: &SourceFile) -> BTreeMap<u32, Vec<Mark<'a>>> {
+    let mut marks: BTreeMap<u32, Vec<Mark>> = BTreeMap::new();
+    for label in labels {
+        let Location {
+            line: start_line,
+            col: start_col,
+        } = label.span.start;
+        let Location {
+            line: end_line,
+            col: end_col,
+        } = label.span.end;
+
+        if end_line <= start_line || src.line(end_line).is_none() {
+            marks.entry(start_line).or_default().push(Mark {
+                start_col,
+                end_col: if end_line == start_line {
+                    end_col.max(start_col)
+                } else {
+                    line_len(src, start_line).max(start_col)
+                },
+                label,
+                show_message: true,
+            });
+        } else {
+            marks.entry(start_line).or_default().push(Mark {
+                start_col,
+                end_col: line_len(src, start_line).max(start_col),
+                label,
+                show_message: false,
+            });
+            let first_col = src
+                .line(end_line)
+                .and_then(|v| v.chars().position(|c| !c.is_whitespace()))
+                .map(|v| v as u32 + 1)
+                .unwrap_or(1)
+                .min(end_col);
+            marks.entry(end_line).or_default().push(Mark {
+                start_col: first_col,
+                end_col,
+                label,
+                show_message: true,
+            });
+        }
+    }
+    for line_marks in marks.values_mut() {
+        line_marks.sort_by_key(|v| v.start_col);
+    }
+    marks
+}
+
+fn line_len(src: &SourceFile, line: u32) -> u32 {
+    src.line(line)
+        .map(|v| v.chars().count() as u32)
+        .unwrap_or(0)
+}
+
+fn expand_tabs(text: &str) -> String {
+    text.replace('\t', &" ".repeat(TAB_WIDTH))
+}
+
+/// Returns the display width of the characters in the 1-based column range `[from, to)`.
+/// Columns past the end of the line are one cell wide.
+fn display_width(text: &str, from: u32, to: u32) -> usize {
+    let mut chars = text.chars().skip(from.saturating_sub(1) as usize);
+    (from..to)
+        .map(|_| match chars.next() {
+            Some('\t') => TAB_WIDTH,
+            _ => 1,
+        })
+        .sum()
+}
@@ -0,0 +1,680 @@
+use crate::{
+    module::errors::{
+        ControlFlowError, IdentResolveError, ResolveError, TypeResolveError, ValueResolveError,
+    },
+    symbol::{
+        ty::{TypeKind, VariantKind},
+        SymbolTable, TypeId,
+    },
+};
+
+use super::Diagnostic;
+
+impl Diagnostic {
+    pub fn from_resolve_error(e: &ResolveError, table: &SymbolTable) -> Self {
+        match e {
+            ResolveError::ValueResolveError(e) => from_value_error(e, table),
+            ResolveError::IdentResolveError(e) => from_ident_error(e, table),
+            ResolveError::TypeResolveError(e) => from_type_error(e, table),
+            ResolveError::ControlFlowError(e) => from_control_flow_error(e),
+        }
+    }
+}
+
+/// Returns the quoted name of the type, or a placeholder if the type is not in the table, so
+/// that rendering a diagnostic never fails.
+fn ty(table: &SymbolTable, ty: TypeId) -> String {
+    format!("`{}`", ty_name(table, ty))
+}
+
+fn ty_name(table: &SymbolTable, ty: TypeId) -> &str {
+    table.get_type(ty).map_or("{unknown}", |v| v.name.as_str())
+}
+
+fn variant_kind<'a>(
+    table: &'a SymbolTable,
+    enum_ty: TypeId,
+    name: &str,
+) -> Option<&'a VariantKind> {
+    let Some(TypeKind::Enum(v)) = &table.get_type(enum_ty)?.kind else {
+        return None;
+    };
+    v.get_variant(name).map(|(_, v)| &v.kind)
+}
+
+fn from_value_error(e: &ValueResolveError, table: &SymbolTable) -> Diagnostic {
+    match e {
+        ValueResolveError::IntegerOutOfRange {
+            is_signed,
+            int_size,
+            val,
+        } => {
+            let ty_name = format!("{}{}", if *is_signed { "i" } else { "u" }, int_size);
+            Diagnostic::error(
+                "E0201",
+                format!("integer literal is out of range for `{}`", ty_name),
+            )
+            .with_primary(
+                val.1,
+                format!("`{}` does not fit into `{}`", val.0, ty_name),
+            )
+        }
+        ValueResolveError::ArgumentCountMismatch {
+            func_name,
+            expect_count,
+            actual_count,
+        } => Diagnostic::error(
+            "E0202",
+            format!(
+                "this function takes {} argument{} but {} {} supplied",
+                expect_count,
+                if *expect_count == 1 { "" } else { "s" },
+                actual_count,
+                if *actual_count == 1 { "was" } else { "were" },
+            ),
+        )
+        .with_primary(
+            func_name.1,
+            format!(
+                "expected {} argument(s) for `{}`",
+                expect_count, func_name.0
+            ),
+        ),
+        ValueResolveError::ArrayLengthMismatch {
+            loc,
+            expect_count,
+            actual_count,
+        } => Diagnostic::error("E0203", "mismatched array length").with_primary(
+            *loc,
+            format!(
+                "expected {} element(s), found {}",
+                expect_count, actual_count
+            ),
+        ),
+        ValueResolveError::MissingStructFields { struct_ty, fields } => Diagnostic::error(
+            "E0204",
+            format!(
+                "missing field{} {} in initializer of {}",
+                if fields.len() == 1 { "" } else { "s" },
+                fields
+                    .iter()
+                    .map(|v| format!("`{}`", v))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                ty(table, struct_ty.0)
+            ),
+        )
+        .with_primary(struct_ty.1, "missing fields in this initializer"),
+        ValueResolveError::EmptyRangePattern(loc) => {
+            Diagnostic::error("E0205", "range pattern matches no value")
+                .with_primary(*loc, "this range is empty")
+        }
+        ValueResolveError::VariantKindMismatch { enum_ty, variant } => {
+            let name = format!("{}::{}", ty_name(table, *enum_ty), variant.0);
+            let (kind, usage) = match variant_kind(table, *enum_ty, &variant.0) {
+                Some(VariantKind::Unit) => ("a unit variant", format!("`{}`", name)),
+                Some(VariantKind::Tuple(_)) => ("a tuple variant", format!("`{}(..)`", name)),
+                Some(VariantKind::Struct(_)) => {
+                    ("a struct variant", format!("`{} {{ .. }}`", name))
+                }
+                None => unreachable!("variant of the enum"),
+            };
+            Diagnostic::error("E0206", format!("`{}` is {}", name, kind))
+                .with_primary(variant.1, "used as another kind of variant")
+                .with_note(format!("use {} instead", usage))
+        }
+        ValueResolveError::PatternFieldCountMismatch {
+            loc,
+            expect_count,
+            actual_count,
+        } => Diagnostic::error(
+            "E0207",
+            format!(
+                "this pattern has {} field{}, but the variant has {}",
+                actual_count,
+                if *actual_count == 1 { "" } else { "s" },
+                expect_count
+            ),
+        )
+        .with_primary(*loc, format!("expected {} field(s)", expect_count)),
+        ValueResolveError::ArrayTooLong { len, max } => {
+            Diagnostic::error("E0208", format!("array length `{}` is too large", len.0))
+                .with_primary(len.1, format!("arrays have at most {} elements", max))
+        }
+    }
+}
+
+fn from_ident_error(e: &IdentResolveError, table: &SymbolTable) -> Diagnostic {
+    match e {
+        IdentResolveError::GlobalIdentAlreadyUsed {
+            ident,
+            first_origin,
+            dup_origin,
+        } => Diagnostic::error(
+            "E0301",
+            format!("the name `{}` is defined multiple times", ident),
+        )
+        .with_primary(*dup_origin, format!("`{}` redefined here", ident))
+        .with_secondary(
+            *first_origin,
+            format!("previous definition of `{}` here", ident),
+        ),
+        IdentResolveError::VarNameAlreadyUsed {
+            ident,
+            first_origin,
+            dup_origin,
+        } => Diagnostic::error(
+            "E0302",
+            format!(
+                "variable `{}` is declared multiple times in the same scope",
+                ident
+            ),
+        )
+        .with_primary(
+            dup_origin.1,
+            format!(
+                "`{}` of type {} redeclared here",
+                ident,
+                ty(table, dup_origin.0)
+            ),
+        )
+        .with_secondary(
+            first_origin.1,
+            format!(
+                "previous declaration of `{}` of type {} here",
+                ident,
+                ty(table, first_origin.0)
+            ),
+        ),
+        IdentResolveError::UnknownIdentifier(ident) => {
+            Diagnostic::error("E0303", format!("cannot find `{}` in this scope", ident.0))
+                .with_primary(ident.1, "not found in this scope")
+        }
+        IdentResolveError::UnexpectedAttrib { attribute } => {
+            Diagnostic::error("E0304", "unexpected attribute")
+                .with_primary(*attribute, "this attribute is not allowed here")
+        }
+        IdentResolveError::FieldNameAlreadyUsed {
+            ident,
+            first_origin,
+            dup_origin,
+        } => Diagnostic::error(
+            "E0305",
+            format!("field `{}` is specified more than once", ident),
+        )
+        .with_primary(*dup_origin, "used again here")
+        .with_secondary(*first_origin, format!("first use of `{}`", ident)),
+        IdentResolveError::TypeUsedAsValue(ident) => Diagnostic::error(
+            "E0306",
+            format!("expected a value, found type `{}`", ident.0),
+        )
+        .with_primary(ident.1, "not a value"),
+        IdentResolveError::FunctionUsedAsValue(ident) => Diagnostic::error(
+            "E0313",
+            format!("expected a value, found function `{}`", ident.0),
+        )
+        .with_primary(ident.1, "functions can only be called"),
+        IdentResolveError::UnknownField {
+            struct_ty,
+            field_name,
+        } => Diagnostic::error(
+            "E0307",
+            format!(
+                "no field `{}` on type {}",
+                field_name.0,
+                ty(table, *struct_ty)
+            ),
+        )
+        .with_primary(field_name.1, "unknown field"),
+        IdentResolveError::VariantNameAlreadyUsed {
+            ident,
+            first_origin,
+            dup_origin,
+        } => Diagnostic::error(
+            "E0310",
+            format!("variant `{}` is declared more than once", ident),
+        )
+        .with_primary(*dup_origin, "declared again here")
+        .with_secondary(*first_origin, format!("first declaration of `{}`", ident)),
+        IdentResolveError::UnknownVariant { enum_ty, variant } => Diagnostic::error(
+            "E0311",
+            format!("no variant `{}` in enum {}", variant.0, ty(table, *enum_ty)),
+        )
+        .with_primary(variant.1, "unknown variant"),
+        IdentResolveError::UnknownMethod {
+            ty: ty_,
+            method_name,
+        } => Diagnostic::error(
+            "E0312",
+            format!("no method `{}` on type {}", method_name.0, ty(table, *ty_)),
+        )
+        .with_primary(method_name.1, "unknown method"),
+        IdentResolveError::UnknownLabel(label) => {
+            Diagnostic::error("E0308", format!("use of undeclared label `'{}`", label.0))
+                .with_primary(label.1, "no enclosing loop has this label")
+        }
+        IdentResolveError::LabelAlreadyUsed {
+            label,
+            first_origin,
+            dup_origin,
+        } => Diagnostic::error(
+            "E0309",
+            format!("label `'{}` shadows a label of an enclosing loop", label),
+        )
+        .with_primary(*dup_origin, "label already in scope")
+        .with_secondary(*first_origin, format!("first declaration of `'{}`", label)),
+    }
+}
+
+fn from_type_error(e: &TypeResolveError, table: &SymbolTable) -> Diagnostic {
+    match e {
+        TypeResolveError::ReturnTypeMismatch {
+            function_name,
+            expected_type,
+            actual_type,
+        } => Diagnostic::error(
+            "E0401",
+            format!("mismatched return type in function `{}`", function_name),
+        )
+        .with_primary(
+            actual_type.1,
+            format!(
+                "expected {}, found {}",
+                ty(table, *expected_type),
+                ty(table, actual_type.0)
+            ),
+        ),
+        TypeResolveError::NonBoolInIfCond(cond) => {
+            Diagnostic::error("E0402", "`if` condition must be a `bool`").with_primary(
+                cond.1,
+                format!("expected `bool`, found {}", ty(table, cond.0)),
+            )
+        }
+        TypeResolveError::BlockBranchTypeMismatch { branch, other } => {
+            Diagnostic::error("E0403", "branches have incompatible types")
+                .with_primary(branch.1, format!("this is {}", ty(table, branch.0)))
+                .with_secondary(
+                    other.1,
+                    format!("expected because this is {}", ty(table, other.0)),
+                )
+        }
+        TypeResolveError::NonAssignableType(target) => {
+            Diagnostic::error("E0404", "invalid left-hand side of assignment").with_primary(
+                target.1,
+                format!(
+                    "cannot assign to this expression of type {}",
+                    ty(table, target.0)
+                ),
+            )
+        }
+        TypeResolveError::AssignmentTypeMismatch {
+            target_ty,
+            value_ty,
+        } => Diagnostic::error("E0405", "mismatched types in assignment")
+            .with_primary(
+                value_ty.1,
+                format!(
+                    "expected {}, found {}",
+                    ty(table, target_ty.0),
+                    ty(table, value_ty.0)
+                ),
+            )
+            .with_secondary(target_ty.1, format!("this is {}", ty(table, target_ty.0))),
+        TypeResolveError::UnknownTypeForIdent(ident) => Diagnostic::error(
+            "E0406",
+            format!("cannot find type `{}` in this scope", ident.0),
+        )
+        .with_primary(ident.1, "not found in this scope"),
+        TypeResolveError::NonBoolUsedInNotOp(operand) => Diagnostic::error(
+            "E0407",
+            format!(
+                "cannot apply unary operator `!` to {}",
+                ty(table, operand.0)
+            ),
+        )
+        .with_primary(operand.1, "expected `bool`"),
+        TypeResolveError::NonNumericInUnaryOp(op, operand) => Diagnostic::error(
+            "E0408",
+            format!(
+                "cannot apply unary operator `{}` to {}",
+                op,
+                ty(table, operand.0)
+            ),
+        )
+        .with_primary(operand.1, "expected a numeric type"),
+        TypeResolveError::UnsignedIntegerInUnaryOp(op) => Diagnostic::error(
+            "E0409",
+            format!(
+                "cannot apply unary operator `{}` to an unsigned integer",
+                op.0
+            ),
+        )
+        .with_primary(op.1, "unsigned values cannot be negated"),
+        TypeResolveError::NonNumericTypeInBinaryOp { op, ty: operand } => Diagnostic::error(
+            "E0410",
+            format!(
+                "cannot apply binary operator `{}` to {}",
+                op.0,
+                ty(table, operand.0)
+            ),
+        )
+        .with_primary(operand.1, "expected a numeric type")
+        .with_secondary(op.1, "operator used here"),
+        TypeResolveError::NonIntegerInUnaryOp(op, operand) => Diagnostic::error(
+            "E0433",
+            format!(
+                "cannot apply unary operator `{}` to {}",
+                op,
+                ty(table, operand.0)
+            ),
+        )
+        .with_primary(operand.1, "expected an integer type"),
+        TypeResolveError::NonIntegerTypeInBinaryOp { op, ty: operand } => Diagnostic::error(
+            "E0434",
+            format!(
+                "cannot apply binary operator `{}` to {}",
+                op.0,
+                ty(table, operand.0)
+            ),
+        )
+        .with_primary(operand.1, "expected an integer type")
+        .with_secondary(op.1, "operator used here"),
+        TypeResolveError::UnorderedTypeInBinaryOp { op, ty: operand } => Diagnostic::error(
+            "E0411",
+            format!("values of type {} cannot be ordered", ty(table, operand.0)),
+        )
+        .with_primary(operand.1, "this value has no ordering")
+        .with_secondary(op.1, format!("required by `{}`", op.0)),
+        TypeResolveError::UncomparableTypeInBinaryOp { op, ty: operand } => Diagnostic::error(
+            "E0435",
+            format!("values of type {} cannot be compared", ty(table, operand.0)),
+        )
+        .with_primary(operand.1, "this expression has no value")
+        .with_secondary(op.1, format!("required by `{}`", op.0)),
+        TypeResolveError::UnsupportedBinaryOp(op) => {
+            Diagnostic::error("E0436", format!("`{}` is not a binary operator", op.0))
+                .with_primary(op.1, "not supported between two operands")
+        }
+        TypeResolveError::UnsupportedUnaryOp(op) => {
+            Diagnostic::error("E0437", format!("`{}` is not a unary operator", op.0))
+                .with_primary(op.1, "not supported before an operand")
+        }
+        TypeResolveError::UnexpectedTypeInBinaryOp {
+            op,
+            expect_type,
+            actual_type,
+        } => Diagnostic::error(
+            "E0412",
+            format!("mismatched operand type for binary operator `{}`", op.0),
+        )
+        .with_primary(
+            actual_type.1,
+            format!(
+                "expected {}, found {}",
+                ty(table, *expect_type),
+                ty(table, actual_type.0)
+            ),
+        )
+        .with_secondary(op.1, "operator used here"),
+        TypeResolveError::TypeMismatchInBinaryOp {
+            op,
+            left_ty,
+            right_ty,
+        } => Diagnostic::error(
+            "E0413",
+            format!("mismatched types in binary operator `{}`", op.0),
+        )
+        .with_primary(
+            op.1,
+            format!(
+                "{} and {} are not compatible",
+                ty(table, *left_ty),
+                ty(table, *right_ty)
+            ),
+        ),
+        TypeResolveError::CallOnNonFunctionType(callee) => Diagnostic::error(
+            "E0414",
+            format!("expected a function, found {}", ty(table, callee.0)),
+        )
+        .with_primary(callee.1, "this is not a function"),
+        TypeResolveError::ArgumentTypeMismatch {
+            func_name,
+            argument_index,
+            expect_type,
+            actual_type,
+        } => Diagnostic::error(
+            "E0415",
+            format!("mismatched type for argument {}", argument_index + 1),
+        )
+        .with_primary(
+            actual_type.1,
+            format!(
+                "expected {}, found {}",
+                ty(table, *expect_type),
+                ty(table, actual_type.0)
+            ),
+        )
+        .with_secondary(func_name.1, format!("in this call to `{}`", func_name.0)),
+        TypeResolveError::UnprintableArgument {
+            func_name,
+            actual_type,
+        } => Diagnostic::error(
+            "E0424",
+            format!("{} cannot be printed", ty(table, actual_type.0)),
+        )
+        .with_primary(
+            actual_type.1,
+            "expected an integer, a float, a bool or a str",
+        )
+        .with_secondary(func_name.1, format!("in this call to `{}`", func_name.0)),
+        TypeResolveError::InvalidConversion {
+            target_type,
+            actual_type,
+        } => Diagnostic::error(
+            "E0425",
+            format!(
+                "cannot convert {} to {}",
+                ty(table, actual_type.0),
+                ty(table, target_type.0)
+            ),
+        )
+        .with_primary(
+            actual_type.1,
+            "only an alias and the types it is declared with can be converted",
+        )
+        .with_secondary(
+            target_type.1,
+            format!("in this conversion to {}", ty(table, target_type.0)),
+        ),
+        TypeResolveError::NonBoolInWhileCond(cond) => {
+            Diagnostic::error("E0427", "`while` condition must be a `bool`").with_primary(
+                cond.1,
+                format!("expected `bool`, found {}", ty(table, cond.0)),
+            )
+        }
+        TypeResolveError::NonMatchableType(target) => Diagnostic::error(
+            "E0428",
+            format!("cannot match on a value of type {}", ty(table, target.0)),
+        )
+        .with_primary(target.1, "expected an integer or a `bool`"),
+        TypeResolveError::PatternTypeMismatch {
+            expect_type,
+            actual_type,
+        } => Diagnostic::error("E0429", "mismatched types in pattern")
+            .with_primary(
+                actual_type.1,
+                format!(
+                    "expected {}, found {}",
+                    ty(table, expect_type.0),
+                    ty(table, actual_type.0)
+                ),
+            )
+            .with_secondary(
+                expect_type.1,
+                format!("this is {}", ty(table, expect_type.0)),
+            ),
+        TypeResolveError::NonBoolInMatchGuard(guard) => {
+            Diagnostic::error("E0430", "`match` guard must be a `bool`").with_primary(
+                guard.1,
+                format!("expected `bool`, found {}", ty(table, guard.0)),
+            )
+        }
+        TypeResolveError::BreakTypeMismatch { value_ty, first_ty } => {
+            Diagnostic::error("E0426", "mismatched types of `break` values in the loop")
+                .with_primary(
+                    value_ty.1,
+                    format!(
+                        "expected {}, found {}",
+                        ty(table, first_ty.0),
+                        ty(table, value_ty.0)
+                    ),
+                )
+                .with_secondary(
+                    first_ty.1,
+                    format!("the loop evaluates to {} here", ty(table, first_ty.0)),
+                )
+        }
+        TypeResolveError::ArrayElementTypeMismatch {
+            element_index,
+            expect_type,
+            actual_type,
+        } => Diagnostic::error(
+            "E0416",
+            format!("mismatched type for array element {}", element_index),
+        )
+        .with_primary(
+            actual_type.1,
+            format!(
+                "expected {}, found {}",
+                ty(table, expect_type.0),
+                ty(table, actual_type.0)
+            ),
+        )
+        .with_secondary(
+            expect_type.1,
+            format!(
+                "element type {} is expected because of this",
+                ty(table, expect_type.0)
+            ),
+        ),
+        TypeResolveError::UnknownArrayElementType(loc) => {
+            Diagnostic::error("E0438", "cannot infer the element type of an empty array")
+                .with_primary(*loc, "give the type of the array where it is declared")
+        }
+        TypeResolveError::IndexingOnNonArrayType(target) => Diagnostic::error(
+            "E0417",
+            format!("cannot index into a value of type {}", ty(table, target.0)),
+        )
+        .with_primary(target.1, "not an array, a vector or a string"),
+        TypeResolveError::ExpectUnsignedIntOnArrayIndex { arr_ty, index_ty } => {
+            Diagnostic::error("E0418", "index must be an integer")
+                .with_primary(index_ty.1, format!("found {}", ty(table, index_ty.0)))
+                .with_secondary(arr_ty.1, format!("indexing into {}", ty(table, arr_ty.0)))
+        }
+        TypeResolveError::InvalidTypeCast {
+            loc,
+            from_ty,
+            to_ty,
+        } => Diagnostic::error(
+            "E0419",
+            format!(
+                "invalid cast from {} to {}",
+                ty(table, *from_ty),
+                ty(table, *to_ty)
+            ),
+        )
+        .with_primary(*loc, "invalid cast"),
+        TypeResolveError::NonUnitIfWithoutElse(then) => {
+            Diagnostic::error("E0420", "`if` without an `else` must evaluate to `()`")
+                .with_primary(then.1, format!("found {}", ty(table, then.0)))
+                .with_note("add an `else` branch that evaluates to the same type")
+        }
+        TypeResolveError::NonStructTypeInStructInit(target) => Diagnostic::error(
+            "E0421",
+            format!("{} is not a struct type", ty(table, target.0)),
+        )
+        .with_primary(target.1, "cannot be initialized with fields"),
+        TypeResolveError::CyclicAlias { alias, cycle } => Diagnostic::error(
+            "E0439",
+            format!("alias `{}` is declared with itself", alias.0),
+        )
+        .with_primary(alias.1, "cyclic alias")
+        .with_note(format!(
+            "the aliases form a cycle: {}",
+            cycle
+                .iter()
+                .map(|v| format!("`{}`", v))
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        )),
+        TypeResolveError::NonEnumTypeInPath(target) => Diagnostic::error(
+            "E0431",
+            format!("{} is not an enum type", ty(table, target.0)),
+        )
+        .with_primary(target.1, "has no variants"),
+        TypeResolveError::NonIterableType(target) => Diagnostic::error(
+            "E0432",
+            format!(
+                "cannot iterate over a value of type {}",
+                ty(table, target.0)
+            ),
+        )
+        .with_primary(target.1, "not an array or a vector"),
+        TypeResolveError::MemberAccessOnNonStructType(target) => Diagnostic::error(
+            "E0422",
+            format!("cannot access a field of type {}", ty(table, target.0)),
+        )
+        .with_primary(target.1, "not a struct"),
+        TypeResolveError::FieldTypeMismatch {
+            field_name,
+            expect_type,
+            actual_type,
+        } => Diagnostic::error(
+            "E0423",
+            format!("mismatched type for field `{}`", field_name.0),
+        )
+        .with_primary(
+            actual_type.1,
+            format!(
+                "expected {}, found {}",
+                ty(table, *expect_type),
+                ty(table, actual_type.0)
+            ),
+        ),
+    }
+}
+
+fn from_control_flow_error(e: &ControlFlowError) -> Diagnostic {
+    match e {
+        ControlFlowError::NotAllFuncPathReturned(func_name) => Diagnostic::error(
+            "E0501",
+            format!("not all paths of function `{}` return a value", func_name.0),
+        )
+        .with_primary(
+            func_name.1,
+            "this function may reach its end without a value",
+        ),
+        ControlFlowError::BreakOutsideLoop(loc) => {
+            Diagnostic::error("E0502", "`break` outside of a loop")
+                .with_primary(*loc, "cannot break outside of a loop")
+        }
+        ControlFlowError::ContinueOutsideLoop(loc) => {
+            Diagnostic::error("E0503", "`continue` outside of a loop")
+                .with_primary(*loc, "cannot continue outside of a loop")
+        }
+        ControlFlowError::BreakWithValueInWhile(loc) => {
+            Diagnostic::error("E0504", "`break` with a value in a `while` or `for` loop")
+                .with_primary(*loc, "this loop can only evaluate to `()`")
+        }
+        ControlFlowError::NonExhaustiveMatch { loc, missing } => Diagnostic::error(
+            "E0505",
+            format!("non-exhaustive patterns: {} not covered", missing),
+        )
+        .with_primary(*loc, format!("pattern {} not covered", missing))
+        .with_note("add an arm for the missing values, or a `_` arm to match any value"),
+        ControlFlowError::UnreachableMatchArm(loc) => {
+            Diagnostic::error("E0506", "unreachable `match` arm")
+                .with_primary(*loc, "already matched by the arms before")
+        }
+    }
+}
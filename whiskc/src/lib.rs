@@ -1,6 +1,7 @@
 pub mod ast;
 pub mod codegen;
 pub mod compile;
+pub mod diagnostics;
 mod interner;
 pub mod module;
 pub mod symbol;
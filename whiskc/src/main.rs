@@ -1,22 +1,40 @@
-use std::env;
+use std::{
+    env,
+    io::{self, IsTerminal},
+    process,
+};
 
-use whiskc::compile::{self, CompileSwitch};
+use whiskc::{
+    compile::{self, CompileSwitch},
+    diagnostics::ColorMode,
+};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let plain = args.iter().any(|v| v == "--plain");
+    let Some(source_path) = args.iter().find(|v| !v.starts_with("--")) else {
         eprintln!("whiskc: expected path to .wsk sourcefile.");
-        return;
-    }
+        process::exit(1);
+    };
+
+    let color_mode = if plain || env::var_os("NO_COLOR").is_some() || !io::stderr().is_terminal() {
+        ColorMode::Plain
+    } else {
+        ColorMode::Colored
+    };
 
-    compile::compile(
-        args[1].clone().into(),
+    let compiled = compile::compile(
+        source_path.into(),
         CompileSwitch {
             do_parse_ast: true,
             debug_ast: false,
             do_resolve_module: true,
             print_module: true,
             do_codegen: true,
+            color_mode,
         },
-    )
+    );
+    if !compiled {
+        process::exit(1);
+    }
 }
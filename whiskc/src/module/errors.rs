@@ -36,6 +36,17 @@ impl From<TypeResolveError> for ResolveError {
 #[derive(Debug, Clone)]
 pub enum ControlFlowError {
     NotAllFuncPathReturned(Located<String>),
+    BreakOutsideLoop(Span),
+    ContinueOutsideLoop(Span),
+    /// `break` with a value out of a `while` or a `for` loop, which can only evaluate to `()`.
+    BreakWithValueInWhile(Span),
+    /// `match` whose arms do not cover every value, `missing` being a pattern of the values left.
+    NonExhaustiveMatch {
+        loc: Span,
+        missing: String,
+    },
+    /// `match` arm whose values are all matched by the arms before it.
+    UnreachableMatchArm(Span),
 }
 
 #[derive(Debug, Clone)]
@@ -43,10 +54,10 @@ pub enum ValueResolveError {
     IntegerOutOfRange {
         is_signed: bool,
         int_size: u16,
-        val: Located<i64>,
+        val: Located<i128>,
     },
     ArgumentCountMismatch {
-        func_ty: Located<Type>,
+        func_name: Located<String>,
         expect_count: usize,
         actual_count: usize,
     },
@@ -55,6 +66,26 @@ pub enum ValueResolveError {
         expect_count: usize,
         actual_count: usize,
     },
+    MissingStructFields {
+        struct_ty: Located<Type>,
+        fields: Vec<String>,
+    },
+    /// range pattern whose start is after its end, so it matches no value.
+    EmptyRangePattern(Span),
+    /// a variant is constructed or matched with the syntax of another kind of variant, such as
+    /// `Shape::Circle` for `Circle(int)`.
+    VariantKindMismatch {
+        enum_ty: Type,
+        variant: Located<String>,
+    },
+    PatternFieldCountMismatch {
+        loc: Span,
+        expect_count: usize,
+        actual_count: usize,
+    },
+    /// length of an array type or of a repeated array which is past the most elements an array
+    /// can have.
+    ArrayTooLong { len: Located<usize>, max: usize },
 }
 
 #[derive(Debug, Clone)]
@@ -73,6 +104,40 @@ pub enum IdentResolveError {
     UnexpectedAttrib {
         attribute: Span,
     },
+    FieldNameAlreadyUsed {
+        ident: String,
+        first_origin: Span,
+        dup_origin: Span,
+    },
+    TypeUsedAsValue(Located<String>),
+    /// function named anywhere but as the callee of a call, as functions are not values.
+    FunctionUsedAsValue(Located<String>),
+    UnknownField {
+        struct_ty: Type,
+        field_name: Located<String>,
+    },
+    VariantNameAlreadyUsed {
+        ident: String,
+        first_origin: Span,
+        dup_origin: Span,
+    },
+    UnknownVariant {
+        enum_ty: Type,
+        variant: Located<String>,
+    },
+    /// call of a method which the builtin type has not, or on a type without methods.
+    UnknownMethod {
+        ty: Type,
+        method_name: Located<String>,
+    },
+    /// `break` or `continue` with a label of no enclosing loop.
+    UnknownLabel(Located<String>),
+    /// label of a loop which is already used by an enclosing loop.
+    LabelAlreadyUsed {
+        label: String,
+        first_origin: Span,
+        dup_origin: Span,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -83,6 +148,7 @@ pub enum TypeResolveError {
         actual_type: Located<Type>,
     },
     NonBoolInIfCond(Located<Type>),
+    NonUnitIfWithoutElse(Located<Type>),
     BlockBranchTypeMismatch {
         branch: Located<Type>,
         other: Located<Type>,
@@ -100,10 +166,25 @@ pub enum TypeResolveError {
         op: Located<Operator>,
         ty: Located<Type>,
     },
+    /// operand of `~` which is not an integer.
+    NonIntegerInUnaryOp(Operator, Located<Type>),
+    NonIntegerTypeInBinaryOp {
+        op: Located<Operator>,
+        ty: Located<Type>,
+    },
     UnorderedTypeInBinaryOp {
         op: Located<Operator>,
         ty: Located<Type>,
     },
+    /// operand of `==` or `!=` which has no value to compare, such as `()`.
+    UncomparableTypeInBinaryOp {
+        op: Located<Operator>,
+        ty: Located<Type>,
+    },
+    /// operator which the expression grammar accepts, but which is not a binary operator.
+    UnsupportedBinaryOp(Located<Operator>),
+    /// operator which the expression grammar accepts, but which is not a unary operator.
+    UnsupportedUnaryOp(Located<Operator>),
     UnexpectedTypeInBinaryOp {
         op: Located<Operator>,
         expect_type: Type,
@@ -116,24 +197,61 @@ pub enum TypeResolveError {
     },
     CallOnNonFunctionType(Located<Type>),
     ArgumentTypeMismatch {
-        func_ty: Located<Type>,
+        func_name: Located<String>,
         argument_index: usize,
         expect_type: Type,
         actual_type: Located<Type>,
     },
+    UnprintableArgument {
+        func_name: Located<String>,
+        actual_type: Located<Type>,
+    },
+    InvalidConversion {
+        target_type: Located<Type>,
+        actual_type: Located<Type>,
+    },
+    NonBoolInWhileCond(Located<Type>),
+    NonMatchableType(Located<Type>),
+    PatternTypeMismatch {
+        expect_type: Located<Type>,
+        actual_type: Located<Type>,
+    },
+    NonBoolInMatchGuard(Located<Type>),
+    BreakTypeMismatch {
+        value_ty: Located<Type>,
+        first_ty: Located<Type>,
+    },
     ArrayElementTypeMismatch {
         element_index: usize,
         expect_type: Located<Type>,
         actual_type: Located<Type>,
     },
+    /// empty array literal where no array or vector of a known type is expected.
+    UnknownArrayElementType(Span),
     IndexingOnNonArrayType(Located<Type>),
     ExpectUnsignedIntOnArrayIndex {
         arr_ty: Located<Type>,
         index_ty: Located<Type>,
     },
+    NonStructTypeInStructInit(Located<Type>),
+    /// alias which is declared with itself, directly or through the other aliases of the cycle,
+    /// which are listed from the alias back to itself.
+    CyclicAlias {
+        alias: Located<String>,
+        cycle: Vec<String>,
+    },
+    NonEnumTypeInPath(Located<Type>),
+    MemberAccessOnNonStructType(Located<Type>),
+    FieldTypeMismatch {
+        field_name: Located<String>,
+        expect_type: Type,
+        actual_type: Located<Type>,
+    },
     InvalidTypeCast {
         loc: Span,
         from_ty: Type,
         to_ty: Type,
     },
+    /// `for` loop over a value which is neither an array nor a vector.
+    NonIterableType(Located<Type>),
 }
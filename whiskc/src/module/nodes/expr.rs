@@ -1,9 +1,11 @@
+use core::fmt;
+
 use crate::{
     ast::parsing::token::Operator,
-    symbol::{BlockId, FuncId, TypeId, VarId},
+    symbol::{ty::CastKind, BlockId, FuncId, TypeId, VarId},
 };
 
-use super::stmt::Stmt;
+use super::{pat::Pattern, stmt::Stmt};
 
 #[derive(Debug, Clone)]
 pub struct Expr {
@@ -14,8 +16,11 @@ pub struct Expr {
 #[derive(Debug, Clone)]
 pub enum ExprKind {
     Unit,
-    Integer(i64),
+    /// integer of the type of the expression, which is within the range of the type.
+    Integer(i128),
+    Float(f64),
     Bool(bool),
+    Str(String),
     VarIdent(VarIdentExpr),
     FuncIdent(FuncIdentExpr),
     Unary(UnaryExpr),
@@ -25,8 +30,20 @@ pub enum ExprKind {
     Return(ReturnExpr),
     If(IfExpr),
     Loop(LoopExpr),
+    While(WhileExpr),
+    For(ForExpr),
+    Break(BreakExpr),
+    Continue(ContinueExpr),
     StructInit(StructInitExpr),
+    EnumInit(EnumInitExpr),
     MemberAccess(MemberAccessExpr),
+    Convert(ConvertExpr),
+    Match(MatchExpr),
+    Array(ArrayExpr),
+    ArrayRepeat(ArrayRepeatExpr),
+    Index(IndexExpr),
+    MethodCall(MethodCallExpr),
+    Cast(CastExpr),
 }
 
 #[derive(Debug, Clone)]
@@ -74,20 +91,66 @@ pub struct ReturnExpr {
 pub struct IfExpr {
     pub cond: Box<Expr>,
     pub then: BlockExpr,
-    pub else_: Option<BlockExpr>,
+    /// either a block, or another `if` of an `else if` chain.
+    pub else_: Option<Box<Expr>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct LoopExpr {
+    pub label: Option<String>,
+    pub body: BlockExpr,
+}
+
+#[derive(Debug, Clone)]
+pub struct WhileExpr {
+    pub label: Option<String>,
+    pub cond: Box<Expr>,
+    pub body: BlockExpr,
+}
+
+/// Loop over the elements of an array or a vector. The element of each iteration is bound to the
+/// variable, which is declared in the block of the loop. The length of a vector is checked before
+/// each iteration, so elements pushed by the body are iterated as well.
+#[derive(Debug, Clone)]
+pub struct ForExpr {
+    pub label: Option<String>,
+    pub block_id: BlockId,
+    pub var: VarId,
+    pub iter: Box<Expr>,
     pub body: BlockExpr,
 }
 
+/// Exits the loop with the label, or the innermost loop if `label` is `None`.
+/// The loop evaluates to the value of the `break`.
+#[derive(Debug, Clone)]
+pub struct BreakExpr {
+    pub label: Option<String>,
+    pub expr: Option<Box<Expr>>,
+}
+
+/// Starts the next iteration of the loop with the label, or the innermost loop if `label` is
+/// `None`.
+#[derive(Debug, Clone)]
+pub struct ContinueExpr {
+    pub label: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct StructInitExpr {
     pub struct_ty: TypeId,
     pub fields: Vec<(String, Expr)>,
 }
 
+/// Value of a variant of an enum, such as `Shape::Circle(1)`.
+#[derive(Debug, Clone)]
+pub struct EnumInitExpr {
+    pub enum_ty: TypeId,
+    pub variant: usize,
+    /// index of each field in the declaration of the variant, along with its value, in the order
+    /// they are evaluated.
+    pub fields: Vec<(usize, Expr)>,
+}
+
 #[derive(Debug, Clone)]
 pub struct MemberAccessExpr {
     pub expr: Box<Expr>,
@@ -95,16 +158,126 @@ pub struct MemberAccessExpr {
     pub field_name: String,
 }
 
-impl From<i64> for ExprKind {
-    fn from(value: i64) -> Self {
+/// Conversion between an alias and a type in its chain of aliases, such as `Int(1)` or
+/// `int(meters)`. The converted value is left as it is, and only its type changes to the type
+/// of the expression.
+#[derive(Debug, Clone)]
+pub struct ConvertExpr {
+    pub expr: Box<Expr>,
+}
+
+/// Cast with `as`, whose type is the type cast to.
+#[derive(Debug, Clone)]
+pub struct CastExpr {
+    pub expr: Box<Expr>,
+    pub kind: CastKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArrayExpr {
+    pub elements: Vec<Expr>,
+}
+
+/// Array of `len` copies of the value, which is evaluated once.
+#[derive(Debug, Clone)]
+pub struct ArrayRepeatExpr {
+    pub expr: Box<Expr>,
+    pub len: usize,
+}
+
+/// Element of an array, whose index is checked against the length of the array at runtime.
+#[derive(Debug, Clone)]
+pub struct IndexExpr {
+    pub expr: Box<Expr>,
+    pub index: Box<Expr>,
+}
+
+/// Call of a method of a builtin type, such as `v.push(1)`.
+#[derive(Debug, Clone)]
+pub struct MethodCallExpr {
+    pub receiver: Box<Expr>,
+    pub method: BuiltinMethod,
+    pub args: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinMethod {
+    /// `len()` of an array, which is known from its type.
+    ArrayLen,
+    /// `to_vec()` of an array, returning a new vector with its elements.
+    ArrayToVec,
+    VecPush,
+    VecPop,
+    VecLen,
+    /// `len()` of a string, which is its length in bytes.
+    StrLen,
+    /// `wrapping_add(v)` of an integer, and likewise for `sub` and `mul`, which wrap around at
+    /// the bounds of the type instead of failing at runtime.
+    WrappingAdd,
+    WrappingSub,
+    WrappingMul,
+    /// `saturating_add(v)` of an integer, and likewise for `sub` and `mul`, which saturate at the
+    /// bounds of the type instead of failing at runtime.
+    SaturatingAdd,
+    SaturatingSub,
+    SaturatingMul,
+}
+impl fmt::Display for BuiltinMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::ArrayLen | Self::VecLen | Self::StrLen => "len",
+                Self::ArrayToVec => "to_vec",
+                Self::VecPush => "push",
+                Self::VecPop => "pop",
+                Self::WrappingAdd => "wrapping_add",
+                Self::WrappingSub => "wrapping_sub",
+                Self::WrappingMul => "wrapping_mul",
+                Self::SaturatingAdd => "saturating_add",
+                Self::SaturatingSub => "saturating_sub",
+                Self::SaturatingMul => "saturating_mul",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchExpr {
+    pub expr: Box<Expr>,
+    pub arms: Vec<MatchArm>,
+}
+
+/// Arm of a `match`, whose bindings are declared in its own block.
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub block_id: BlockId,
+    pub pat: Pattern,
+    pub guard: Option<Expr>,
+    pub expr: Expr,
+}
+
+impl From<i128> for ExprKind {
+    fn from(value: i128) -> Self {
         Self::Integer(value)
     }
 }
+impl From<f64> for ExprKind {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
 impl From<bool> for ExprKind {
     fn from(value: bool) -> Self {
         Self::Bool(value)
     }
 }
+impl From<String> for ExprKind {
+    fn from(value: String) -> Self {
+        Self::Str(value)
+    }
+}
 impl From<VarIdentExpr> for ExprKind {
     fn from(value: VarIdentExpr) -> Self {
         Self::VarIdent(value)
@@ -150,13 +323,73 @@ impl From<LoopExpr> for ExprKind {
         Self::Loop(value)
     }
 }
+impl From<WhileExpr> for ExprKind {
+    fn from(value: WhileExpr) -> Self {
+        Self::While(value)
+    }
+}
+impl From<ForExpr> for ExprKind {
+    fn from(value: ForExpr) -> Self {
+        Self::For(value)
+    }
+}
+impl From<BreakExpr> for ExprKind {
+    fn from(value: BreakExpr) -> Self {
+        Self::Break(value)
+    }
+}
+impl From<ContinueExpr> for ExprKind {
+    fn from(value: ContinueExpr) -> Self {
+        Self::Continue(value)
+    }
+}
 impl From<StructInitExpr> for ExprKind {
     fn from(value: StructInitExpr) -> Self {
         Self::StructInit(value)
     }
 }
+impl From<EnumInitExpr> for ExprKind {
+    fn from(value: EnumInitExpr) -> Self {
+        Self::EnumInit(value)
+    }
+}
 impl From<MemberAccessExpr> for ExprKind {
     fn from(value: MemberAccessExpr) -> Self {
         Self::MemberAccess(value)
     }
 }
+impl From<ConvertExpr> for ExprKind {
+    fn from(value: ConvertExpr) -> Self {
+        Self::Convert(value)
+    }
+}
+impl From<MatchExpr> for ExprKind {
+    fn from(value: MatchExpr) -> Self {
+        Self::Match(value)
+    }
+}
+impl From<ArrayExpr> for ExprKind {
+    fn from(value: ArrayExpr) -> Self {
+        Self::Array(value)
+    }
+}
+impl From<ArrayRepeatExpr> for ExprKind {
+    fn from(value: ArrayRepeatExpr) -> Self {
+        Self::ArrayRepeat(value)
+    }
+}
+impl From<IndexExpr> for ExprKind {
+    fn from(value: IndexExpr) -> Self {
+        Self::Index(value)
+    }
+}
+impl From<MethodCallExpr> for ExprKind {
+    fn from(value: MethodCallExpr) -> Self {
+        Self::MethodCall(value)
+    }
+}
+impl From<CastExpr> for ExprKind {
+    fn from(value: CastExpr) -> Self {
+        Self::Cast(value)
+    }
+}
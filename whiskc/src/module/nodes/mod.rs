@@ -2,5 +2,6 @@ pub mod expr;
 pub mod func;
 pub mod item;
 pub mod module;
+pub mod pat;
 pub mod stmt;
 pub mod ty;
@@ -0,0 +1,37 @@
+use crate::symbol::{TypeId, VarId};
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Wildcard,
+    /// matches any value, storing it in the variable.
+    Binding(VarId),
+    Integer(i128),
+    Bool(bool),
+    /// matches integers from `start` to `end`, both inclusive.
+    Range {
+        start: i128,
+        end: i128,
+    },
+    /// matches a variant of the enum whose fields match the patterns, each of which is paired
+    /// with the index of its field in the declaration of the variant. Fields which are left out
+    /// match any value.
+    Variant {
+        enum_ty: TypeId,
+        variant: usize,
+        fields: Vec<(usize, Pattern)>,
+    },
+}
+impl Pattern {
+    /// Returns whether the pattern matches every value of its type.
+    pub fn is_irrefutable(&self) -> bool {
+        matches!(self, Pattern::Wildcard | Pattern::Binding(_))
+    }
+
+    pub fn has_bindings(&self) -> bool {
+        match self {
+            Pattern::Binding(_) => true,
+            Pattern::Variant { fields, .. } => fields.iter().any(|(_, v)| v.has_bindings()),
+            _ => false,
+        }
+    }
+}
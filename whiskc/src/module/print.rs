@@ -1,8 +1,11 @@
 use std::fmt::{Display, Write};
 
-use crate::symbol::{ty::TypeKind, SymbolTable};
+use crate::symbol::{
+    ty::{TypeKind, Variant, VariantKind},
+    SymbolTable, TypeId,
+};
 
-use super::{visit::Visit, Module};
+use super::{nodes::pat::Pattern, visit::Visit, Module};
 
 impl Module {
     pub fn pretty_print<W: Write>(&self, w: &mut W) {
@@ -176,6 +179,34 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
         self.end_item();
     }
 
+    fn visit_break_expr(&mut self, node: &super::nodes::expr::BreakExpr) {
+        self.start_item("break");
+        if let Some(label) = &node.label {
+            self.add_attrib("label", label);
+        }
+        if let Some(expr) = &node.expr {
+            self.set_prefix("value: ");
+            self.visit_expr(expr);
+        }
+        self.end_item();
+    }
+
+    fn visit_for_expr(&mut self, node: &super::nodes::expr::ForExpr) {
+        self.start_item("for");
+
+        if let Some(label) = &node.label {
+            self.add_attrib("label", label);
+        }
+        self.add_attrib("var", &node.var.sym(self.table).name);
+
+        self.set_prefix("iter: ");
+        self.visit_expr(&node.iter);
+
+        self.set_prefix("body: ");
+        self.visit_block_expr(&node.body);
+
+        self.end_item();
+    }
     fn visit_call_expr(&mut self, node: &super::nodes::expr::CallExpr) {
         self.start_item("call");
 
@@ -191,6 +222,27 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
         self.end_item();
     }
 
+    fn visit_continue_expr(&mut self, node: &super::nodes::expr::ContinueExpr) {
+        self.start_item("continue");
+        if let Some(label) = &node.label {
+            self.add_attrib("label", label);
+        }
+        self.end_item();
+    }
+
+    fn visit_convert_expr(&mut self, node: &super::nodes::expr::ConvertExpr) {
+        self.start_item("convert");
+        self.visit_expr(&node.expr);
+        self.end_item();
+    }
+
+    fn visit_cast_expr(&mut self, node: &super::nodes::expr::CastExpr) {
+        self.start_item("cast");
+        self.add_attrib("kind", node.kind);
+        self.visit_expr(&node.expr);
+        self.end_item();
+    }
+
     fn visit_expr(&mut self, node: &super::nodes::expr::Expr) {
         super::visit::visit_expr(self, node);
         self.add_attrib_to_last_pop("type", &node.ty.sym(self.table).name);
@@ -220,6 +272,11 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
         self.end_item();
     }
 
+    fn visit_float_expr(&mut self, value: f64) {
+        self.start_item(format!("float: {:?}", value).as_str());
+        self.end_item();
+    }
+
     fn visit_func(&mut self, node: &super::nodes::func::Function) {
         self.start_item("func_decl");
 
@@ -274,17 +331,22 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
 
         if let Some(else_) = &node.else_ {
             self.set_prefix("else: ");
-            self.visit_block_expr(else_);
+            self.visit_expr(else_);
         }
 
         self.end_item();
     }
 
-    fn visit_int_expr(&mut self, value: i64) {
+    fn visit_int_expr(&mut self, value: i128) {
         self.start_item(format!("integer: {}", value).as_str());
         self.end_item();
     }
 
+    fn visit_str_expr(&mut self, value: &str) {
+        self.start_item(format!("string: \"{}\"", value.escape_debug()).as_str());
+        self.end_item();
+    }
+
     fn visit_item(&mut self, node: &super::nodes::item::Item) {
         super::visit::visit_item(self, node);
     }
@@ -306,6 +368,34 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
     fn visit_loop_expr(&mut self, node: &super::nodes::expr::LoopExpr) {
         self.set_rename("loop");
         super::visit::visit_loop_expr(self, node);
+        if let Some(label) = &node.label {
+            self.add_attrib_to_last_pop("label", label);
+        }
+    }
+
+    fn visit_match_expr(&mut self, node: &super::nodes::expr::MatchExpr) {
+        self.start_item("match");
+
+        self.set_prefix("value: ");
+        self.visit_expr(&node.expr);
+
+        for arm in &node.arms {
+            self.start_item("arm");
+
+            self.add_attrib("pattern", fmt_pattern(self.table, &arm.pat));
+
+            if let Some(guard) = &arm.guard {
+                self.set_prefix("guard: ");
+                self.visit_expr(guard);
+            }
+
+            self.set_prefix("body: ");
+            self.visit_expr(&arm.expr);
+
+            self.end_item();
+        }
+
+        self.end_item();
     }
 
     fn visit_member_access_expr(&mut self, node: &super::nodes::expr::MemberAccessExpr) {
@@ -316,6 +406,47 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
         self.end_item();
     }
 
+    fn visit_array_expr(&mut self, node: &super::nodes::expr::ArrayExpr) {
+        self.start_item("array");
+        for (i, element) in node.elements.iter().enumerate() {
+            self.set_prefix(format!("{}: ", i));
+            self.visit_expr(element);
+        }
+        self.end_item();
+    }
+
+    fn visit_array_repeat_expr(&mut self, node: &super::nodes::expr::ArrayRepeatExpr) {
+        self.start_item("array_repeat");
+        self.add_attrib("len", node.len);
+        self.set_prefix("value: ");
+        self.visit_expr(&node.expr);
+        self.end_item();
+    }
+
+    fn visit_index_expr(&mut self, node: &super::nodes::expr::IndexExpr) {
+        self.start_item("index");
+        self.set_prefix("array: ");
+        self.visit_expr(&node.expr);
+        self.set_prefix("index: ");
+        self.visit_expr(&node.index);
+        self.end_item();
+    }
+
+    fn visit_method_call_expr(&mut self, node: &super::nodes::expr::MethodCallExpr) {
+        self.start_item("method_call");
+        self.add_attrib("method", node.method);
+        self.set_prefix("receiver: ");
+        self.visit_expr(&node.receiver);
+
+        self.start_item("args");
+        for arg in &node.args {
+            self.visit_expr(arg);
+        }
+        self.end_item();
+
+        self.end_item();
+    }
+
     fn visit_return_expr(&mut self, node: &super::nodes::expr::ReturnExpr) {
         self.set_prefix("return: ");
         super::visit::visit_return_expr(self, node);
@@ -343,6 +474,26 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
         self.end_item();
     }
 
+    fn visit_enum_init_expr(&mut self, node: &super::nodes::expr::EnumInitExpr) {
+        self.start_item("enum_init");
+
+        let variant = get_variant(self.table, node.enum_ty, node.variant);
+        self.add_attrib("enum_name", &node.enum_ty.sym(self.table).name);
+        self.add_attrib("variant", &variant.name);
+
+        if !node.fields.is_empty() {
+            self.start_item("fields");
+            for (i, expr) in &node.fields {
+                self.start_item(field_name(variant, *i));
+                self.visit_expr(expr);
+                self.end_item();
+            }
+            self.end_item();
+        }
+
+        self.end_item();
+    }
+
     fn visit_type_decl(&mut self, node: &super::nodes::ty::TypeDecl) {
         self.start_item("type_decl");
 
@@ -361,6 +512,40 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
                     }
                     self.end_item();
                 }
+                TypeKind::Enum(v) => {
+                    self.start_item("enum");
+                    for variant in &v.variants {
+                        match &variant.kind {
+                            VariantKind::Unit => self.add_attrib(&variant.name, "()"),
+                            VariantKind::Tuple(types) => {
+                                let types: Vec<&str> = types
+                                    .iter()
+                                    .map(|ty| ty.sym(self.table).name.as_str())
+                                    .collect();
+                                self.add_attrib(&variant.name, format!("({})", types.join(", ")))
+                            }
+                            VariantKind::Struct(v) => {
+                                self.start_item(&variant.name);
+                                for (name, ty) in &v.fields {
+                                    self.add_attrib(name, &ty.sym(self.table).name)
+                                }
+                                self.end_item();
+                            }
+                        }
+                    }
+                    self.end_item();
+                }
+                TypeKind::Array(v) => {
+                    self.start_item("array");
+                    self.add_attrib("elem", &v.elem_ty.sym(self.table).name);
+                    self.add_attrib("len", v.len);
+                    self.end_item();
+                }
+                TypeKind::Vec(v) => {
+                    self.start_item("vec");
+                    self.add_attrib("elem", &v.elem_ty.sym(self.table).name);
+                    self.end_item();
+                }
                 TypeKind::Ident(v) => self.add_attrib("underlying", &v.sym(self.table).name),
             };
             self.end_item();
@@ -385,4 +570,67 @@ impl<W: Write> Visit for PrintVisitor<'_, W> {
         self.start_item("unit");
         self.end_item();
     }
+
+    fn visit_while_expr(&mut self, node: &super::nodes::expr::WhileExpr) {
+        self.start_item("while");
+
+        if let Some(label) = &node.label {
+            self.add_attrib("label", label);
+        }
+
+        self.set_prefix("cond: ");
+        self.visit_expr(&node.cond);
+
+        self.set_prefix("body: ");
+        self.visit_block_expr(&node.body);
+
+        self.end_item();
+    }
+}
+
+fn get_variant(table: &SymbolTable, enum_ty: TypeId, tag: usize) -> &Variant {
+    let Some(TypeKind::Enum(v)) = &enum_ty.sym(table).kind else {
+        panic!("enum type expected");
+    };
+    &v.variants[tag]
+}
+
+/// Returns the name of the field of the variant, which is its index for a tuple variant.
+fn field_name(variant: &Variant, i: usize) -> String {
+    match &variant.kind {
+        VariantKind::Struct(v) => v.fields[i].0.clone(),
+        _ => i.to_string(),
+    }
+}
+
+fn fmt_pattern(table: &SymbolTable, pat: &Pattern) -> String {
+    match pat {
+        Pattern::Wildcard => "_".to_owned(),
+        Pattern::Binding(id) => id.sym(table).name.clone(),
+        Pattern::Integer(v) => v.to_string(),
+        Pattern::Bool(v) => v.to_string(),
+        Pattern::Range { start, end } => format!("{}..={}", start, end),
+        Pattern::Variant {
+            enum_ty,
+            variant,
+            fields,
+        } => {
+            let variant = get_variant(table, *enum_ty, *variant);
+            let name = format!("{}::{}", enum_ty.sym(table).name, variant.name);
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(i, v)| match &variant.kind {
+                    VariantKind::Struct(_) => {
+                        format!("{}: {}", field_name(variant, *i), fmt_pattern(table, v))
+                    }
+                    _ => fmt_pattern(table, v),
+                })
+                .collect();
+            match &variant.kind {
+                VariantKind::Unit => name,
+                VariantKind::Tuple(_) => format!("{}({})", name, fields.join(", ")),
+                VariantKind::Struct(_) => format!("{} {{ {} }}", name, fields.join(", ")),
+            }
+        }
+    }
 }
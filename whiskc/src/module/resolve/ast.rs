@@ -32,6 +32,16 @@ impl Resolve<(), Vec<Item>> for ast::AST {
             }
         }
 
+        // resolve the signatures of functions before any body, so that a function can be called
+        // before it is declared.
+        for &index in &ok_indexes {
+            match &self.items[index] {
+                ast::nodes::item::Item::Function(v) => v.sig.resolve(ctx, ()),
+                ast::nodes::item::Item::ExternFunction(v) => v.sig.resolve(ctx, ()),
+                _ => continue,
+            }
+        }
+
         let mut items = Vec::new();
 
         // no need to separately resolve items as they had been ordered in the record phase.
@@ -1,128 +1,262 @@
 use crate::{
-    ast::{location::Located, nodes as ast, parsing::token::Operator},
+    ast::{
+        location::{Locatable, Located, Span},
+        nodes as ast,
+        parsing::token::{IntLiteral, Operator},
+    },
     module::{
+        errors::{ControlFlowError, IdentResolveError, TypeResolveError, ValueResolveError},
         nodes::expr::{
-            BinaryExpr, BlockExpr, CallExpr, Expr, ExprKind, FuncIdentExpr, IfExpr, LoopExpr,
-            MemberAccessExpr, ReturnExpr, StructInitExpr, UnaryExpr, VarIdentExpr,
+            ArrayExpr, ArrayRepeatExpr, BinaryExpr, BlockExpr, BreakExpr, BuiltinMethod, CallExpr,
+            CastExpr, ContinueExpr, ConvertExpr, EnumInitExpr, Expr, ExprKind, ForExpr,
+            FuncIdentExpr, IfExpr, IndexExpr, LoopExpr, MatchArm, MatchExpr, MemberAccessExpr,
+            MethodCallExpr, ReturnExpr, StructInitExpr, UnaryExpr, VarIdentExpr, WhileExpr,
         },
         resolve::Flow,
     },
-    symbol::ty::TypeKind,
+    symbol::{
+        ty::{CastKind, StructType, TypeKind, VariantKind},
+        FuncId, TypeId,
+    },
 };
 
-use super::{FlowObj, Resolve, ResolveContext};
+use super::{
+    pat::Coverage,
+    ty::{check_array_len, resolve_prim_type, resolve_variant},
+    FlowObj, Resolve, ResolveContext,
+};
 
 impl Resolve<(), FlowObj<Expr>> for ast::expr::Expr {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        self.resolve(ctx, None)
+    }
+}
+
+/// Resolves the expression where a value of `expect_ty` is expected, if any. An integer literal
+/// without a suffix is of the expected type if it is an integer type, rather than `int`. The
+/// expected type is passed on to the operands of arithmetic, and to the values of blocks, `if`s
+/// and arrays.
+impl Resolve<Option<TypeId>, FlowObj<Expr>> for ast::expr::Expr {
+    fn resolve(&self, ctx: &mut ResolveContext, expect_ty: Option<TypeId>) -> FlowObj<Expr> {
         match self {
             ast::expr::Expr::Unit(_) => FlowObj::cont(Expr {
                 kind: ExprKind::Unit,
                 ty: ctx.table.common_type().unit,
             }),
-            ast::expr::Expr::Integer(v) => FlowObj::cont(Expr {
-                kind: ExprKind::Integer(v.0),
-                ty: ctx.table.common_type().int,
+            ast::expr::Expr::Integer(v) => resolve_int_literal(ctx, v, None, expect_ty),
+            ast::expr::Expr::Float(v) => FlowObj::cont(Expr {
+                kind: ExprKind::Float(v.0),
+                ty: ctx.table.common_type().float,
             }),
             ast::expr::Expr::Bool(v) => FlowObj::cont(Expr {
                 kind: ExprKind::Bool(v.0),
                 ty: ctx.table.common_type().bool,
             }),
+            ast::expr::Expr::Str(v) => FlowObj::cont(Expr {
+                kind: ExprKind::Str(v.0.clone()),
+                ty: ctx.table.common_type().str,
+            }),
             ast::expr::Expr::Ident(v) => v.resolve(ctx, ()),
-            ast::expr::Expr::Unary(v) => v.resolve(ctx, ()),
-            ast::expr::Expr::Binary(v) => v.resolve(ctx, ()),
-            ast::expr::Expr::Grouped(v) => v.expr.resolve(ctx, ()),
+            ast::expr::Expr::Unary(v) => v.resolve(ctx, expect_ty),
+            ast::expr::Expr::Binary(v) => v.resolve(ctx, expect_ty),
+            ast::expr::Expr::Grouped(v) => v.expr.resolve(ctx, expect_ty),
             ast::expr::Expr::Call(v) => v.resolve(ctx, ()),
-            ast::expr::Expr::Block(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::Block(v) => v.resolve(ctx, expect_ty),
             ast::expr::Expr::Return(v) => v.resolve(ctx, ()),
-            ast::expr::Expr::If(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::If(v) => v.resolve(ctx, expect_ty),
             ast::expr::Expr::Loop(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::While(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::For(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::Cast(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::Break(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::Continue(v) => v.resolve(ctx, ()),
             ast::expr::Expr::StructInit(v) => v.resolve(ctx, ()),
             ast::expr::Expr::MemberAccess(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::Match(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::Path(v) => v.resolve(ctx, ()),
+            ast::expr::Expr::Array(v) => v.resolve(ctx, expect_ty),
+            ast::expr::Expr::ArrayRepeat(v) => v.resolve(ctx, expect_ty),
+            ast::expr::Expr::Index(v) => v.resolve(ctx, ()),
         }
     }
 }
 
-impl Resolve<(), FlowObj<Expr>> for ast::expr::BinaryExpr {
-    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
-        let FlowObj {
-            value: left,
-            flow: left_flow,
-        } = self.left.resolve(ctx, ());
+/// Resolves an integer literal, which is negated if `neg_loc` is the location of its minus sign.
+/// The literal is of the type of its suffix, or else of the expected type if it is an integer
+/// type, or else of `int`.
+fn resolve_int_literal(
+    ctx: &mut ResolveContext,
+    lit: &Located<IntLiteral>,
+    neg_loc: Option<Span>,
+    expect_ty: Option<TypeId>,
+) -> FlowObj<Expr> {
+    let ty = match (lit.0.suffix, expect_ty) {
+        (Some(suffix), _) => resolve_prim_type(ctx, suffix.into()),
+        (None, Some(expect_ty)) if ctx.table.get_int(expect_ty).is_some() => {
+            ctx.table.underlying_type(expect_ty)
+        }
+        _ => ctx.table.common_type().int,
+    };
+    let value = match neg_loc {
+        Some(neg_loc) => Located(-(lit.0.value as i128), Span::combine(neg_loc, lit.1)),
+        None => Located(lit.0.value as i128, lit.1),
+    };
 
-        let FlowObj {
-            value: right,
-            flow: right_flow,
-        } = self.right.resolve(ctx, ());
+    let int = ctx.table.get_int(ty).expect("integer type");
+    let (min, max) = int.int_range().expect("integer type");
+    if value.0 < min || max < value.0 {
+        let (is_signed, int_size) = int.int_layout().expect("integer type");
+        ctx._error(ValueResolveError::IntegerOutOfRange {
+            is_signed,
+            int_size,
+            val: value,
+        });
+    }
+    FlowObj::cont(Expr {
+        kind: ExprKind::Integer(value.0),
+        ty,
+    })
+}
+
+/// Returns whether the expression is an integer literal without a suffix, or arithmetic on such
+/// literals, whose type is taken from where it is used.
+fn is_unsuffixed_int(expr: &ast::expr::Expr) -> bool {
+    match expr {
+        ast::expr::Expr::Integer(v) => v.0.suffix.is_none(),
+        ast::expr::Expr::Grouped(v) => is_unsuffixed_int(&v.expr),
+        ast::expr::Expr::Unary(v) => {
+            matches!(v.op.0, Operator::Sub | Operator::BitNot) && is_unsuffixed_int(&v.expr)
+        }
+        ast::expr::Expr::Binary(v) if is_shift_op(v.op.0) => is_unsuffixed_int(&v.left),
+        ast::expr::Expr::Binary(v) => {
+            is_arithmetic_op(v.op.0) && is_unsuffixed_int(&v.left) && is_unsuffixed_int(&v.right)
+        }
+        _ => false,
+    }
+}
+
+fn is_arithmetic_op(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::Add
+            | Operator::Sub
+            | Operator::Mul
+            | Operator::Div
+            | Operator::Mod
+            | Operator::BitAnd
+            | Operator::BitOr
+            | Operator::BitXor
+    )
+}
+
+fn is_shift_op(op: Operator) -> bool {
+    matches!(op, Operator::Shl | Operator::Shr)
+}
+
+impl Resolve<Option<TypeId>, FlowObj<Expr>> for ast::expr::BinaryExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, expect_ty: Option<TypeId>) -> FlowObj<Expr> {
+        // an unsuffixed integer operand is of the type of the other operand, so the other one is
+        // resolved first if only the left one is unsuffixed. Only the operands of arithmetic and
+        // bitwise operators, and the shifted value of a shift, are of the type expected of the
+        // result. The amount of a shift is of its own type.
+        let operand_ty =
+            expect_ty.filter(|_| is_arithmetic_op(self.op.0) || is_shift_op(self.op.0));
+        let (
+            FlowObj {
+                value: left,
+                flow: left_flow,
+            },
+            FlowObj {
+                value: right,
+                flow: right_flow,
+            },
+        ) = if is_shift_op(self.op.0) {
+            (
+                self.left.resolve(ctx, operand_ty),
+                self.right.resolve(ctx, None),
+            )
+        } else if is_unsuffixed_int(&self.left) && !is_unsuffixed_int(&self.right) {
+            let right = self.right.resolve(ctx, operand_ty);
+            let right_ty = right.value.as_ref().map(|v| v.ty);
+            (self.left.resolve(ctx, right_ty), right)
+        } else {
+            let left = self.left.resolve(ctx, operand_ty);
+            let left_ty = left.value.as_ref().map(|v| v.ty);
+            (left, self.right.resolve(ctx, left_ty))
+        };
 
         let merged_flow = left_flow & right_flow;
         let (Some(left), Some(right)) = (left, right) else {
             return FlowObj::none(merged_flow);
         };
 
-        let check_ty_equal = || {
-            if !ctx.table.is_type_symmetric(left.ty, right.ty) {
-                todo!("report error")
-            }
-        };
-        let check_ty_num = |expr: &Expr| {
-            if !ctx
-                .table
-                .is_type_coercible(expr.ty, ctx.table.common_type().int)
-            {
-                todo!("report error")
+        let left_loc = self.left.get_location();
+        let right_loc = self.right.get_location();
+        let op = self.op;
+        let common_ty = *ctx.table.common_type();
+
+        let op_ty = match op.0 {
+            Operator::Assign => {
+                if !is_assignable_place(ctx, &left) {
+                    ctx._error(TypeResolveError::NonAssignableType(Located(
+                        left.ty, left_loc,
+                    )));
+                } else {
+                    check_assignment(
+                        ctx,
+                        Located(left.ty, left_loc),
+                        Located(right.ty, right_loc),
+                    );
+                }
+                common_ty.unit
             }
-        };
-        let check_ty_bool = |expr: &Expr| {
-            if !ctx
-                .table
-                .is_type_coercible(expr.ty, ctx.table.common_type().bool)
-            {
-                todo!("report error")
+            // `+` concatenates strings, including aliases of `str`; both operands must be of the
+            // same type, and the result is of that type.
+            Operator::Add if ctx.table.underlying_type(left.ty) == common_ty.str => {
+                let _ = check_operand_type(ctx, op, &right, right_loc, common_ty.str)
+                    && check_same_operand_types(ctx, op, &left, &right);
+                left.ty
             }
-        };
-        let check_ty_int = |expr: &Expr| {
-            if !ctx
-                .table
-                .is_type_coercible(expr.ty, ctx.table.common_type().int)
-            {
-                todo!("report error")
+            Operator::Add | Operator::Sub | Operator::Mul | Operator::Div | Operator::Mod => {
+                let _ = check_numeric_operand(ctx, op, &left, left_loc)
+                    && check_numeric_operand(ctx, op, &right, right_loc)
+                    && check_same_operand_types(ctx, op, &left, &right);
+                left.ty
             }
-        };
-
-        let op_ty = match self.op.0 {
-            Operator::Assign => {
-                // TODO: check if expr is assignable
-                check_ty_equal();
-                ctx.table.common_type().unit
-            }
-            Operator::Add | Operator::Sub | Operator::Mul | Operator::Div => {
-                check_ty_num(&left);
-                check_ty_num(&right);
-                check_ty_equal();
+            Operator::BitAnd | Operator::BitOr | Operator::BitXor => {
+                let _ = check_int_operand(ctx, op, &left, left_loc)
+                    && check_int_operand(ctx, op, &right, right_loc)
+                    && check_same_operand_types(ctx, op, &left, &right);
                 left.ty
             }
-            Operator::Mod => {
-                check_ty_num(&left);
-                check_ty_int(&right);
-                ctx.table.common_type().int
+            // the shifted value and the amount may be of different integer types.
+            Operator::Shl | Operator::Shr => {
+                let _ = check_int_operand(ctx, op, &left, left_loc)
+                    && check_int_operand(ctx, op, &right, right_loc);
+                left.ty
             }
             Operator::And | Operator::Or => {
-                check_ty_bool(&left);
-                check_ty_bool(&right);
-                ctx.table.common_type().bool
+                let _ = check_operand_type(ctx, op, &left, left_loc, common_ty.bool)
+                    && check_operand_type(ctx, op, &right, right_loc, common_ty.bool)
+                    && check_same_operand_types(ctx, op, &left, &right);
+                left.ty
             }
-            Operator::Equal
-            | Operator::NotEqual
-            | Operator::Less
-            | Operator::LessEqual
-            | Operator::Greater
-            | Operator::GreaterEqual => {
-                check_ty_num(&left);
-                check_ty_num(&right);
-                check_ty_equal();
-                ctx.table.common_type().bool
+            Operator::Equal | Operator::NotEqual => {
+                let _ = check_comparable_operand(ctx, op, &left, left_loc)
+                    && check_comparable_operand(ctx, op, &right, right_loc)
+                    && check_same_operand_types(ctx, op, &left, &right);
+                common_ty.bool
+            }
+            Operator::Less | Operator::LessEqual | Operator::Greater | Operator::GreaterEqual => {
+                let _ = check_ordered_operand(ctx, op, &left, left_loc)
+                    && check_ordered_operand(ctx, op, &right, right_loc)
+                    && check_same_operand_types(ctx, op, &left, &right);
+                common_ty.bool
+            }
+            _ => {
+                ctx._error(TypeResolveError::UnsupportedBinaryOp(op));
+                return FlowObj::none(merged_flow);
             }
-            _ => unimplemented!(),
         };
 
         FlowObj::new(
@@ -140,9 +274,15 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::BinaryExpr {
     }
 }
 
-impl Resolve<(), FlowObj<Expr>> for ast::expr::UnaryExpr {
-    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
-        let FlowObj { value, flow } = self.expr.resolve(ctx, ());
+impl Resolve<Option<TypeId>, FlowObj<Expr>> for ast::expr::UnaryExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, expect_ty: Option<TypeId>) -> FlowObj<Expr> {
+        // a negated literal is checked against the range of its type as a negative value, so that
+        // the smallest value of a signed type can be written.
+        if let (Operator::Sub, ast::expr::Expr::Integer(lit)) = (self.op.0, self.expr.as_ref()) {
+            return resolve_int_literal(ctx, lit, Some(self.op.1), expect_ty);
+        }
+
+        let FlowObj { value, flow } = self.expr.resolve(ctx, expect_ty);
         let Some(value) = value else {
             return FlowObj::none(flow);
         };
@@ -150,26 +290,46 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::UnaryExpr {
             return FlowObj::new(value, flow);
         };
 
+        let value_loc = self.expr.get_location();
         let op_ty = match self.op.0 {
             Operator::Sub => {
-                if !ctx
-                    .table
-                    .is_type_coercible(value.ty, ctx.table.common_type().int)
-                {
-                    todo!("report error")
+                match ctx.table.get_int(value.ty) {
+                    Some(int) if !int.int_layout().expect("integer type").0 => {
+                        ctx._error(TypeResolveError::UnsignedIntegerInUnaryOp(self.op));
+                    }
+                    Some(_) => {}
+                    None if is_numeric_type(ctx, value.ty) => {}
+                    None => ctx._error(TypeResolveError::NonNumericInUnaryOp(
+                        self.op.0,
+                        Located(value.ty, value_loc),
+                    )),
                 }
-                ctx.table.common_type().int
+                value.ty
             }
             Operator::Not => {
-                if !ctx
-                    .table
-                    .is_type_coercible(value.ty, ctx.table.common_type().bool)
-                {
-                    todo!("report error")
+                if !ctx.table.is_type_coercible(
+                    ctx.table.underlying_type(value.ty),
+                    ctx.table.common_type().bool,
+                ) {
+                    ctx._error(TypeResolveError::NonBoolUsedInNotOp(Located(
+                        value.ty, value_loc,
+                    )));
+                }
+                value.ty
+            }
+            Operator::BitNot => {
+                if !is_int_type(ctx, value.ty) {
+                    ctx._error(TypeResolveError::NonIntegerInUnaryOp(
+                        self.op.0,
+                        Located(value.ty, value_loc),
+                    ));
                 }
-                ctx.table.common_type().bool
+                value.ty
+            }
+            _ => {
+                ctx._error(TypeResolveError::UnsupportedUnaryOp(self.op));
+                return FlowObj::none(flow);
             }
-            _ => unimplemented!(),
         };
 
         FlowObj::new(
@@ -186,8 +346,8 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::UnaryExpr {
     }
 }
 
-impl Resolve<(), FlowObj<Expr>> for ast::expr::IfExpr {
-    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+impl Resolve<Option<TypeId>, FlowObj<Expr>> for ast::expr::IfExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, expect_ty: Option<TypeId>) -> FlowObj<Expr> {
         let FlowObj { value, flow } = self.cond.resolve(ctx, ());
         let Some(cond) = value else {
             return FlowObj::none(flow);
@@ -200,19 +360,25 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::IfExpr {
             .table
             .is_type_coercible(cond.ty, ctx.table.common_type().bool)
         {
-            todo!("report error")
+            ctx._error(TypeResolveError::NonBoolInIfCond(Located(
+                cond.ty,
+                self.cond.get_location(),
+            )));
         }
 
         let FlowObj {
             value: then_body,
             flow: then_flow,
-        } = self.then.resolve(ctx, ());
+        } = self.then.resolve(ctx, expect_ty);
 
-        if let Some(else_) = &self.else_expr {
+        if let Some(else_ast) = &self.else_expr {
             let FlowObj {
                 value: else_body,
                 flow: else_flow,
-            } = else_.body.resolve(ctx, ());
+            } = match &else_ast.body {
+                ast::expr::ElseBody::Block(v) => v.resolve(ctx, expect_ty),
+                ast::expr::ElseBody::If(v) => v.resolve(ctx, expect_ty),
+            };
 
             let merged_flow = then_flow & else_flow;
 
@@ -221,10 +387,17 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::IfExpr {
                 return FlowObj::none(merged_flow);
             };
 
-            let Some(if_ty) = ctx.table.compare_type_asymmetric(then.ty, else_.ty) else {
-                todo!("report error")
+            let if_ty = match ctx.table.compare_type_asymmetric(then.ty, else_.ty) {
+                Some(ty) => ty,
+                None => {
+                    ctx._error(TypeResolveError::BlockBranchTypeMismatch {
+                        branch: Located(else_.ty, else_value_loc(&else_ast.body)),
+                        other: Located(then.ty, block_value_loc(&self.then)),
+                    });
+                    then.ty
+                }
             };
-            let (ExprKind::Block(then), ExprKind::Block(else_)) = (then.kind, else_.kind) else {
+            let ExprKind::Block(then) = then.kind else {
                 unreachable!()
             };
 
@@ -233,7 +406,7 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::IfExpr {
                     kind: IfExpr {
                         cond: Box::new(cond),
                         then,
-                        else_: Some(else_),
+                        else_: Some(Box::new(else_)),
                     }
                     .into(),
                     ty: if_ty,
@@ -252,7 +425,10 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::IfExpr {
                 .table
                 .is_type_coercible(then_body.ty, ctx.table.common_type().unit)
             {
-                todo!("report error")
+                ctx._error(TypeResolveError::NonUnitIfWithoutElse(Located(
+                    then_body.ty,
+                    block_value_loc(&self.then),
+                )));
             }
 
             let ExprKind::Block(then) = then_body.kind else {
@@ -271,21 +447,357 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::IfExpr {
     }
 }
 
+impl Resolve<(), FlowObj<Expr>> for ast::expr::MatchExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        let FlowObj { value, flow } = self.expr.resolve(ctx, ());
+        let Some(expr) = value else {
+            return FlowObj::none(flow);
+        };
+        if flow != Flow::Continue {
+            return FlowObj::new(expr, flow);
+        }
+
+        let expr_ty = Located(expr.ty, self.expr.get_location());
+        let mut coverage = Coverage::new(ctx.table, expr.ty);
+        if coverage.is_none() && ctx.table.underlying_type(expr.ty) != ctx.table.common_type().error
+        {
+            ctx._error(TypeResolveError::NonMatchableType(expr_ty));
+        }
+
+        let mut arms = Vec::new();
+        // the coverage is unknown once a pattern fails to resolve, so exhaustiveness is not
+        // checked to avoid reporting what the pattern would have covered.
+        let mut is_pat_ok = true;
+        let mut match_ty: Option<Located<TypeId>> = None;
+        let mut match_flow: Option<Flow> = None;
+        for arm_ast in &self.arms {
+            let bid = ctx.table.new_block(ctx.get_func_id());
+            {
+                let parent_block = ctx.get_block();
+                bid.sym_mut(ctx.table).parent_block = Some(parent_block);
+            }
+            ctx.push_block(bid);
+
+            let pat = arm_ast.pat.resolve(ctx, expr_ty);
+            is_pat_ok &= pat.is_some();
+            if let (Some(coverage), Some(pat)) = (&coverage, &pat) {
+                if coverage.covers(ctx.table, pat) {
+                    ctx._error(ControlFlowError::UnreachableMatchArm(
+                        arm_ast.pat.get_location(),
+                    ));
+                }
+            }
+
+            let guard = arm_ast.guard.as_ref().and_then(|guard_ast| {
+                let guard = guard_ast.cond.resolve(ctx, ()).value?;
+                if !ctx
+                    .table
+                    .is_type_coercible(guard.ty, ctx.table.common_type().bool)
+                {
+                    ctx._error(TypeResolveError::NonBoolInMatchGuard(Located(
+                        guard.ty,
+                        guard_ast.cond.get_location(),
+                    )));
+                }
+                Some(guard)
+            });
+
+            let FlowObj {
+                value: arm_expr,
+                flow: arm_flow,
+            } = arm_ast.expr.resolve(ctx, ());
+            ctx.pop_block();
+
+            // guarded arms may not match, so they do not cover their pattern.
+            if let (Some(coverage), Some(pat), None) = (&mut coverage, &pat, &arm_ast.guard) {
+                coverage.insert(ctx.table, pat);
+            }
+
+            match_flow = Some(match match_flow {
+                Some(flow) => flow & arm_flow,
+                None => arm_flow,
+            });
+
+            let Some(arm_expr) = arm_expr else {
+                continue;
+            };
+            let arm_ty = Located(arm_expr.ty, arm_value_loc(&arm_ast.expr));
+            match_ty = Some(match match_ty {
+                None => arm_ty,
+                Some(first_ty) => match ctx.table.compare_type_asymmetric(first_ty.0, arm_ty.0) {
+                    Some(ty) => Located(ty, first_ty.1),
+                    None => {
+                        ctx._error(TypeResolveError::BlockBranchTypeMismatch {
+                            branch: arm_ty,
+                            other: first_ty,
+                        });
+                        first_ty
+                    }
+                },
+            });
+
+            let Some(pat) = pat else {
+                continue;
+            };
+            if arm_ast.guard.is_some() && guard.is_none() {
+                continue;
+            }
+            arms.push(MatchArm {
+                block_id: bid,
+                pat,
+                guard,
+                expr: arm_expr,
+            });
+        }
+
+        if let Some(missing) = coverage.filter(|_| is_pat_ok).and_then(|v| v.missing()) {
+            ctx._error(ControlFlowError::NonExhaustiveMatch {
+                loc: self.expr.get_location(),
+                missing,
+            });
+        }
+
+        FlowObj::new(
+            Expr {
+                kind: MatchExpr {
+                    expr: Box::new(expr),
+                    arms,
+                }
+                .into(),
+                ty: match_ty
+                    .map(|v| v.0)
+                    .unwrap_or(ctx.table.common_type().never),
+            },
+            match_flow.unwrap_or(Flow::Continue),
+        )
+    }
+}
+
 impl Resolve<(), FlowObj<Expr>> for ast::expr::LoopExpr {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
-        let FlowObj { value, flow } = self.body.resolve(ctx, ());
+        let label = self.label.as_ref().map(|v| v.name.clone());
+        ctx.push_loop(false, label.clone());
+        let FlowObj { value, .. } = self.body.resolve(ctx, None);
+        let scope = ctx.pop_loop();
+
+        // the loop only ends with a `break`, and evaluates to its value.
+        let (ty, flow) = match scope.break_ty {
+            Some(ty) => (ty.0, Flow::Continue),
+            None => (ctx.table.common_type().never, Flow::Break),
+        };
         let Some(body) = value else {
             return FlowObj::none(flow);
         };
+        let ExprKind::Block(block_expr) = body.kind else {
+            unreachable!()
+        };
+        FlowObj::new(
+            Expr {
+                kind: LoopExpr {
+                    label: label.map(|v| v.0),
+                    body: block_expr,
+                }
+                .into(),
+                ty,
+            },
+            flow,
+        )
+    }
+}
+
+impl Resolve<(), FlowObj<Expr>> for ast::expr::WhileExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        let FlowObj { value, flow } = self.cond.resolve(ctx, ());
+        let Some(cond) = value else {
+            return FlowObj::none(flow);
+        };
         if flow != Flow::Continue {
-            return FlowObj::new(body, flow);
+            return FlowObj::new(cond, flow);
         }
-        let ExprKind::Block(block_expr) = body.kind else {
+
+        if !ctx
+            .table
+            .is_type_coercible(cond.ty, ctx.table.common_type().bool)
+        {
+            ctx._error(TypeResolveError::NonBoolInWhileCond(Located(
+                cond.ty,
+                self.cond.get_location(),
+            )));
+        }
+
+        let label = self.label.as_ref().map(|v| v.name.clone());
+        ctx.push_loop(true, label.clone());
+        let FlowObj { value, .. } = self.body.resolve(ctx, None);
+        ctx.pop_loop();
+        let Some(body) = value else {
+            return FlowObj::cont_none();
+        };
+        let ExprKind::Block(body) = body.kind else {
             unreachable!()
         };
+
+        // the condition may be false from the start, so the loop may be skipped entirely.
+        FlowObj::cont(Expr {
+            kind: WhileExpr {
+                label: label.map(|v| v.0),
+                cond: Box::new(cond),
+                body,
+            }
+            .into(),
+            ty: ctx.table.common_type().unit,
+        })
+    }
+}
+
+impl Resolve<(), FlowObj<Expr>> for ast::expr::ForExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        let FlowObj { value, flow } = self.iter.resolve(ctx, ());
+        let Some(iter) = value else {
+            return FlowObj::none(flow);
+        };
+        if flow != Flow::Continue {
+            return FlowObj::new(iter, flow);
+        }
+
+        let common_ty = *ctx.table.common_type();
+        let elem_ty = match ctx.table.get_array(iter.ty) {
+            Some(v) => v.elem_ty,
+            None => match ctx.table.get_vec(iter.ty) {
+                Some(v) => v.elem_ty,
+                None => {
+                    if iter.ty != common_ty.error {
+                        ctx._error(TypeResolveError::NonIterableType(Located(
+                            iter.ty,
+                            self.iter.get_location(),
+                        )));
+                    }
+                    common_ty.error
+                }
+            },
+        };
+
+        // the loop variable is declared in a block of its own, which encloses the body.
+        let bid = ctx.table.new_block(ctx.get_func_id());
+        bid.sym_mut(ctx.table).parent_block = Some(ctx.get_block());
+        ctx.push_block(bid);
+        let var = ctx
+            .table
+            .new_variable(self.var.0.clone(), bid)
+            .expect("block of the loop should be empty");
+        let var_sym = var.sym_mut(ctx.table);
+        var_sym.ty = elem_ty;
+        var_sym.origin = self.var.1;
+
+        let label = self.label.as_ref().map(|v| v.name.clone());
+        ctx.push_loop(true, label.clone());
+        let FlowObj { value, .. } = self.body.resolve(ctx, None);
+        ctx.pop_loop();
+        ctx.pop_block();
+        let Some(body) = value else {
+            return FlowObj::cont_none();
+        };
+        let ExprKind::Block(body) = body.kind else {
+            unreachable!()
+        };
+
+        // the array or the vector may be empty, so the body may never run.
         FlowObj::cont(Expr {
-            kind: LoopExpr { body: block_expr }.into(),
-            // always return the Never type for now, as there is no Break/Continue expr yet.
+            kind: ForExpr {
+                label: label.map(|v| v.0),
+                block_id: bid,
+                var,
+                iter: Box::new(iter),
+                body,
+            }
+            .into(),
+            ty: common_ty.unit,
+        })
+    }
+}
+
+impl Resolve<(), FlowObj<Expr>> for ast::expr::BreakExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        let value = match &self.expr {
+            Some(expr) => {
+                let FlowObj { value, flow } = expr.resolve(ctx, ());
+                let Some(value) = value else {
+                    return FlowObj::none(flow);
+                };
+                if flow != Flow::Continue {
+                    return FlowObj::new(value, flow);
+                }
+                Some(value)
+            }
+            None => None,
+        };
+
+        let value_ty = Located(
+            value
+                .as_ref()
+                .map(|v| v.ty)
+                .unwrap_or(ctx.table.common_type().unit),
+            self.expr
+                .as_ref()
+                .map(|v| v.get_location())
+                .unwrap_or(self.break_tok.1),
+        );
+        let label = self.label.as_ref().map(|v| v.0.as_str());
+        let Some(scope) = ctx.get_loop(label) else {
+            match &self.label {
+                Some(label) if ctx.get_loop(None).is_some() => {
+                    ctx._error(IdentResolveError::UnknownLabel(label.clone()))
+                }
+                _ => ctx._error(ControlFlowError::BreakOutsideLoop(self.get_location())),
+            }
+            // the break still diverges, so no flow error follows it.
+            return FlowObj::brk_none();
+        };
+        let break_ty = match scope.break_ty {
+            _ if scope.is_while => {
+                if value.is_some() {
+                    ctx._error(ControlFlowError::BreakWithValueInWhile(value_ty.1));
+                }
+                value_ty
+            }
+            Some(first_ty) => match ctx.table.compare_type_asymmetric(first_ty.0, value_ty.0) {
+                Some(ty) => Located(ty, first_ty.1),
+                None => {
+                    ctx._error(TypeResolveError::BreakTypeMismatch { value_ty, first_ty });
+                    first_ty
+                }
+            },
+            None => value_ty,
+        };
+        ctx.get_loop_mut(label).expect("enclosing loop").break_ty = Some(break_ty);
+
+        FlowObj::brk(Expr {
+            kind: BreakExpr {
+                label: label.map(|v| v.to_owned()),
+                expr: value.map(Box::new),
+            }
+            .into(),
+            ty: ctx.table.common_type().never,
+        })
+    }
+}
+
+impl Resolve<(), FlowObj<Expr>> for ast::expr::ContinueExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        let label = self.label.as_ref().map(|v| v.0.as_str());
+        if ctx.get_loop(label).is_none() {
+            match &self.label {
+                Some(label) if ctx.get_loop(None).is_some() => {
+                    ctx._error(IdentResolveError::UnknownLabel(label.clone()))
+                }
+                _ => ctx._error(ControlFlowError::ContinueOutsideLoop(self.get_location())),
+            }
+            return FlowObj::brk_none();
+        }
+        FlowObj::brk(Expr {
+            kind: ContinueExpr {
+                label: label.map(|v| v.to_owned()),
+            }
+            .into(),
             ty: ctx.table.common_type().never,
         })
     }
@@ -293,7 +805,8 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::LoopExpr {
 
 impl Resolve<(), FlowObj<Expr>> for ast::expr::ReturnExpr {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
-        let expr = self.expr.as_ref().map(|v| v.resolve(ctx, ()));
+        let ret_ty = ctx.get_func_id().sym(ctx.table).ret_ty;
+        let expr = self.expr.as_ref().map(|v| v.resolve(ctx, Some(ret_ty)));
         if let Some(FlowObj { value, flow }) = expr {
             let Some(value) = value else {
                 return FlowObj::none(flow);
@@ -301,11 +814,13 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::ReturnExpr {
             if flow != Flow::Continue {
                 return FlowObj::new(value, flow);
             }
-            if !ctx
-                .table
-                .is_type_coercible(value.ty, ctx.get_func_id().sym(ctx.table).ret_ty)
-            {
-                todo!("report error");
+            let func_sym = ctx.get_func_id().sym(ctx.table);
+            if !ctx.table.is_type_coercible(value.ty, func_sym.ret_ty) {
+                ctx._error(TypeResolveError::ReturnTypeMismatch {
+                    function_name: func_sym.name.clone(),
+                    expected_type: func_sym.ret_ty,
+                    actual_type: Located(value.ty, expr_ast_loc(&self.expr)),
+                });
             }
             FlowObj::brk(Expr {
                 kind: ReturnExpr {
@@ -315,8 +830,13 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::ReturnExpr {
                 ty: ctx.table.common_type().never,
             })
         } else {
-            if ctx.get_func_id().sym(ctx.table).ret_ty != ctx.table.common_type().unit {
-                todo!("report error");
+            let func_sym = ctx.get_func_id().sym(ctx.table);
+            if func_sym.ret_ty != ctx.table.common_type().unit {
+                ctx._error(TypeResolveError::ReturnTypeMismatch {
+                    function_name: func_sym.name.clone(),
+                    expected_type: func_sym.ret_ty,
+                    actual_type: Located(ctx.table.common_type().unit, self.return_tok.1),
+                });
             }
             FlowObj::brk(Expr {
                 kind: ReturnExpr { expr: None }.into(),
@@ -328,34 +848,82 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::ReturnExpr {
 
 impl Resolve<(), FlowObj<Expr>> for ast::expr::CallExpr {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        if let ast::expr::Expr::Ident(name) = self.caller.as_ref() {
+            if let Some(target_ty) = conversion_target(ctx, name) {
+                return resolve_conversion(ctx, self, Located(target_ty, name.1));
+            }
+        }
+        if let ast::expr::Expr::Path(path) = self.caller.as_ref() {
+            return resolve_tuple_variant(ctx, self, path);
+        }
+        if let ast::expr::Expr::MemberAccess(access) = self.caller.as_ref() {
+            return resolve_method_call(ctx, self, access);
+        }
+
         let FlowObj {
             value,
             flow: mut result_flow,
-        } = self.caller.resolve(ctx, ());
+        } = match self.caller.as_ref() {
+            ast::expr::Expr::Ident(name) => match callee_function(ctx, name) {
+                Some(fid) => FlowObj::cont(Expr {
+                    kind: FuncIdentExpr { id: fid }.into(),
+                    ty: fid.sym(ctx.table).ret_ty,
+                }),
+                None => self.caller.resolve(ctx, ()),
+            },
+            _ => self.caller.resolve(ctx, ()),
+        };
         let Some(caller) = value else {
             // assumed the earlier resolve call had already reported the error.
+            resolve_unused_args(ctx, &self.args.items);
             return FlowObj::none(result_flow);
         };
 
         let ExprKind::FuncIdent(FuncIdentExpr { id: fid }) = caller.kind else {
-            todo!("report error")
+            if caller.ty != ctx.table.common_type().error {
+                ctx._error(TypeResolveError::CallOnNonFunctionType(Located(
+                    caller.ty,
+                    self.caller.get_location(),
+                )));
+            }
+            resolve_unused_args(ctx, &self.args.items);
+            return FlowObj::none(result_flow);
         };
 
         let sym = fid.sym(ctx.table);
-        if self.args.items.len() != sym.params.len() {
-            todo!("report error");
+        let func_name = Located(sym.name.clone(), self.caller.get_location());
+        let params = sym.params.clone();
+        if self.args.items.len() != params.len() {
+            ctx._error(ValueResolveError::ArgumentCountMismatch {
+                func_name: Located(func_name.0.clone(), self.get_location()),
+                expect_count: params.len(),
+                actual_count: self.args.items.len(),
+            });
         }
 
         let mut args = Vec::new();
-        for (ast_arg, param_id) in self.args.items.iter().zip(sym.params.clone()) {
-            let FlowObj { value, flow } = ast_arg.resolve(ctx, ());
+        let param_count = params.len();
+        for (i, (ast_arg, param_id)) in self.args.items.iter().zip(params).enumerate() {
+            let param_ty = param_id.sym(ctx.table).ty;
+            let FlowObj { value, flow } = ast_arg.resolve(ctx, Some(param_ty));
             let Some(arg) = value else {
                 // assumed the resolve called had already reported the error.
                 continue;
             };
-            let param_ty = param_id.sym(ctx.table).ty;
-            if !ctx.table.is_type_coercible(arg.ty, param_ty) {
-                todo!("report error");
+            if ctx.table.common_func().is_print(fid) {
+                if !ctx.table.is_type_printable(arg.ty) {
+                    ctx._error(TypeResolveError::UnprintableArgument {
+                        func_name: func_name.clone(),
+                        actual_type: Located(arg.ty, ast_arg.get_location()),
+                    });
+                }
+            } else if !ctx.table.is_type_coercible(arg.ty, param_ty) {
+                ctx._error(TypeResolveError::ArgumentTypeMismatch {
+                    func_name: func_name.clone(),
+                    argument_index: i,
+                    expect_type: param_ty,
+                    actual_type: Located(arg.ty, ast_arg.get_location()),
+                });
             }
             args.push(arg);
             result_flow = flow;
@@ -364,6 +932,9 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::CallExpr {
                 break;
             }
         }
+        if result_flow == Flow::Continue {
+            resolve_unused_args(ctx, self.args.items.get(param_count..).unwrap_or_default());
+        }
 
         FlowObj::new(
             Expr {
@@ -386,21 +957,21 @@ impl Resolve<(), FlowObj<Expr>> for Located<String> {
                 kind: VarIdentExpr { id: var.get_id() }.into(),
                 ty: var.ty,
             })
-        } else if let Some(func) = ctx.table.get_function_by_name_mut(&self.0) {
-            FlowObj::cont(Expr {
-                kind: FuncIdentExpr { id: func.get_id() }.into(),
-                ty: func.ret_ty,
-            })
+        } else if ctx.table.get_function_id(&self.0).is_some() {
+            ctx._error(IdentResolveError::FunctionUsedAsValue(self.clone()));
+            FlowObj::cont_none()
         } else if ctx.table.get_type_by_name_mut(&self.0).is_some() {
-            todo!("report error")
+            ctx._error(IdentResolveError::TypeUsedAsValue(self.clone()));
+            FlowObj::cont_none()
         } else {
-            todo!("report error")
+            ctx._error(IdentResolveError::UnknownIdentifier(self.clone()));
+            FlowObj::cont_none()
         }
     }
 }
 
-impl Resolve<(), FlowObj<Expr>> for ast::expr::BlockExpr {
-    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+impl Resolve<Option<TypeId>, FlowObj<Expr>> for ast::expr::BlockExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, expect_ty: Option<TypeId>) -> FlowObj<Expr> {
         let bid = ctx.table.new_block(ctx.get_func_id());
         {
             let parent_block = ctx.get_block();
@@ -411,12 +982,10 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::BlockExpr {
         let mut stmts = Vec::new();
         let mut result_flow = Flow::Continue;
         for stmt_ast in &self.stmts {
+            // a statement which failed to resolve may still diverge, like a `break` outside of a
+            // loop.
             let FlowObj { value: stmt, flow } = stmt_ast.resolve(ctx, ());
-            let Some(stmt) = stmt else {
-                continue;
-            };
-
-            stmts.push(stmt);
+            stmts.extend(stmt);
 
             if flow == Flow::Break {
                 result_flow = flow;
@@ -441,11 +1010,14 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::BlockExpr {
             );
         }
 
-        let eval_expr = self.eval_expr.as_ref().map(|v| v.resolve(ctx, ()));
-        let eval_expr_ty = eval_expr
-            .as_ref()
-            .and_then(|v| v.value.as_ref().map(|v| v.ty))
-            .unwrap_or(ctx.table.common_type().unit);
+        // a block whose value failed to resolve is of the error type, so that it does not produce
+        // follow-on errors.
+        let eval_expr = self.eval_expr.as_ref().map(|v| v.resolve(ctx, expect_ty));
+        let eval_expr_ty = match &eval_expr {
+            Some(FlowObj { value: Some(v), .. }) => v.ty,
+            Some(FlowObj { value: None, .. }) => ctx.table.common_type().error,
+            None => ctx.table.common_type().unit,
+        };
         if let Some(expr) = &eval_expr {
             result_flow = expr.flow;
         }
@@ -469,75 +1041,357 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::BlockExpr {
 
 impl Resolve<(), FlowObj<Expr>> for ast::expr::StructInitExpr {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        if let Some(variant_name) = &self.variant {
+            return resolve_struct_variant(ctx, self, variant_name);
+        }
+
         let (struct_id, struct_ty) = {
             let Some(ty_sym) = ctx.table.get_type_by_name(&self.ty_name.0) else {
-                todo!("report error")
+                ctx._error(TypeResolveError::UnknownTypeForIdent(self.ty_name.clone()));
+                return FlowObj::cont_none();
             };
             let Some(ty_kind) = &ty_sym.kind else {
-                todo!("report error")
+                // assumed the type declaration had already reported the error.
+                return FlowObj::cont_none();
             };
             let TypeKind::Struct(struct_ty) = ty_kind else {
-                todo!("report error")
+                let e = TypeResolveError::NonStructTypeInStructInit(Located(
+                    ty_sym.get_id(),
+                    self.ty_name.1,
+                ));
+                ctx._error(e);
+                return FlowObj::cont_none();
             };
             (ty_sym.get_id(), struct_ty.clone() /* :( */)
         };
 
-        if self.fields.items.len() != struct_ty.fields.len() {
-            todo!("report error")
+        let (fields, result_flow) = resolve_field_inits(ctx, self, struct_id, &struct_ty);
+        FlowObj::new(
+            Expr {
+                kind: StructInitExpr {
+                    struct_ty: struct_id,
+                    fields,
+                }
+                .into(),
+                ty: struct_id,
+            },
+            result_flow,
+        )
+    }
+}
+
+/// Resolves a variant with named fields, such as `Shape::Rect { w: 1, h: 2 }`.
+fn resolve_struct_variant(
+    ctx: &mut ResolveContext,
+    init: &ast::expr::StructInitExpr,
+    variant_name: &Located<String>,
+) -> FlowObj<Expr> {
+    let Some((enum_ty, tag, variant)) = resolve_variant(ctx, &init.ty_name, variant_name) else {
+        resolve_unused_field_inits(ctx, init);
+        return FlowObj::cont_none();
+    };
+    let VariantKind::Struct(struct_ty) = variant.kind else {
+        ctx._error(ValueResolveError::VariantKindMismatch {
+            enum_ty,
+            variant: variant_name.clone(),
+        });
+        resolve_unused_field_inits(ctx, init);
+        return FlowObj::cont_none();
+    };
+
+    let (fields, result_flow) = resolve_field_inits(ctx, init, enum_ty, &struct_ty);
+    let fields = fields
+        .into_iter()
+        .map(|(name, expr)| {
+            let i = struct_ty
+                .fields
+                .iter()
+                .position(|v| v.0 == name)
+                .expect("field of the variant");
+            (i, expr)
+        })
+        .collect();
+    FlowObj::new(
+        Expr {
+            kind: EnumInitExpr {
+                enum_ty,
+                variant: tag,
+                fields,
+            }
+            .into(),
+            ty: enum_ty,
+        },
+        result_flow,
+    )
+}
+
+/// Resolves the fields initialized for a struct, or for a variant with named fields, which
+/// belongs to `owner_ty`.
+fn resolve_field_inits(
+    ctx: &mut ResolveContext,
+    init: &ast::expr::StructInitExpr,
+    owner_ty: TypeId,
+    struct_ty: &StructType,
+) -> (Vec<(String, Expr)>, Flow) {
+    let mut result_flow = Flow::Continue;
+    let mut fields: Vec<(String, Expr)> = Vec::new();
+    let mut seen_fields: Vec<&Located<String>> = Vec::new();
+
+    for ast::expr::FieldInit {
+        field_name: ast_field_name,
+        expr: ast_expr,
+        ..
+    } in &init.fields.items
+    {
+        let FlowObj { value, flow } =
+            ast_expr.resolve(ctx, struct_ty.get_field_type(&ast_field_name.0));
+        result_flow = result_flow & flow;
+
+        let Some(expect_field_ty) = struct_ty.get_field_type(&ast_field_name.0) else {
+            ctx._error(IdentResolveError::UnknownField {
+                struct_ty: owner_ty,
+                field_name: ast_field_name.clone(),
+            });
+            continue;
+        };
+
+        if let Some(first) = seen_fields.iter().find(|v| v.0 == ast_field_name.0) {
+            ctx._error(IdentResolveError::FieldNameAlreadyUsed {
+                ident: ast_field_name.0.clone(),
+                first_origin: first.1,
+                dup_origin: ast_field_name.1,
+            });
+            continue;
         }
+        seen_fields.push(ast_field_name);
 
-        let mut result_flow = Flow::Continue;
-        let mut fields: Vec<(String, Expr)> = Vec::new();
+        let Some(value) = value else {
+            // assumed error had already been reported by the resolve call.
+            continue;
+        };
 
-        for ast::expr::FieldInit {
-            field_name: ast_field_name,
-            expr: ast_expr,
-            ..
-        } in &self.fields.items
-        {
-            let FlowObj { value, flow } = ast_expr.resolve(ctx, ());
-            result_flow = result_flow & flow;
-            let Some(value) = value else {
-                // assumed error had already been reported by the resolve call.
-                continue;
-            };
+        if !ctx.table.is_type_coercible(value.ty, expect_field_ty) {
+            ctx._error(TypeResolveError::FieldTypeMismatch {
+                field_name: ast_field_name.clone(),
+                expect_type: expect_field_ty,
+                actual_type: Located(value.ty, ast_expr.get_location()),
+            });
+        }
 
-            let Some(expect_field_ty) = struct_ty.get_field_type(&ast_field_name.0) else {
-                todo!("report error");
-            };
+        fields.push((ast_field_name.0.clone(), value));
 
-            if fields.iter().any(|v| v.0 == ast_field_name.0) {
-                todo!("report error");
-            }
+        if result_flow != Flow::Continue {
+            break;
+        }
+    }
 
-            if !ctx.table.is_type_coercible(value.ty, expect_field_ty) {
-                todo!("report error");
+    let missing_fields: Vec<String> = struct_ty
+        .fields
+        .iter()
+        .filter(|(name, _)| !seen_fields.iter().any(|v| v.0 == *name))
+        .map(|(name, _)| name.clone())
+        .collect();
+    if result_flow == Flow::Continue && !missing_fields.is_empty() {
+        ctx._error(ValueResolveError::MissingStructFields {
+            struct_ty: Located(owner_ty, init.get_location()),
+            fields: missing_fields,
+        });
+    }
+
+    (fields, result_flow)
+}
+
+/// Resolves the fields of an initializer that cannot be resolved, only to report the errors in
+/// them.
+fn resolve_unused_field_inits(ctx: &mut ResolveContext, init: &ast::expr::StructInitExpr) {
+    for field in &init.fields.items {
+        let _ = field.expr.resolve(ctx, ());
+    }
+}
+
+impl Resolve<(), FlowObj<Expr>> for ast::expr::PathExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        let Some((enum_ty, tag, variant)) = resolve_variant(ctx, &self.ty_name, &self.name) else {
+            return FlowObj::cont_none();
+        };
+        if !matches!(variant.kind, VariantKind::Unit) {
+            ctx._error(ValueResolveError::VariantKindMismatch {
+                enum_ty,
+                variant: self.name.clone(),
+            });
+            return FlowObj::cont_none();
+        }
+        FlowObj::cont(Expr {
+            kind: EnumInitExpr {
+                enum_ty,
+                variant: tag,
+                fields: vec![],
             }
+            .into(),
+            ty: enum_ty,
+        })
+    }
+}
+
+impl Resolve<(), FlowObj<Expr>> for ast::expr::MemberAccessExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        let FlowObj { value, flow } = self.expr.resolve(ctx, ());
+        let Some(value) = value else {
+            return FlowObj::none(flow);
+        };
+        if flow != Flow::Continue {
+            return FlowObj::new(value, flow);
+        }
+        if value.ty == ctx.table.common_type().error {
+            return FlowObj::none(flow);
+        }
+
+        let ty_sym = value.ty.sym(ctx.table);
+        let Some(ty_kind) = &ty_sym.kind else {
+            // assumed the type declaration had already reported the error.
+            return FlowObj::none(flow);
+        };
+        let TypeKind::Struct(struct_ty) = ty_kind else {
+            ctx._error(TypeResolveError::MemberAccessOnNonStructType(Located(
+                value.ty,
+                self.expr.get_location(),
+            )));
+            return FlowObj::none(flow);
+        };
+        let Some(field_ty) = struct_ty.get_field_type(&self.field_name.0) else {
+            ctx._error(IdentResolveError::UnknownField {
+                struct_ty: value.ty,
+                field_name: self.field_name.clone(),
+            });
+            return FlowObj::none(flow);
+        };
 
-            fields.push((ast_field_name.0.clone(), value));
+        FlowObj::new(
+            Expr {
+                kind: MemberAccessExpr {
+                    expr: Box::new(value),
+                    struct_ty: ty_sym.get_id(),
+                    field_name: self.field_name.0.clone(),
+                }
+                .into(),
+                ty: field_ty,
+            },
+            flow,
+        )
+    }
+}
 
+impl Resolve<Option<TypeId>, FlowObj<Expr>> for ast::expr::ArrayExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, expect_ty: Option<TypeId>) -> FlowObj<Expr> {
+        // the type of the elements is the first one they are all coercible to, or the expected
+        // one for an empty array.
+        let mut elem_ty: Option<Located<TypeId>> = None;
+        let expect_vec_ty = expect_ty.filter(|ty| ctx.table.get_vec(*ty).is_some());
+        let expect_elem_ty = expect_ty.and_then(|ty| {
+            ctx.table
+                .get_array(ty)
+                .map(|v| v.elem_ty)
+                .or(ctx.table.get_vec(ty).map(|v| v.elem_ty))
+        });
+        let mut elements = Vec::new();
+        let mut result_flow = Flow::Continue;
+        let mut is_ok = true;
+        for (i, ast_elem) in self.elements.items.iter().enumerate() {
+            let elem_hint = elem_ty.map(|v| v.0).or(expect_elem_ty);
+            let FlowObj { value, flow } = ast_elem.resolve(ctx, elem_hint);
+            let Some(elem) = value else {
+                // assumed the earlier resolve call had already reported the error. The rest of
+                // the elements are still resolved to report their errors.
+                is_ok = false;
+                continue;
+            };
+            let elem_loc = ast_elem.get_location();
+            match &mut elem_ty {
+                None => elem_ty = Some(Located(elem.ty, elem_loc)),
+                Some(expect) => match ctx.table.compare_type_asymmetric(expect.0, elem.ty) {
+                    Some(ty) => expect.0 = ty,
+                    None => ctx._error(TypeResolveError::ArrayElementTypeMismatch {
+                        element_index: i,
+                        expect_type: *expect,
+                        actual_type: Located(elem.ty, elem_loc),
+                    }),
+                },
+            }
+            elements.push(elem);
+            result_flow = flow;
             if result_flow != Flow::Continue {
+                // stop evaluating the subsequent elements if the control flow won't reach them.
                 break;
             }
         }
 
+        if !is_ok {
+            return FlowObj::none(result_flow);
+        }
+        let Some(elem_ty) = elem_ty.map(|v| v.0).or(expect_elem_ty) else {
+            ctx._error(TypeResolveError::UnknownArrayElementType(
+                self.get_location(),
+            ));
+            return FlowObj::cont_none();
+        };
+        // elements after one which diverges are not evaluated, so they are left out of the type.
+        let len = elements.len();
+        let array = Expr {
+            kind: ArrayExpr { elements }.into(),
+            ty: ctx.table.get_array_type(elem_ty, len),
+        };
+        let Some(expect_vec_ty) = expect_vec_ty else {
+            return FlowObj::new(array, result_flow);
+        };
+
+        // an array literal where a vector is expected is a new vector with its elements, so that
+        // `[]` is an empty vector of the expected type.
+        let vec_ty = match len {
+            0 => expect_vec_ty,
+            _ => ctx.table.get_vec_type(elem_ty),
+        };
         FlowObj::new(
             Expr {
-                kind: StructInitExpr {
-                    struct_ty: struct_id,
-                    fields,
+                kind: MethodCallExpr {
+                    receiver: Box::new(array),
+                    method: BuiltinMethod::ArrayToVec,
+                    args: vec![],
                 }
                 .into(),
-                ty: struct_id,
+                ty: vec_ty,
             },
             result_flow,
         )
     }
 }
 
-impl Resolve<(), FlowObj<Expr>> for ast::expr::MemberAccessExpr {
+impl Resolve<Option<TypeId>, FlowObj<Expr>> for ast::expr::ArrayRepeatExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, expect_ty: Option<TypeId>) -> FlowObj<Expr> {
+        let expect_elem_ty = expect_ty.and_then(|ty| ctx.table.get_array(ty).map(|v| v.elem_ty));
+        let FlowObj { value, flow } = self.expr.resolve(ctx, expect_elem_ty);
+        check_array_len(ctx, self.len);
+        let Some(value) = value else {
+            return FlowObj::none(flow);
+        };
+        let ty = ctx.table.get_array_type(value.ty, self.len.0);
+        FlowObj::new(
+            Expr {
+                kind: ArrayRepeatExpr {
+                    expr: Box::new(value),
+                    len: self.len.0,
+                }
+                .into(),
+                ty,
+            },
+            flow,
+        )
+    }
+}
+
+impl Resolve<(), FlowObj<Expr>> for ast::expr::CastExpr {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
         let FlowObj { value, flow } = self.expr.resolve(ctx, ());
+        let target_ty = self.ty.resolve(ctx, ());
         let Some(value) = value else {
             return FlowObj::none(flow);
         };
@@ -545,28 +1399,550 @@ impl Resolve<(), FlowObj<Expr>> for ast::expr::MemberAccessExpr {
             return FlowObj::new(value, flow);
         }
 
-        let ty_sym = value.ty.sym(ctx.table);
-        let Some(ty_kind) = &ty_sym.kind else {
-            todo!("report error");
+        let Some(kind) = ctx.table.get_cast_kind(value.ty, target_ty) else {
+            ctx._error(TypeResolveError::InvalidTypeCast {
+                loc: self.get_location(),
+                from_ty: value.ty,
+                to_ty: target_ty,
+            });
+            return FlowObj::cont_none();
         };
-        let TypeKind::Struct(struct_ty) = ty_kind else {
-            todo!("report error");
+        FlowObj::cont(Expr {
+            kind: CastExpr {
+                expr: Box::new(value),
+                kind,
+            }
+            .into(),
+            ty: target_ty,
+        })
+    }
+}
+
+impl Resolve<(), FlowObj<Expr>> for ast::expr::IndexExpr {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<Expr> {
+        let FlowObj { value, flow } = self.expr.resolve(ctx, ());
+        let Some(value) = value else {
+            resolve_unused_args(ctx, std::slice::from_ref(self.index.as_ref()));
+            return FlowObj::none(flow);
         };
-        let Some(field_ty) = struct_ty.get_field_type(&self.field_name.0) else {
-            todo!("report error");
+        if flow != Flow::Continue {
+            return FlowObj::new(value, flow);
+        }
+        let FlowObj { value: index, flow } = self.index.resolve(ctx, ());
+        let Some(index) = index else {
+            return FlowObj::none(flow);
+        };
+
+        let common_ty = *ctx.table.common_type();
+        if value.ty == common_ty.error {
+            return FlowObj::none(flow);
+        }
+        let arr_loc = self.expr.get_location();
+        // indexing a string gives the byte at the index
+        let elem_ty = match ctx.table.get_array(value.ty) {
+            Some(v) => Some(v.elem_ty),
+            None if ctx.table.underlying_type(value.ty) == common_ty.str => Some(common_ty.int),
+            None => ctx.table.get_vec(value.ty).map(|v| v.elem_ty),
+        };
+        let Some(elem_ty) = elem_ty else {
+            ctx._error(TypeResolveError::IndexingOnNonArrayType(Located(
+                value.ty, arr_loc,
+            )));
+            return FlowObj::none(flow);
+        };
+        // an index of any integer type is converted to an `int`.
+        let index = if ctx
+            .table
+            .is_type_coercible(ctx.table.underlying_type(index.ty), common_ty.int)
+        {
+            index
+        } else if ctx.table.get_int(index.ty).is_some() {
+            Expr {
+                kind: CastExpr {
+                    expr: Box::new(index),
+                    kind: CastKind::IntToInt,
+                }
+                .into(),
+                ty: common_ty.int,
+            }
+        } else {
+            ctx._error(TypeResolveError::ExpectUnsignedIntOnArrayIndex {
+                arr_ty: Located(value.ty, arr_loc),
+                index_ty: Located(index.ty, self.index.get_location()),
+            });
+            index
         };
 
         FlowObj::new(
             Expr {
-                kind: MemberAccessExpr {
+                kind: IndexExpr {
                     expr: Box::new(value),
-                    struct_ty: ty_sym.get_id(),
-                    field_name: self.field_name.0.clone(),
+                    index: Box::new(index),
                 }
                 .into(),
-                ty: field_ty,
+                ty: elem_ty,
             },
             flow,
         )
     }
 }
+
+/// Reports the value not being coercible to the target of an assignment or a `let`. A mismatch
+/// between arrays which only differ in length is reported as such.
+pub fn check_assignment(
+    ctx: &mut ResolveContext,
+    target_ty: Located<TypeId>,
+    value_ty: Located<TypeId>,
+) {
+    if ctx.table.is_type_coercible(value_ty.0, target_ty.0) {
+        return;
+    }
+    if let (Some(target), Some(value)) = (
+        ctx.table.get_array(target_ty.0),
+        ctx.table.get_array(value_ty.0),
+    ) {
+        if target.len != value.len && ctx.table.is_type_coercible(value.elem_ty, target.elem_ty) {
+            let (expect_count, actual_count) = (target.len, value.len);
+            ctx._error(ValueResolveError::ArrayLengthMismatch {
+                loc: value_ty.1,
+                expect_count,
+                actual_count,
+            });
+            return;
+        }
+    }
+    ctx._error(TypeResolveError::AssignmentTypeMismatch {
+        target_ty,
+        value_ty,
+    });
+}
+
+/// Returns whether the expression is a variable, or a field or an element of an assignable
+/// place. Strings are immutable, so the bytes of a string are not assignable.
+fn is_assignable_place(ctx: &ResolveContext, expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::VarIdent(_) => true,
+        ExprKind::MemberAccess(access) => is_assignable_place(ctx, &access.expr),
+        ExprKind::Index(index) => {
+            ctx.table.underlying_type(index.expr.ty) != ctx.table.common_type().str
+                && is_assignable_place(ctx, &index.expr)
+        }
+        _ => false,
+    }
+}
+
+/// Returns the type named by the callee of a call, which makes the call a conversion, unless the
+/// name is shadowed by a variable.
+fn conversion_target(ctx: &ResolveContext, name: &Located<String>) -> Option<TypeId> {
+    if ctx
+        .table
+        .get_variable_id_by_name(ctx.get_block(), &name.0)
+        .is_some()
+    {
+        return None;
+    }
+    ctx.table.get_type_id(&name.0)
+}
+
+/// Returns the function called by its name, unless a variable of the name shadows it.
+fn callee_function(ctx: &ResolveContext, name: &Located<String>) -> Option<FuncId> {
+    if ctx
+        .table
+        .get_variable_id_by_name(ctx.get_block(), &name.0)
+        .is_some()
+    {
+        return None;
+    }
+    ctx.table.get_function_id(&name.0)
+}
+
+/// Resolves a call to a type, such as `Int(1)` for `type Int = int;` or `int(v)` to convert it
+/// back. Aliases are distinct from the types they are declared with, so the conversion is
+/// explicit, but it is free at runtime.
+fn resolve_conversion(
+    ctx: &mut ResolveContext,
+    call: &ast::expr::CallExpr,
+    target_ty: Located<TypeId>,
+) -> FlowObj<Expr> {
+    let [ast_arg] = call.args.items.as_slice() else {
+        ctx._error(ValueResolveError::ArgumentCountMismatch {
+            func_name: Located(target_ty.0.sym(ctx.table).name.clone(), call.get_location()),
+            expect_count: 1,
+            actual_count: call.args.items.len(),
+        });
+        resolve_unused_args(ctx, &call.args.items);
+        return FlowObj::cont_none();
+    };
+
+    // a literal argument is of the underlying target type, as it is in a `let` of the type.
+    let arg_hint = ctx.table.underlying_type(target_ty.0);
+    let FlowObj { value, flow } = ast_arg.resolve(ctx, Some(arg_hint));
+    let Some(arg) = value else {
+        return FlowObj::none(flow);
+    };
+    if !ctx.table.is_type_convertible(arg.ty, target_ty.0) {
+        ctx._error(TypeResolveError::InvalidConversion {
+            target_type: target_ty,
+            actual_type: Located(arg.ty, ast_arg.get_location()),
+        });
+    }
+
+    FlowObj::new(
+        Expr {
+            kind: ConvertExpr {
+                expr: Box::new(arg),
+            }
+            .into(),
+            ty: target_ty.0,
+        },
+        flow,
+    )
+}
+
+/// Resolves a call to a tuple variant, such as `Shape::Circle(1)`, which constructs the variant
+/// with the arguments as its fields.
+fn resolve_tuple_variant(
+    ctx: &mut ResolveContext,
+    call: &ast::expr::CallExpr,
+    path: &ast::expr::PathExpr,
+) -> FlowObj<Expr> {
+    let Some((enum_ty, tag, variant)) = resolve_variant(ctx, &path.ty_name, &path.name) else {
+        resolve_unused_args(ctx, &call.args.items);
+        return FlowObj::cont_none();
+    };
+    let VariantKind::Tuple(field_types) = variant.kind else {
+        ctx._error(ValueResolveError::VariantKindMismatch {
+            enum_ty,
+            variant: path.name.clone(),
+        });
+        resolve_unused_args(ctx, &call.args.items);
+        return FlowObj::cont_none();
+    };
+
+    let variant_name = format!("{}::{}", path.ty_name.0, path.name.0);
+    if call.args.items.len() != field_types.len() {
+        ctx._error(ValueResolveError::ArgumentCountMismatch {
+            func_name: Located(variant_name.clone(), call.get_location()),
+            expect_count: field_types.len(),
+            actual_count: call.args.items.len(),
+        });
+    }
+
+    let mut result_flow = Flow::Continue;
+    let mut fields = Vec::new();
+    for (i, (ast_arg, field_ty)) in call.args.items.iter().zip(&field_types).enumerate() {
+        let FlowObj { value, flow } = ast_arg.resolve(ctx, Some(*field_ty));
+        result_flow = flow;
+        if let Some(arg) = value {
+            if !ctx.table.is_type_coercible(arg.ty, *field_ty) {
+                ctx._error(TypeResolveError::ArgumentTypeMismatch {
+                    func_name: Located(variant_name.clone(), path.get_location()),
+                    argument_index: i,
+                    expect_type: *field_ty,
+                    actual_type: Located(arg.ty, ast_arg.get_location()),
+                });
+            }
+            fields.push((i, arg));
+        }
+        if result_flow != Flow::Continue {
+            break;
+        }
+    }
+    if result_flow == Flow::Continue {
+        let field_count = field_types.len();
+        resolve_unused_args(ctx, call.args.items.get(field_count..).unwrap_or_default());
+    }
+
+    FlowObj::new(
+        Expr {
+            kind: EnumInitExpr {
+                enum_ty,
+                variant: tag,
+                fields,
+            }
+            .into(),
+            ty: enum_ty,
+        },
+        result_flow,
+    )
+}
+
+/// Resolves a call to a method of a builtin type, such as `v.push(1)`. Arrays have `len` and
+/// `to_vec`, and vectors have `push`, `pop` and `len`.
+fn resolve_method_call(
+    ctx: &mut ResolveContext,
+    call: &ast::expr::CallExpr,
+    access: &ast::expr::MemberAccessExpr,
+) -> FlowObj<Expr> {
+    let FlowObj {
+        value,
+        flow: mut result_flow,
+    } = access.expr.resolve(ctx, ());
+    let Some(receiver) = value else {
+        resolve_unused_args(ctx, &call.args.items);
+        return FlowObj::none(result_flow);
+    };
+    if result_flow != Flow::Continue {
+        return FlowObj::new(receiver, result_flow);
+    }
+    let common_ty = *ctx.table.common_type();
+    if receiver.ty == common_ty.error {
+        resolve_unused_args(ctx, &call.args.items);
+        return FlowObj::cont_none();
+    }
+
+    let method_name = &access.field_name;
+    let method = if let Some(vec_ty) = ctx.table.get_vec(receiver.ty) {
+        let elem_ty = vec_ty.elem_ty;
+        match method_name.0.as_str() {
+            "push" => Some((BuiltinMethod::VecPush, vec![elem_ty], common_ty.unit)),
+            "pop" => Some((BuiltinMethod::VecPop, vec![], elem_ty)),
+            "len" => Some((BuiltinMethod::VecLen, vec![], common_ty.int)),
+            _ => None,
+        }
+    } else if let Some(array_ty) = ctx.table.get_array(receiver.ty) {
+        let elem_ty = array_ty.elem_ty;
+        match method_name.0.as_str() {
+            "len" => Some((BuiltinMethod::ArrayLen, vec![], common_ty.int)),
+            "to_vec" => Some((
+                BuiltinMethod::ArrayToVec,
+                vec![],
+                ctx.table.get_vec_type(elem_ty),
+            )),
+            _ => None,
+        }
+    } else if ctx.table.get_int(receiver.ty).is_some() {
+        let method = match method_name.0.as_str() {
+            "wrapping_add" => Some(BuiltinMethod::WrappingAdd),
+            "wrapping_sub" => Some(BuiltinMethod::WrappingSub),
+            "wrapping_mul" => Some(BuiltinMethod::WrappingMul),
+            "saturating_add" => Some(BuiltinMethod::SaturatingAdd),
+            "saturating_sub" => Some(BuiltinMethod::SaturatingSub),
+            "saturating_mul" => Some(BuiltinMethod::SaturatingMul),
+            _ => None,
+        };
+        method.map(|v| (v, vec![receiver.ty], receiver.ty))
+    } else if ctx.table.underlying_type(receiver.ty) == common_ty.str {
+        match method_name.0.as_str() {
+            "len" => Some((BuiltinMethod::StrLen, vec![], common_ty.int)),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    let Some((method, params, ret_ty)) = method else {
+        ctx._error(IdentResolveError::UnknownMethod {
+            ty: receiver.ty,
+            method_name: method_name.clone(),
+        });
+        resolve_unused_args(ctx, &call.args.items);
+        return FlowObj::cont_none();
+    };
+
+    if call.args.items.len() != params.len() {
+        ctx._error(ValueResolveError::ArgumentCountMismatch {
+            func_name: Located(method_name.0.clone(), call.get_location()),
+            expect_count: params.len(),
+            actual_count: call.args.items.len(),
+        });
+    }
+
+    let mut args = Vec::new();
+    for (i, (ast_arg, param_ty)) in call.args.items.iter().zip(&params).enumerate() {
+        let FlowObj { value, flow } = ast_arg.resolve(ctx, Some(*param_ty));
+        result_flow = flow;
+        if let Some(arg) = value {
+            if !ctx.table.is_type_coercible(arg.ty, *param_ty) {
+                ctx._error(TypeResolveError::ArgumentTypeMismatch {
+                    func_name: method_name.clone(),
+                    argument_index: i,
+                    expect_type: *param_ty,
+                    actual_type: Located(arg.ty, ast_arg.get_location()),
+                });
+            }
+            args.push(arg);
+        }
+        if result_flow != Flow::Continue {
+            break;
+        }
+    }
+    if result_flow == Flow::Continue {
+        resolve_unused_args(ctx, call.args.items.get(params.len()..).unwrap_or_default());
+    }
+
+    FlowObj::new(
+        Expr {
+            kind: MethodCallExpr {
+                receiver: Box::new(receiver),
+                method,
+                args,
+            }
+            .into(),
+            ty: ret_ty,
+        },
+        result_flow,
+    )
+}
+
+/// Resolves the arguments that cannot be passed to the callee, only to report the errors in them.
+fn resolve_unused_args(ctx: &mut ResolveContext, args: &[ast::expr::Expr]) {
+    for arg in args {
+        let _ = arg.resolve(ctx, ());
+    }
+}
+
+fn check_operand_type(
+    ctx: &mut ResolveContext,
+    op: Located<Operator>,
+    operand: &Expr,
+    loc: Span,
+    expect_type: TypeId,
+) -> bool {
+    if ctx
+        .table
+        .is_type_coercible(ctx.table.underlying_type(operand.ty), expect_type)
+    {
+        return true;
+    }
+    ctx._error(TypeResolveError::UnexpectedTypeInBinaryOp {
+        op,
+        expect_type,
+        actual_type: Located(operand.ty, loc),
+    });
+    false
+}
+
+/// Returns whether the type is an integer type, `float`, or an alias of one. The types without
+/// values, which are coercible to any type, are also numeric.
+fn is_numeric_type(ctx: &ResolveContext, ty: TypeId) -> bool {
+    ctx.table.get_int(ty).is_some()
+        || ctx.table.underlying_type(ty) == ctx.table.common_type().float
+        || ctx
+            .table
+            .is_type_coercible(ctx.table.underlying_type(ty), ctx.table.common_type().int)
+}
+
+fn check_numeric_operand(
+    ctx: &mut ResolveContext,
+    op: Located<Operator>,
+    operand: &Expr,
+    loc: Span,
+) -> bool {
+    if is_numeric_type(ctx, operand.ty) {
+        return true;
+    }
+    ctx._error(TypeResolveError::NonNumericTypeInBinaryOp {
+        op,
+        ty: Located(operand.ty, loc),
+    });
+    false
+}
+
+fn is_int_type(ctx: &ResolveContext, ty: TypeId) -> bool {
+    ctx.table.get_int(ty).is_some()
+        || ctx
+            .table
+            .is_type_coercible(ctx.table.underlying_type(ty), ctx.table.common_type().int)
+}
+
+fn check_int_operand(
+    ctx: &mut ResolveContext,
+    op: Located<Operator>,
+    operand: &Expr,
+    loc: Span,
+) -> bool {
+    if is_int_type(ctx, operand.ty) {
+        return true;
+    }
+    ctx._error(TypeResolveError::NonIntegerTypeInBinaryOp {
+        op,
+        ty: Located(operand.ty, loc),
+    });
+    false
+}
+
+fn check_comparable_operand(
+    ctx: &mut ResolveContext,
+    op: Located<Operator>,
+    operand: &Expr,
+    loc: Span,
+) -> bool {
+    let common_ty = ctx.table.common_type();
+    let ty = ctx.table.underlying_type(operand.ty);
+    if ty != common_ty.unit && ty != common_ty.never {
+        return true;
+    }
+    ctx._error(TypeResolveError::UncomparableTypeInBinaryOp {
+        op,
+        ty: Located(operand.ty, loc),
+    });
+    false
+}
+
+fn check_ordered_operand(
+    ctx: &mut ResolveContext,
+    op: Located<Operator>,
+    operand: &Expr,
+    loc: Span,
+) -> bool {
+    if is_numeric_type(ctx, operand.ty) {
+        return true;
+    }
+    ctx._error(TypeResolveError::UnorderedTypeInBinaryOp {
+        op,
+        ty: Located(operand.ty, loc),
+    });
+    false
+}
+
+fn check_same_operand_types(
+    ctx: &mut ResolveContext,
+    op: Located<Operator>,
+    left: &Expr,
+    right: &Expr,
+) -> bool {
+    // an operand which diverges, such as `break`, never produces a value to mismatch.
+    if ctx
+        .table
+        .compare_type_asymmetric(left.ty, right.ty)
+        .is_some()
+    {
+        return true;
+    }
+    ctx._error(TypeResolveError::TypeMismatchInBinaryOp {
+        op,
+        left_ty: left.ty,
+        right_ty: right.ty,
+    });
+    false
+}
+
+/// Returns the location of the value a block evaluates to.
+fn block_value_loc(block: &ast::expr::BlockExpr) -> Span {
+    block
+        .eval_expr
+        .as_ref()
+        .map(|v| v.get_location())
+        .unwrap_or(block.get_location())
+}
+
+/// Returns the location of the value of an `else` branch. The value of an `else if` is located
+/// at its first branch, which its type is taken from.
+fn else_value_loc(body: &ast::expr::ElseBody) -> Span {
+    match body {
+        ast::expr::ElseBody::Block(v) => block_value_loc(v),
+        ast::expr::ElseBody::If(v) => block_value_loc(&v.then),
+    }
+}
+
+/// Returns the location of the value of a `match` arm.
+fn arm_value_loc(expr: &ast::expr::Expr) -> Span {
+    match expr {
+        ast::expr::Expr::Block(v) => block_value_loc(v),
+        _ => expr.get_location(),
+    }
+}
+
+fn expr_ast_loc(expr: &Option<Box<ast::expr::Expr>>) -> Span {
+    expr.as_ref().map(|v| v.get_location()).unwrap_or_default()
+}
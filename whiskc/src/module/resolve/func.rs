@@ -1,77 +1,119 @@
 use super::{Flow, FlowObj, Record, Resolve, ResolveContext};
 
 use crate::{
-    ast::nodes as ast,
-    module::nodes::{
-        expr::ExprKind,
-        func::{ExternFunction, Function},
+    ast::{
+        location::{Locatable, Located},
+        nodes as ast,
+    },
+    module::{
+        errors::{ControlFlowError, IdentResolveError, TypeResolveError},
+        nodes::{
+            expr::ExprKind,
+            func::{ExternFunction, Function},
+        },
     },
     symbol::{BlockId, FuncId, VarId},
 };
 
 impl Record<(), bool> for ast::func::FunctionSig {
     fn record(&self, ctx: &mut ResolveContext, _: ()) -> bool {
-        if ctx.table.get_type_by_name(&self.name.0).is_some() {
-            todo!("report error");
-        }
-        if ctx.table.new_function(self.name.0.clone()).is_none() {
-            todo!("report error");
+        let first_origin = if let Some(ty) = ctx.table.get_type_by_name(&self.name.0) {
+            Some(ty.origin)
         } else {
-            true
+            ctx.table
+                .get_function_by_name(&self.name.0)
+                .map(|func| func.origin)
+        };
+        if let Some(first_origin) = first_origin {
+            ctx._error(IdentResolveError::GlobalIdentAlreadyUsed {
+                ident: self.name.0.clone(),
+                first_origin,
+                dup_origin: self.name.1,
+            });
+            return false;
         }
+
+        let fid = ctx
+            .table
+            .new_function(self.name.0.clone())
+            .expect("unused function name");
+        fid.sym_mut(ctx.table).origin = self.name.1;
+        true
     }
 }
 
-impl Resolve<(), Option<(FuncId, BlockId)>> for ast::func::FunctionSig {
-    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> Option<(FuncId, BlockId)> {
+impl Resolve<(), ()> for ast::func::FunctionSig {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) {
         let fid = ctx.table.get_function_id(&self.name.0).unwrap();
         let bid = ctx.table.new_block(fid);
 
         let mut params: Vec<VarId> = Vec::new();
         for ast_param in &self.params.items {
-            let Some(param_ty) = ast_param.1.resolve(ctx, ()) else {
-                // assumed an error is reported by the resolve call.
-                continue;
-            };
+            let param_ty = ast_param.1.resolve(ctx, ());
             let Some(param_id) = ctx.table.new_variable(ast_param.0 .0.clone(), bid) else {
-                todo!("report error")
+                let first = ctx
+                    .table
+                    .get_variable_id_by_name(bid, &ast_param.0 .0)
+                    .expect("variable with the same name")
+                    .sym(ctx.table);
+                ctx._error(IdentResolveError::VarNameAlreadyUsed {
+                    ident: ast_param.0 .0.clone(),
+                    first_origin: (first.ty, first.origin),
+                    dup_origin: (param_ty, ast_param.0 .1),
+                });
+                continue;
             };
-            param_id.sym_mut(ctx.table).ty = param_ty;
+            let param_sym = param_id.sym_mut(ctx.table);
+            param_sym.ty = param_ty;
+            param_sym.origin = ast_param.0 .1;
             params.push(param_id)
         }
 
-        let Some(ret_ty) = self.ret_ty.resolve(ctx, ()) else {
-            // assumed an error is reported by the resolve call.
-            return None;
-        };
+        let ret_ty = self.ret_ty.resolve(ctx, ());
 
         let sym = fid.sym_mut(ctx.table);
         sym.params = params;
         sym.ret_ty = ret_ty;
         sym.entry_block = bid;
-        Some((fid, bid))
     }
 }
 
+/// Returns the function of the signature and its entry block, whose signature must have been
+/// resolved before.
+fn resolved_sig(ctx: &ResolveContext, sig: &ast::func::FunctionSig) -> (FuncId, BlockId) {
+    let fid = ctx
+        .table
+        .get_function_id(&sig.name.0)
+        .expect("recorded function");
+    (fid, fid.sym(ctx.table).entry_block)
+}
+
 impl Resolve<(), Option<Function>> for ast::func::Function {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> Option<Function> {
-        let (fid, bid) = self.sig.resolve(ctx, ())?;
+        let (fid, bid) = resolved_sig(ctx, &self.sig);
         ctx.set_func_id(fid);
         ctx.push_block(bid);
 
-        let FlowObj { value: body, flow } = self.body.resolve(ctx, ());
-        let ExprKind::Block(body) = body?.kind else {
+        let expect_ret_ty = fid.sym(ctx.table).ret_ty;
+        let FlowObj { value: body, flow } = self.body.resolve(ctx, Some(expect_ret_ty));
+        let body = body?;
+        let ret_ty = body.ty;
+        let ExprKind::Block(body) = body.kind else {
             unreachable!()
         };
 
-        let ret_ty = body
-            .eval_expr
-            .as_ref()
-            .map(|v| v.ty)
-            .unwrap_or(ctx.table.common_type().unit);
-        let expect_ret_ty = fid.sym(ctx.table).ret_ty;
         if flow != Flow::Break && !ctx.table.is_type_coercible(ret_ty, expect_ret_ty) {
-            todo!("report error");
+            if let Some(eval_expr) = &self.body.eval_expr {
+                ctx._error(TypeResolveError::ReturnTypeMismatch {
+                    function_name: self.sig.name.0.clone(),
+                    expected_type: expect_ret_ty,
+                    actual_type: Located(ret_ty, eval_expr.get_location()),
+                });
+            } else {
+                ctx._error(ControlFlowError::NotAllFuncPathReturned(
+                    self.sig.name.clone(),
+                ));
+            }
         }
 
         ctx.pop_block();
@@ -82,7 +124,7 @@ impl Resolve<(), Option<Function>> for ast::func::Function {
 
 impl Resolve<(), Option<ExternFunction>> for ast::func::ExternFunction {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> Option<ExternFunction> {
-        let (fid, _) = self.sig.resolve(ctx, ())?;
+        let (fid, _) = resolved_sig(ctx, &self.sig);
         Some(ExternFunction(fid))
     }
 }
@@ -1,20 +1,29 @@
 use std::ops::BitAnd;
 
 use crate::{
-    ast::AST,
-    symbol::{BlockId, FuncId, SymbolTable},
+    ast::{location::Located, AST},
+    symbol::{BlockId, FuncId, SymbolTable, TypeId},
 };
 
-use super::{errors::ResolveError, Module};
+use super::{
+    errors::{IdentResolveError, ResolveError},
+    Module,
+};
 
 mod ast;
 mod expr;
 mod func;
 mod item;
+mod pat;
 mod stmt;
 mod ty;
 
-pub fn resolve(ast: &AST, module_name: String) -> Result<Module, Vec<ResolveError>> {
+/// Resolves the AST into a module. On failure, the errors are returned along with the symbol table
+/// they refer to.
+pub fn resolve(
+    ast: &AST,
+    module_name: String,
+) -> Result<Module, (Vec<ResolveError>, Box<SymbolTable>)> {
     let mut module = Module {
         sym_table: SymbolTable::new(),
         name: module_name,
@@ -26,7 +35,8 @@ pub fn resolve(ast: &AST, module_name: String) -> Result<Module, Vec<ResolveErro
     // dbg!(&ctx);
 
     if !ctx.errors.is_empty() {
-        Err(ctx.errors)
+        let errors = ctx.errors;
+        Err((errors, Box::new(module.sym_table)))
     } else {
         Ok(module)
     }
@@ -51,6 +61,7 @@ struct ResolveContext<'md> {
     errors: Vec<ResolveError>,
     current_fid: Option<FuncId>,
     blocks: Vec<BlockId>,
+    loops: Vec<LoopScope>,
 }
 impl<'a> ResolveContext<'a> {
     pub fn new(table: &'a mut SymbolTable) -> Self {
@@ -59,6 +70,7 @@ impl<'a> ResolveContext<'a> {
             errors: vec![],
             current_fid: None,
             blocks: vec![],
+            loops: vec![],
         }
     }
 
@@ -93,11 +105,66 @@ impl<'a> ResolveContext<'a> {
         self.blocks.last().copied().unwrap()
     }
 
+    pub fn push_loop(&mut self, is_while: bool, label: Option<Located<String>>) {
+        if let Some(label) = &label {
+            if let Some(first) = self
+                .loops
+                .iter()
+                .filter_map(|v| v.label.as_ref())
+                .find(|v| v.0 == label.0)
+            {
+                self._error(IdentResolveError::LabelAlreadyUsed {
+                    label: label.0.clone(),
+                    first_origin: first.1,
+                    dup_origin: label.1,
+                });
+            }
+        }
+        self.loops.push(LoopScope {
+            is_while,
+            label,
+            break_ty: None,
+        });
+    }
+
+    pub fn pop_loop(&mut self) -> LoopScope {
+        self.loops.pop().unwrap()
+    }
+
+    /// Returns the target loop of `break` and `continue`, which is the innermost loop with the
+    /// label, or the innermost loop if `label` is `None`.
+    pub fn get_loop(&self, label: Option<&str>) -> Option<&LoopScope> {
+        self.loops.iter().rev().find(|v| v.has_label(label))
+    }
+
+    pub fn get_loop_mut(&mut self, label: Option<&str>) -> Option<&mut LoopScope> {
+        self.loops.iter_mut().rev().find(|v| v.has_label(label))
+    }
+
     pub fn _error(&mut self, e: impl Into<ResolveError>) {
         self.errors.push(e.into());
     }
 }
 
+/// A loop enclosing the expressions being resolved.
+#[derive(Debug)]
+struct LoopScope {
+    /// whether it is a `while` or a `for` loop, which can only break without a value.
+    is_while: bool,
+    label: Option<Located<String>>,
+    /// type of the values the loop is broken with, located at the first `break`.
+    /// `None` if there is no `break`, so the loop never ends.
+    break_ty: Option<Located<TypeId>>,
+}
+impl LoopScope {
+    fn has_label(&self, label: Option<&str>) -> bool {
+        match label {
+            Some(label) => self.label.as_ref().is_some_and(|v| v.0 == label),
+            None => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Flow {
     /// control flow continues to flow down the current path.
@@ -155,7 +222,7 @@ impl<T> FlowObj<T> {
         }
     }
 
-    pub fn _brk_none() -> Self {
+    pub fn brk_none() -> Self {
         Self {
             value: None,
             flow: Flow::Break,
@@ -0,0 +1,471 @@
+use crate::{
+    ast::{
+        location::{Locatable, Located},
+        nodes::{self as ast, pat::IntBound},
+    },
+    module::{
+        errors::{IdentResolveError, TypeResolveError, ValueResolveError},
+        nodes::pat::Pattern,
+    },
+    symbol::{
+        ty::{TypeKind, VariantKind},
+        SymbolTable, TypeId,
+    },
+};
+
+use super::{
+    ty::{resolve_prim_type, resolve_variant},
+    Resolve, ResolveContext,
+};
+
+/// Resolves the pattern against the type of the matched value, declaring its binding in the
+/// current block.
+impl Resolve<Located<TypeId>, Option<Pattern>> for ast::pat::Pattern {
+    fn resolve(&self, ctx: &mut ResolveContext, matched_ty: Located<TypeId>) -> Option<Pattern> {
+        let common_ty = ctx.table.common_type();
+        let pat_ty = match self {
+            ast::pat::Pattern::Wildcard(_) => return Some(Pattern::Wildcard),
+            ast::pat::Pattern::Ident(name) => {
+                let Some(var_id) = ctx.table.new_variable(name.0.clone(), ctx.get_block()) else {
+                    let first = ctx
+                        .table
+                        .get_variable_id_by_name(ctx.get_block(), &name.0)
+                        .expect("variable with the same name")
+                        .sym(ctx.table);
+                    ctx._error(IdentResolveError::VarNameAlreadyUsed {
+                        ident: name.0.clone(),
+                        first_origin: (first.ty, first.origin),
+                        dup_origin: (matched_ty.0, name.1),
+                    });
+                    return None;
+                };
+                let var_sym = var_id.sym_mut(ctx.table);
+                var_sym.ty = matched_ty.0;
+                var_sym.origin = name.1;
+                return Some(Pattern::Binding(var_id));
+            }
+            ast::pat::Pattern::Integer(v) => int_pattern_type(ctx, v, matched_ty.0),
+            ast::pat::Pattern::Range(v) => match v.start.as_ref().or(v.end.as_ref()) {
+                Some(bound) => int_pattern_type(ctx, bound, matched_ty.0),
+                None => common_ty.int,
+            },
+            ast::pat::Pattern::Bool(_) => common_ty.bool,
+            ast::pat::Pattern::Variant(v) => return resolve_variant_pattern(ctx, v, matched_ty),
+        };
+
+        // patterns of the underlying type also match values of its aliases.
+        let underlying_ty = ctx.table.underlying_type(matched_ty.0);
+        if !ctx.table.is_type_coercible(pat_ty, underlying_ty) {
+            ctx._error(TypeResolveError::PatternTypeMismatch {
+                expect_type: matched_ty,
+                actual_type: Located(pat_ty, self.get_location()),
+            });
+            return None;
+        }
+
+        Some(match self {
+            ast::pat::Pattern::Integer(v) => Pattern::Integer(resolve_int_bound(ctx, v, pat_ty)?),
+            ast::pat::Pattern::Bool(v) => Pattern::Bool(v.0),
+            ast::pat::Pattern::Range(v) => {
+                let (min, max) = ctx
+                    .table
+                    .get_int(pat_ty)
+                    .and_then(|v| v.int_range())
+                    .expect("integer type");
+                let start = match &v.start {
+                    Some(start) => resolve_int_bound(ctx, start, pat_ty)?,
+                    None => min,
+                };
+                let end = match &v.end {
+                    Some(end) if v.is_inclusive() => Some(resolve_int_bound(ctx, end, pat_ty)?),
+                    Some(end) => Some(resolve_int_bound(ctx, end, pat_ty)? - 1),
+                    None => Some(max),
+                };
+                match end {
+                    Some(end) if start <= end => Pattern::Range { start, end },
+                    _ => {
+                        ctx._error(ValueResolveError::EmptyRangePattern(v.get_location()));
+                        return None;
+                    }
+                }
+            }
+            ast::pat::Pattern::Wildcard(_)
+            | ast::pat::Pattern::Ident(_)
+            | ast::pat::Pattern::Variant(_) => unreachable!(),
+        })
+    }
+}
+
+/// Returns the type of an integer in a pattern, which is given by its suffix, or else is the matched
+/// type if it is an integer.
+fn int_pattern_type(ctx: &ResolveContext, bound: &Located<IntBound>, matched_ty: TypeId) -> TypeId {
+    match bound.0.suffix {
+        Some(suffix) => resolve_prim_type(ctx, suffix.into()),
+        None if ctx.table.get_int(matched_ty).is_some() => ctx.table.underlying_type(matched_ty),
+        None => ctx.table.common_type().int,
+    }
+}
+
+/// Returns the value of an integer in a pattern, or `None` if it is out of the range of the type.
+fn resolve_int_bound(
+    ctx: &mut ResolveContext,
+    bound: &Located<IntBound>,
+    ty: TypeId,
+) -> Option<i128> {
+    let int = ctx.table.get_int(ty).expect("integer type");
+    let (min, max) = int.int_range().expect("integer type");
+    if bound.0.value < min || max < bound.0.value {
+        let (is_signed, int_size) = int.int_layout().expect("integer type");
+        ctx._error(ValueResolveError::IntegerOutOfRange {
+            is_signed,
+            int_size,
+            val: Located(bound.0.value, bound.1),
+        });
+        return None;
+    }
+    Some(bound.0.value)
+}
+
+fn resolve_variant_pattern(
+    ctx: &mut ResolveContext,
+    pat: &ast::pat::VariantPattern,
+    matched_ty: Located<TypeId>,
+) -> Option<Pattern> {
+    let (enum_ty, tag, variant) = resolve_variant(ctx, &pat.ty_name, &pat.name)?;
+    let underlying_ty = ctx.table.underlying_type(matched_ty.0);
+    if !ctx.table.is_type_coercible(enum_ty, underlying_ty) {
+        ctx._error(TypeResolveError::PatternTypeMismatch {
+            expect_type: matched_ty,
+            actual_type: Located(enum_ty, pat.get_location()),
+        });
+        return None;
+    }
+
+    // every field is resolved even if one of them fails, to report the errors in all of them.
+    let mut fields = Vec::new();
+    let mut is_ok = true;
+    match (&pat.fields, &variant.kind) {
+        (ast::pat::VariantPatternFields::Unit, VariantKind::Unit) => {}
+        (ast::pat::VariantPatternFields::Tuple { pats, .. }, VariantKind::Tuple(field_types)) => {
+            if pats.items.len() != field_types.len() {
+                ctx._error(ValueResolveError::PatternFieldCountMismatch {
+                    loc: pat.get_location(),
+                    expect_count: field_types.len(),
+                    actual_count: pats.items.len(),
+                });
+                return None;
+            }
+            for (i, (sub_pat, field_ty)) in pats.items.iter().zip(field_types).enumerate() {
+                match sub_pat.resolve(ctx, Located(*field_ty, sub_pat.get_location())) {
+                    Some(sub_pat) => fields.push((i, sub_pat)),
+                    None => is_ok = false,
+                }
+            }
+        }
+        (
+            ast::pat::VariantPatternFields::Struct {
+                fields: field_pats, ..
+            },
+            VariantKind::Struct(struct_ty),
+        ) => {
+            let mut seen_fields: Vec<&Located<String>> = Vec::new();
+            for field_pat in &field_pats.items {
+                let name = &field_pat.field_name;
+                let Some(i) = struct_ty.fields.iter().position(|v| v.0 == name.0) else {
+                    ctx._error(IdentResolveError::UnknownField {
+                        struct_ty: enum_ty,
+                        field_name: name.clone(),
+                    });
+                    is_ok = false;
+                    continue;
+                };
+                if let Some(first) = seen_fields.iter().find(|v| v.0 == name.0) {
+                    ctx._error(IdentResolveError::FieldNameAlreadyUsed {
+                        ident: name.0.clone(),
+                        first_origin: first.1,
+                        dup_origin: name.1,
+                    });
+                    is_ok = false;
+                    continue;
+                }
+                seen_fields.push(name);
+
+                let field_ty = struct_ty.fields[i].1;
+                let sub_pat = match &field_pat.pat {
+                    Some((_, sub_pat)) => {
+                        sub_pat.resolve(ctx, Located(field_ty, sub_pat.get_location()))
+                    }
+                    // the shorthand binds the field to a variable of the same name.
+                    None => ast::pat::Pattern::Ident(name.clone())
+                        .resolve(ctx, Located(field_ty, name.1)),
+                };
+                match sub_pat {
+                    Some(sub_pat) => fields.push((i, sub_pat)),
+                    None => is_ok = false,
+                }
+            }
+        }
+        _ => {
+            ctx._error(ValueResolveError::VariantKindMismatch {
+                enum_ty,
+                variant: pat.name.clone(),
+            });
+            return None;
+        }
+    }
+
+    is_ok.then_some(Pattern::Variant {
+        enum_ty,
+        variant: tag,
+        fields,
+    })
+}
+
+/// Values of a matched type which are covered by the arms of a `match`, tracked as integers.
+/// `bool`s are covered as `0` for `false` and `1` for `true`, and enums by the tags of their
+/// variants.
+#[derive(Debug)]
+pub struct Coverage {
+    ty: TypeId,
+    /// every value of the matched type.
+    domain: (i128, i128),
+    /// sorted and disjoint inclusive ranges, none of which are adjacent.
+    ranges: Vec<(i128, i128)>,
+    /// patterns which were inserted, which also cover parts of the payloads of variants.
+    pats: Vec<Pattern>,
+    is_bool: bool,
+    /// pattern matching each variant of a matched enum, indexed by tag.
+    variants: Option<Vec<String>>,
+}
+impl Coverage {
+    /// Returns the coverage of no value of the type, or `None` if the type cannot be matched.
+    pub fn new(table: &SymbolTable, ty: TypeId) -> Option<Self> {
+        let common_ty = table.common_type();
+        let ty = table.underlying_type(ty);
+        let mut variants = None;
+        let domain = if let Some(range) = int_domain(table, ty) {
+            range
+        } else if let Some(TypeKind::Enum(enum_ty)) = &ty.sym(table).kind {
+            let enum_name = &ty.sym(table).name;
+            let pats: Vec<String> = enum_ty
+                .variants
+                .iter()
+                .map(|v| match &v.kind {
+                    VariantKind::Unit => format!("{}::{}", enum_name, v.name),
+                    VariantKind::Tuple(types) => format!(
+                        "{}::{}({})",
+                        enum_name,
+                        v.name,
+                        vec!["_"; types.len()].join(", ")
+                    ),
+                    VariantKind::Struct(struct_ty) => format!(
+                        "{}::{} {{ {} }}",
+                        enum_name,
+                        v.name,
+                        struct_ty
+                            .fields
+                            .iter()
+                            .map(|(name, _)| format!("{}: _", name))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                })
+                .collect();
+            let domain = (0, pats.len() as i128 - 1);
+            variants = Some(pats);
+            domain
+        } else {
+            return None;
+        };
+        Some(Self {
+            ty,
+            domain,
+            ranges: vec![],
+            pats: vec![],
+            is_bool: ty == common_ty.bool,
+            variants,
+        })
+    }
+
+    /// Returns the values matched by the pattern.
+    fn pattern_range(&self, pat: &Pattern) -> (i128, i128) {
+        pattern_range(pat, self.domain)
+    }
+
+    /// Returns whether every value matched by the pattern is already covered.
+    pub fn covers(&self, table: &SymbolTable, pat: &Pattern) -> bool {
+        let pats: Vec<_> = self.pats.iter().map(|v| vec![v]).collect();
+        !is_useful(table, &[self.ty], &pats, &[pat])
+    }
+
+    pub fn insert(&mut self, table: &SymbolTable, pat: &Pattern) {
+        self.pats.push(pat.clone());
+        // a variant is only covered once the patterns of its tag together match any payload.
+        if let Pattern::Variant {
+            enum_ty, variant, ..
+        } = pat
+        {
+            let any_payload = Pattern::Variant {
+                enum_ty: *enum_ty,
+                variant: *variant,
+                fields: vec![],
+            };
+            if !self.covers(table, &any_payload) {
+                return;
+            }
+        }
+        let (mut start, mut end) = self.pattern_range(pat);
+        self.ranges.retain(|(s, e)| {
+            // ranges which overlap or are adjacent are merged into one.
+            let is_disjoint = *e + 1 < start || end + 1 < *s;
+            if !is_disjoint {
+                start = start.min(*s);
+                end = end.max(*e);
+            }
+            is_disjoint
+        });
+        self.ranges.push((start, end));
+        self.ranges.sort();
+    }
+
+    /// Returns a pattern of the first values left uncovered, or `None` if every value is covered.
+    pub fn missing(&self) -> Option<String> {
+        let mut next = self.domain.0;
+        let mut missing = None;
+        for (s, e) in &self.ranges {
+            if *s > next {
+                missing = Some((next, s - 1));
+                break;
+            }
+            next = *e + 1;
+        }
+        let (start, end) = missing.or((next <= self.domain.1).then_some((next, self.domain.1)))?;
+
+        Some(if (start, end) == self.domain {
+            "`_`".to_owned()
+        } else if let Some(variants) = &self.variants {
+            variants[start as usize..=end as usize]
+                .iter()
+                .map(|v| format!("`{}`", v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else if self.is_bool {
+            format!("`{}`", start == 1)
+        } else if start == end {
+            format!("`{}`", start)
+        } else if start == self.domain.0 {
+            format!("`..={}`", end)
+        } else if end == self.domain.1 {
+            format!("`{}..`", start)
+        } else {
+            format!("`{}..={}`", start, end)
+        })
+    }
+}
+
+/// Returns every value of an integer or a `bool` type, or `None` for other types.
+fn int_domain(table: &SymbolTable, ty: TypeId) -> Option<(i128, i128)> {
+    match table.get_int(ty).and_then(|v| v.int_range()) {
+        Some(range) => Some(range),
+        None => (ty == table.common_type().bool).then_some((0, 1)),
+    }
+}
+
+/// Returns the values of an integer or a `bool` type matched by the pattern, or the tags of the
+/// variants of an enum.
+fn pattern_range(pat: &Pattern, domain: (i128, i128)) -> (i128, i128) {
+    match pat {
+        Pattern::Wildcard | Pattern::Binding(_) => domain,
+        Pattern::Integer(v) => (*v, *v),
+        Pattern::Bool(v) => (*v as i128, *v as i128),
+        Pattern::Range { start, end } => (*start, *end),
+        Pattern::Variant { variant, .. } => (*variant as i128, *variant as i128),
+    }
+}
+
+/// Returns the patterns of the payload of the variant matched by the pattern, with one for each
+/// field, or `None` if the pattern matches another variant.
+fn variant_fields(pat: &Pattern, tag: usize, field_count: usize) -> Option<Vec<&Pattern>> {
+    let mut fields = vec![&Pattern::Wildcard; field_count];
+    match pat {
+        Pattern::Variant {
+            variant,
+            fields: field_pats,
+            ..
+        } => {
+            if *variant != tag {
+                return None;
+            }
+            for (i, field_pat) in field_pats {
+                fields[*i] = field_pat;
+            }
+        }
+        Pattern::Wildcard | Pattern::Binding(_) => {}
+        _ => return None,
+    }
+    Some(fields)
+}
+
+/// Returns whether the row matches a value which none of the rows match. Each row has a pattern
+/// for a value of each of the types.
+fn is_useful(
+    table: &SymbolTable,
+    types: &[TypeId],
+    rows: &[Vec<&Pattern>],
+    row: &[&Pattern],
+) -> bool {
+    let Some((&ty, rest_types)) = types.split_first() else {
+        return rows.is_empty();
+    };
+    let ty = table.underlying_type(ty);
+    let (head, rest) = (row[0], &row[1..]);
+
+    if let Some(TypeKind::Enum(enum_ty)) = &ty.sym(table).kind {
+        // the payloads of each variant the row matches are checked on their own.
+        return enum_ty.variants.iter().enumerate().any(|(tag, variant)| {
+            let field_types = variant.field_types();
+            let Some(mut row) = variant_fields(head, tag, field_types.len()) else {
+                return false;
+            };
+            row.extend_from_slice(rest);
+            let rows: Vec<_> = rows
+                .iter()
+                .filter_map(|v| {
+                    let mut fields = variant_fields(v[0], tag, field_types.len())?;
+                    fields.extend_from_slice(&v[1..]);
+                    Some(fields)
+                })
+                .collect();
+            is_useful(table, &[&field_types, rest_types].concat(), &rows, &row)
+        });
+    }
+
+    let Some(domain) = int_domain(table, ty) else {
+        // values of other types are only matched by wildcards and bindings.
+        let rows: Vec<_> = rows.iter().map(|v| v[1..].to_vec()).collect();
+        return is_useful(table, rest_types, &rows, rest);
+    };
+    // the values are split at the bounds of the patterns, so that each pattern matches either
+    // every value of a part or none of them.
+    let (start, end) = pattern_range(head, domain);
+    let mut bounds = vec![start, end + 1];
+    for v in rows {
+        let (s, e) = pattern_range(v[0], domain);
+        bounds.extend([s, e + 1]);
+    }
+    bounds.retain(|v| (start..=end + 1).contains(v));
+    bounds.sort();
+    bounds.dedup();
+    bounds.windows(2).any(|part| {
+        let (s, e) = (part[0], part[1] - 1);
+        let rows: Vec<_> = rows
+            .iter()
+            .filter(|v| {
+                let (rs, re) = pattern_range(v[0], domain);
+                rs <= s && e <= re
+            })
+            .map(|v| v[1..].to_vec())
+            .collect();
+        is_useful(table, rest_types, &rows, rest)
+    })
+}
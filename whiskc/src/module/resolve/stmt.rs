@@ -1,9 +1,15 @@
 use crate::{
-    ast::nodes as ast,
-    module::nodes::stmt::{ExprStmt, LetStmt, Stmt},
+    ast::{
+        location::{Locatable, Located},
+        nodes as ast,
+    },
+    module::{
+        errors::IdentResolveError,
+        nodes::stmt::{ExprStmt, LetStmt, Stmt},
+    },
 };
 
-use super::{FlowObj, Resolve, ResolveContext};
+use super::{expr::check_assignment, FlowObj, Resolve, ResolveContext};
 
 impl<T> FlowObj<T> {
     pub fn map_stmt<F>(self, f: F) -> FlowObj<Stmt>
@@ -38,25 +44,44 @@ impl Resolve<(), FlowObj<ExprStmt>> for ast::stmt::ExprStmt {
 
 impl Resolve<(), FlowObj<LetStmt>> for ast::stmt::LetStmt {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> FlowObj<LetStmt> {
-        let FlowObj { value, flow } = self.value.resolve(ctx, ());
-        let Some(value) = value else {
-            return FlowObj::none(flow);
-        };
+        let anno_ty = self.ty.as_ref().map(|v| v.resolve(ctx, ()));
+        let FlowObj { value, flow } = self.value.resolve(ctx, anno_ty);
 
-        let mut var_ty = value.ty;
-        let anno_ty = self.ty.as_ref().and_then(|v| v.resolve(ctx, ()));
-        if let Some(anno_ty) = anno_ty {
-            if anno_ty != value.ty {
-                todo!("report error")
-            }
+        // the variable is still declared if its value fails to resolve, so that its later uses
+        // are checked against the error type instead of being reported as unknown.
+        let mut var_ty = value
+            .as_ref()
+            .map(|v| v.ty)
+            .unwrap_or(ctx.table.common_type().error);
+        if let (Some(ast_ty), Some(anno_ty)) = (&self.ty, anno_ty) {
+            check_assignment(
+                ctx,
+                Located(anno_ty, ast_ty.get_location()),
+                Located(var_ty, self.value.get_location()),
+            );
             var_ty = anno_ty;
         }
 
         let Some(var_id) = ctx.table.new_variable(self.name.0.clone(), ctx.get_block()) else {
-            todo!("report error");
+            let first = ctx
+                .table
+                .get_variable_id_by_name(ctx.get_block(), &self.name.0)
+                .expect("variable with the same name")
+                .sym(ctx.table);
+            ctx._error(IdentResolveError::VarNameAlreadyUsed {
+                ident: self.name.0.clone(),
+                first_origin: (first.ty, first.origin),
+                dup_origin: (var_ty, self.name.1),
+            });
+            return FlowObj::none(flow);
         };
-        var_id.sym_mut(ctx.table).ty = var_ty;
+        let var_sym = var_id.sym_mut(ctx.table);
+        var_sym.ty = var_ty;
+        var_sym.origin = self.name.1;
 
+        let Some(value) = value else {
+            return FlowObj::none(flow);
+        };
         FlowObj::new(LetStmt { var_id, value }, flow)
     }
 }
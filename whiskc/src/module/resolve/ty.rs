@@ -1,24 +1,43 @@
 use crate::{
-    ast::nodes as ast,
-    module::nodes::ty::TypeDecl,
+    ast::{
+        location::{Located, Span},
+        nodes as ast,
+    },
+    module::{
+        errors::{IdentResolveError, TypeResolveError, ValueResolveError},
+        nodes::ty::TypeDecl,
+    },
     symbol::{
-        ty::{StructType, TypeKind},
+        ty::{EnumType, StructType, TypeKind, Variant, VariantKind},
         TypeId,
     },
 };
+use wsk_vm::value::MAX_ARRAY_LEN;
 
 use super::{Record, Resolve, ResolveContext};
 
 impl Record<(), bool> for ast::ty::TypeDecl {
     fn record(&self, ctx: &mut ResolveContext, _: ()) -> bool {
-        if ctx.table.get_function_by_name(&self.name.0).is_some() {
-            todo!("report error");
-        }
-        if ctx.table.new_type(self.name.0.clone()).is_none() {
-            todo!("report error");
+        let first_origin = if let Some(func) = ctx.table.get_function_by_name(&self.name.0) {
+            Some(func.origin)
         } else {
-            true
+            ctx.table.get_type_by_name(&self.name.0).map(|ty| ty.origin)
+        };
+        if let Some(first_origin) = first_origin {
+            ctx._error(IdentResolveError::GlobalIdentAlreadyUsed {
+                ident: self.name.0.clone(),
+                first_origin,
+                dup_origin: self.name.1,
+            });
+            return false;
         }
+
+        let ty_id = ctx
+            .table
+            .new_type(self.name.0.clone())
+            .expect("unused type name");
+        ty_id.sym_mut(ctx.table).origin = self.name.1;
+        true
     }
 }
 
@@ -26,49 +45,71 @@ impl Resolve<(), Option<TypeDecl>> for ast::ty::TypeDecl {
     fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> Option<TypeDecl> {
         match &self.kind {
             ast::ty::TypeDeclKind::Type(ast_ty) => {
-                let underlying_ty_id = ast_ty.resolve(ctx, ())?;
+                let underlying_ty_id = ast_ty.resolve(ctx, ());
                 let ty_id = ctx
                     .table
                     .get_type_id(&self.name.0)
                     .expect("recorded type name");
                 ty_id.sym_mut(ctx.table).kind = Some(TypeKind::Ident(underlying_ty_id));
-
-                let con_func_id = ctx
-                    .table
-                    .new_function(self.name.0.clone())
-                    .expect("valid construct func");
-                let con_var_id = {
-                    let con_block = ctx.table.new_block(con_func_id);
-                    let con_var_id = ctx
-                        .table
-                        .new_variable("_0".to_owned(), con_block)
-                        .expect("temp con param");
-                    con_var_id.sym_mut(ctx.table).ty = underlying_ty_id;
-                    con_var_id
-                };
-                let con_sym = con_func_id.sym_mut(ctx.table);
-                con_sym.ret_ty = ty_id;
-                con_sym.params.push(con_var_id);
+                // the last alias of a cycle to be declared closes it, and is reported with the
+                // cycle broken at it, so that the other aliases of the cycle are not reported.
+                if underlying_ty_id == ty_id || ctx.table.is_alias_of(underlying_ty_id, ty_id) {
+                    let mut cycle = vec![self.name.0.clone()];
+                    let mut ty = underlying_ty_id;
+                    while ty != ty_id {
+                        cycle.push(ty.sym(ctx.table).name.clone());
+                        ty = ctx.table.aliased_type(ty).expect("alias in the cycle");
+                    }
+                    cycle.push(self.name.0.clone());
+                    ctx._error(TypeResolveError::CyclicAlias {
+                        alias: self.name.clone(),
+                        cycle,
+                    });
+                    ty_id.sym_mut(ctx.table).kind =
+                        Some(TypeKind::Ident(ctx.table.common_type().error));
+                }
             }
             ast::ty::TypeDeclKind::Struct(ast_struct) => {
-                let mut fields: Vec<(String, TypeId)> = Vec::new();
-                for ast_field in &ast_struct.fields.items {
-                    if fields.iter().any(|(name, _)| *name == ast_field.name.0) {
-                        todo!("report error");
+                let struct_ty = resolve_fields(ctx, &ast_struct.fields.items);
+                let sym = ctx
+                    .table
+                    .get_type_by_name_mut(&self.name.0)
+                    .expect("recorded type name");
+                sym.kind = Some(TypeKind::Struct(struct_ty));
+            }
+            ast::ty::TypeDeclKind::Enum(ast_enum) => {
+                let mut variants: Vec<Variant> = Vec::new();
+                let mut variant_origins: Vec<Span> = Vec::new();
+                for ast_variant in &ast_enum.variants.items {
+                    if let Some(i) = variants.iter().position(|v| v.name == ast_variant.name.0) {
+                        ctx._error(IdentResolveError::VariantNameAlreadyUsed {
+                            ident: ast_variant.name.0.clone(),
+                            first_origin: variant_origins[i],
+                            dup_origin: ast_variant.name.1,
+                        });
+                        continue;
                     }
-                    let ty_id = match ast_field.ty.resolve(ctx, ()) {
-                        Some(id) => id,
-                        // TODO: maybe it should be 'unknown' type.
-                        None => ctx.table.common_type().never,
+                    let kind = match &ast_variant.kind {
+                        ast::ty::VariantKind::Unit => VariantKind::Unit,
+                        ast::ty::VariantKind::Tuple { types, .. } => VariantKind::Tuple(
+                            types.items.iter().map(|v| v.resolve(ctx, ())).collect(),
+                        ),
+                        ast::ty::VariantKind::Struct { fields, .. } => {
+                            VariantKind::Struct(resolve_fields(ctx, &fields.items))
+                        }
                     };
-                    fields.push((ast_field.name.0.clone(), ty_id));
+                    variants.push(Variant {
+                        name: ast_variant.name.0.clone(),
+                        kind,
+                    });
+                    variant_origins.push(ast_variant.name.1);
                 }
 
                 let sym = ctx
                     .table
                     .get_type_by_name_mut(&self.name.0)
                     .expect("recorded type name");
-                sym.kind = Some(TypeKind::Struct(StructType { fields }));
+                sym.kind = Some(TypeKind::Enum(EnumType { variants }));
             }
         };
 
@@ -80,15 +121,106 @@ impl Resolve<(), Option<TypeDecl>> for ast::ty::TypeDecl {
     }
 }
 
-impl Resolve<(), Option<TypeId>> for ast::ty::Type {
-    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> Option<TypeId> {
+/// Resolves the variant named by a path such as `Shape::Circle`, returning the enum type along
+/// with the tag and the declaration of the variant.
+pub fn resolve_variant(
+    ctx: &mut ResolveContext,
+    ty_name: &Located<String>,
+    name: &Located<String>,
+) -> Option<(TypeId, usize, Variant)> {
+    let Some(ty_sym) = ctx.table.get_type_by_name(&ty_name.0) else {
+        ctx._error(TypeResolveError::UnknownTypeForIdent(ty_name.clone()));
+        return None;
+    };
+    let enum_ty = ty_sym.get_id();
+    // assumed the type declaration had already reported the error if it has no kind.
+    let ty_kind = ty_sym.kind.as_ref()?;
+    let TypeKind::Enum(enum_kind) = ty_kind else {
+        ctx._error(TypeResolveError::NonEnumTypeInPath(Located(
+            enum_ty, ty_name.1,
+        )));
+        return None;
+    };
+    let Some((tag, variant)) = enum_kind.get_variant(&name.0) else {
+        ctx._error(IdentResolveError::UnknownVariant {
+            enum_ty,
+            variant: name.clone(),
+        });
+        return None;
+    };
+    Some((enum_ty, tag, variant.clone()))
+}
+
+/// Resolves the fields of a struct, or of a variant with named fields.
+fn resolve_fields(ctx: &mut ResolveContext, ast_fields: &[ast::ty::Field]) -> StructType {
+    let mut fields: Vec<(String, TypeId)> = Vec::new();
+    let mut field_origins: Vec<Span> = Vec::new();
+    for ast_field in ast_fields {
+        if let Some(i) = fields
+            .iter()
+            .position(|(name, _)| *name == ast_field.name.0)
+        {
+            ctx._error(IdentResolveError::FieldNameAlreadyUsed {
+                ident: ast_field.name.0.clone(),
+                first_origin: field_origins[i],
+                dup_origin: ast_field.name.1,
+            });
+            continue;
+        }
+        let ty_id = ast_field.ty.resolve(ctx, ());
+        fields.push((ast_field.name.0.clone(), ty_id));
+        field_origins.push(ast_field.name.1);
+    }
+    StructType { fields }
+}
+
+pub fn resolve_prim_type(ctx: &ResolveContext, ty: ast::ty::PrimType) -> TypeId {
+    let common_ty = ctx.table.common_type();
+    match ty {
+        ast::ty::PrimType::Unit => common_ty.unit,
+        ast::ty::PrimType::Int => common_ty.int,
+        ast::ty::PrimType::I8 => common_ty.i8,
+        ast::ty::PrimType::I16 => common_ty.i16,
+        ast::ty::PrimType::I32 => common_ty.i32,
+        ast::ty::PrimType::I64 => common_ty.i64,
+        ast::ty::PrimType::U8 => common_ty.u8,
+        ast::ty::PrimType::U16 => common_ty.u16,
+        ast::ty::PrimType::U32 => common_ty.u32,
+        ast::ty::PrimType::U64 => common_ty.u64,
+        ast::ty::PrimType::Float => common_ty.float,
+        ast::ty::PrimType::Bool => common_ty.bool,
+        ast::ty::PrimType::Str => common_ty.str,
+    }
+}
+
+/// Reports the length of an array being past the most elements an array can have at runtime.
+pub fn check_array_len(ctx: &mut ResolveContext, len: Located<usize>) {
+    if len.0 > MAX_ARRAY_LEN {
+        ctx._error(ValueResolveError::ArrayTooLong {
+            len,
+            max: MAX_ARRAY_LEN,
+        });
+    }
+}
+
+/// Resolves the type, falling back to the error type if it cannot be resolved.
+impl Resolve<(), TypeId> for ast::ty::Type {
+    fn resolve(&self, ctx: &mut ResolveContext, _: ()) -> TypeId {
         match self {
-            ast::ty::Type::Primitive(v) => Some(match v.0 {
-                ast::ty::PrimType::Unit => ctx.table.common_type().unit,
-                ast::ty::PrimType::Int => ctx.table.common_type().int,
-                ast::ty::PrimType::Bool => ctx.table.common_type().bool,
+            ast::ty::Type::Primitive(v) => resolve_prim_type(ctx, v.0),
+            ast::ty::Type::Ident(v) => ctx.table.get_type_id(&v.0).unwrap_or_else(|| {
+                ctx._error(TypeResolveError::UnknownTypeForIdent(v.clone()));
+                ctx.table.common_type().error
             }),
-            ast::ty::Type::Ident(v) => ctx.table.get_type_id(&v.0),
+            ast::ty::Type::Array(v) => {
+                let elem_ty = v.elem_ty.resolve(ctx, ());
+                check_array_len(ctx, v.len);
+                ctx.table.get_array_type(elem_ty, v.len.0)
+            }
+            ast::ty::Type::Vec(v) => {
+                let elem_ty = v.elem_ty.resolve(ctx, ());
+                ctx.table.get_vec_type(elem_ty)
+            }
         }
     }
 }
@@ -1,13 +1,16 @@
 use crate::module::nodes::{
-    expr::{ExprKind, FuncIdentExpr, MemberAccessExpr, StructInitExpr},
+    expr::{
+        ArrayExpr, ArrayRepeatExpr, CastExpr, ConvertExpr, EnumInitExpr, ExprKind, ForExpr,
+        FuncIdentExpr, IndexExpr, MatchExpr, MemberAccessExpr, MethodCallExpr, StructInitExpr,
+    },
     ty::TypeDecl,
 };
 
 use super::super::{
     nodes::{
         expr::{
-            BinaryExpr, BlockExpr, CallExpr, Expr, IfExpr, LoopExpr, ReturnExpr, UnaryExpr,
-            VarIdentExpr,
+            BinaryExpr, BlockExpr, BreakExpr, CallExpr, ContinueExpr, Expr, IfExpr, LoopExpr,
+            ReturnExpr, UnaryExpr, VarIdentExpr, WhileExpr,
         },
         func::{ExternFunction, Function},
         item::Item,
@@ -33,10 +36,26 @@ pub trait Visit: Sized {
         /* terminal */
     }
 
+    fn visit_break_expr(&mut self, node: &BreakExpr) {
+        visit_break_expr(self, node);
+    }
+
     fn visit_call_expr(&mut self, node: &CallExpr) {
         visit_call_expr(self, node);
     }
 
+    fn visit_continue_expr(&mut self, _node: &ContinueExpr) {
+        /* terminal */
+    }
+
+    fn visit_convert_expr(&mut self, node: &ConvertExpr) {
+        visit_convert_expr(self, node);
+    }
+
+    fn visit_cast_expr(&mut self, node: &CastExpr) {
+        visit_cast_expr(self, node);
+    }
+
     fn visit_expr(&mut self, node: &Expr) {
         visit_expr(self, node);
     }
@@ -49,6 +68,10 @@ pub trait Visit: Sized {
         /* terminal */
     }
 
+    fn visit_float_expr(&mut self, _value: f64) {
+        /* terminal */
+    }
+
     fn visit_func(&mut self, node: &Function) {
         visit_func(self, node);
     }
@@ -65,7 +88,11 @@ pub trait Visit: Sized {
         visit_if_expr(self, node);
     }
 
-    fn visit_int_expr(&mut self, _value: i64) {
+    fn visit_int_expr(&mut self, _value: i128) {
+        /* terminal */
+    }
+
+    fn visit_str_expr(&mut self, _value: &str) {
         /* terminal */
     }
 
@@ -81,6 +108,10 @@ pub trait Visit: Sized {
         visit_loop_expr(self, node);
     }
 
+    fn visit_match_expr(&mut self, node: &MatchExpr) {
+        visit_match_expr(self, node);
+    }
+
     fn visit_member_access_expr(&mut self, node: &MemberAccessExpr) {
         visit_member_access_expr(self, node);
     }
@@ -97,6 +128,30 @@ pub trait Visit: Sized {
         visit_struct_init_expr(self, node)
     }
 
+    fn visit_enum_init_expr(&mut self, node: &EnumInitExpr) {
+        visit_enum_init_expr(self, node);
+    }
+
+    fn visit_array_expr(&mut self, node: &ArrayExpr) {
+        visit_array_expr(self, node);
+    }
+
+    fn visit_array_repeat_expr(&mut self, node: &ArrayRepeatExpr) {
+        visit_array_repeat_expr(self, node);
+    }
+
+    fn visit_index_expr(&mut self, node: &IndexExpr) {
+        visit_index_expr(self, node);
+    }
+
+    fn visit_method_call_expr(&mut self, node: &MethodCallExpr) {
+        visit_method_call_expr(self, node);
+    }
+
+    fn visit_for_expr(&mut self, node: &ForExpr) {
+        visit_for_expr(self, node);
+    }
+
     fn visit_type_decl(&mut self, _node: &TypeDecl) {
         /* terminal */
     }
@@ -108,6 +163,10 @@ pub trait Visit: Sized {
     fn visit_unit_expr(&mut self) {
         /* terminal */
     }
+
+    fn visit_while_expr(&mut self, node: &WhileExpr) {
+        visit_while_expr(self, node);
+    }
 }
 
 pub fn visit_module(v: &mut impl Visit, node: &Module) {
@@ -130,6 +189,12 @@ pub fn visit_block_expr(v: &mut impl Visit, node: &BlockExpr) {
     }
 }
 
+pub fn visit_break_expr(v: &mut impl Visit, node: &BreakExpr) {
+    if let Some(expr) = &node.expr {
+        v.visit_expr(expr);
+    }
+}
+
 pub fn visit_call_expr(v: &mut impl Visit, node: &CallExpr) {
     v.visit_expr(&node.caller);
     for arg in &node.args {
@@ -137,11 +202,21 @@ pub fn visit_call_expr(v: &mut impl Visit, node: &CallExpr) {
     }
 }
 
+pub fn visit_convert_expr(v: &mut impl Visit, node: &ConvertExpr) {
+    v.visit_expr(&node.expr);
+}
+
+pub fn visit_cast_expr(v: &mut impl Visit, node: &CastExpr) {
+    v.visit_expr(&node.expr);
+}
+
 pub fn visit_expr(v: &mut impl Visit, node: &Expr) {
     match &node.kind {
         ExprKind::Unit => v.visit_unit_expr(),
         ExprKind::Integer(value) => v.visit_int_expr(*value),
+        ExprKind::Float(value) => v.visit_float_expr(*value),
         ExprKind::Bool(value) => v.visit_bool_expr(*value),
+        ExprKind::Str(value) => v.visit_str_expr(value),
         ExprKind::VarIdent(node) => v.visit_var_ident_expr(node),
         ExprKind::FuncIdent(node) => v.visit_func_ident_expr(node),
         ExprKind::Unary(node) => v.visit_unary_expr(node),
@@ -151,8 +226,20 @@ pub fn visit_expr(v: &mut impl Visit, node: &Expr) {
         ExprKind::Return(node) => v.visit_return_expr(node),
         ExprKind::If(node) => v.visit_if_expr(node),
         ExprKind::Loop(node) => v.visit_loop_expr(node),
+        ExprKind::While(node) => v.visit_while_expr(node),
+        ExprKind::For(node) => v.visit_for_expr(node),
+        ExprKind::Break(node) => v.visit_break_expr(node),
+        ExprKind::Continue(node) => v.visit_continue_expr(node),
         ExprKind::StructInit(node) => v.visit_struct_init_expr(node),
+        ExprKind::EnumInit(node) => v.visit_enum_init_expr(node),
         ExprKind::MemberAccess(node) => v.visit_member_access_expr(node),
+        ExprKind::Convert(node) => v.visit_convert_expr(node),
+        ExprKind::Cast(node) => v.visit_cast_expr(node),
+        ExprKind::Match(node) => v.visit_match_expr(node),
+        ExprKind::Array(node) => v.visit_array_expr(node),
+        ExprKind::ArrayRepeat(node) => v.visit_array_repeat_expr(node),
+        ExprKind::Index(node) => v.visit_index_expr(node),
+        ExprKind::MethodCall(node) => v.visit_method_call_expr(node),
     };
 }
 
@@ -168,7 +255,7 @@ pub fn visit_if_expr(v: &mut impl Visit, node: &IfExpr) {
     v.visit_expr(&node.cond);
     v.visit_block_expr(&node.then);
     if let Some(else_) = &node.else_ {
-        v.visit_block_expr(else_);
+        v.visit_expr(else_);
     }
 }
 
@@ -188,6 +275,16 @@ pub fn visit_loop_expr(v: &mut impl Visit, node: &LoopExpr) {
     v.visit_block_expr(&node.body);
 }
 
+pub fn visit_match_expr(v: &mut impl Visit, node: &MatchExpr) {
+    v.visit_expr(&node.expr);
+    for arm in &node.arms {
+        if let Some(guard) = &arm.guard {
+            v.visit_expr(guard);
+        }
+        v.visit_expr(&arm.expr);
+    }
+}
+
 pub fn visit_member_access_expr(v: &mut impl Visit, node: &MemberAccessExpr) {
     v.visit_expr(&node.expr);
 }
@@ -211,6 +308,44 @@ pub fn visit_struct_init_expr(v: &mut impl Visit, node: &StructInitExpr) {
     }
 }
 
+pub fn visit_enum_init_expr(v: &mut impl Visit, node: &EnumInitExpr) {
+    for field in &node.fields {
+        v.visit_expr(&field.1);
+    }
+}
+
+pub fn visit_array_expr(v: &mut impl Visit, node: &ArrayExpr) {
+    for element in &node.elements {
+        v.visit_expr(element);
+    }
+}
+
+pub fn visit_array_repeat_expr(v: &mut impl Visit, node: &ArrayRepeatExpr) {
+    v.visit_expr(&node.expr);
+}
+
+pub fn visit_index_expr(v: &mut impl Visit, node: &IndexExpr) {
+    v.visit_expr(&node.expr);
+    v.visit_expr(&node.index);
+}
+
+pub fn visit_method_call_expr(v: &mut impl Visit, node: &MethodCallExpr) {
+    v.visit_expr(&node.receiver);
+    for arg in &node.args {
+        v.visit_expr(arg);
+    }
+}
+
 pub fn visit_unary_expr(v: &mut impl Visit, node: &UnaryExpr) {
     v.visit_expr(&node.expr);
 }
+
+pub fn visit_while_expr(v: &mut impl Visit, node: &WhileExpr) {
+    v.visit_expr(&node.cond);
+    v.visit_block_expr(&node.body);
+}
+
+pub fn visit_for_expr(v: &mut impl Visit, node: &ForExpr) {
+    v.visit_expr(&node.iter);
+    v.visit_block_expr(&node.body);
+}
@@ -1,13 +1,16 @@
 use crate::module::nodes::{
-    expr::{ExprKind, FuncIdentExpr, MemberAccessExpr, StructInitExpr},
+    expr::{
+        ArrayExpr, ArrayRepeatExpr, CastExpr, ConvertExpr, EnumInitExpr, ExprKind, ForExpr,
+        FuncIdentExpr, IndexExpr, MatchExpr, MemberAccessExpr, MethodCallExpr, StructInitExpr,
+    },
     ty::TypeDecl,
 };
 
 use super::super::{
     nodes::{
         expr::{
-            BinaryExpr, BlockExpr, CallExpr, Expr, IfExpr, LoopExpr, ReturnExpr, UnaryExpr,
-            VarIdentExpr,
+            BinaryExpr, BlockExpr, BreakExpr, CallExpr, ContinueExpr, Expr, IfExpr, LoopExpr,
+            ReturnExpr, UnaryExpr, VarIdentExpr, WhileExpr,
         },
         func::{ExternFunction, Function},
         item::Item,
@@ -33,10 +36,26 @@ pub trait VisitMut: Sized {
         /* terminal */
     }
 
+    fn visit_break_expr_mut(&mut self, node: &mut BreakExpr) {
+        visit_break_expr_mut(self, node);
+    }
+
     fn visit_call_expr_mut(&mut self, node: &mut CallExpr) {
         visit_call_expr_mut(self, node);
     }
 
+    fn visit_continue_expr_mut(&mut self, _node: &mut ContinueExpr) {
+        /* terminal */
+    }
+
+    fn visit_convert_expr_mut(&mut self, node: &mut ConvertExpr) {
+        visit_convert_expr_mut(self, node);
+    }
+
+    fn visit_cast_expr_mut(&mut self, node: &mut CastExpr) {
+        visit_cast_expr_mut(self, node);
+    }
+
     fn visit_expr_mut(&mut self, node: &mut Expr) {
         visit_expr_mut(self, node);
     }
@@ -49,6 +68,10 @@ pub trait VisitMut: Sized {
         /* terminal */
     }
 
+    fn visit_float_expr_mut(&mut self, _value: &mut f64) {
+        /* terminal */
+    }
+
     fn visit_func_mut(&mut self, node: &mut Function) {
         visit_func_mut(self, node);
     }
@@ -65,7 +88,11 @@ pub trait VisitMut: Sized {
         visit_if_expr_mut(self, node);
     }
 
-    fn visit_int_expr_mut(&mut self, _value: &mut i64) {
+    fn visit_int_expr_mut(&mut self, _value: &mut i128) {
+        /* terminal */
+    }
+
+    fn visit_str_expr_mut(&mut self, _value: &mut String) {
         /* terminal */
     }
 
@@ -81,6 +108,10 @@ pub trait VisitMut: Sized {
         visit_loop_expr_mut(self, node);
     }
 
+    fn visit_match_expr_mut(&mut self, node: &mut MatchExpr) {
+        visit_match_expr_mut(self, node);
+    }
+
     fn visit_member_access_expr_mut(&mut self, node: &mut MemberAccessExpr) {
         visit_member_access_expr_mut(self, node);
     }
@@ -97,6 +128,30 @@ pub trait VisitMut: Sized {
         visit_struct_init_expr_mut(self, node);
     }
 
+    fn visit_enum_init_expr_mut(&mut self, node: &mut EnumInitExpr) {
+        visit_enum_init_expr_mut(self, node);
+    }
+
+    fn visit_array_expr_mut(&mut self, node: &mut ArrayExpr) {
+        visit_array_expr_mut(self, node);
+    }
+
+    fn visit_array_repeat_expr_mut(&mut self, node: &mut ArrayRepeatExpr) {
+        visit_array_repeat_expr_mut(self, node);
+    }
+
+    fn visit_index_expr_mut(&mut self, node: &mut IndexExpr) {
+        visit_index_expr_mut(self, node);
+    }
+
+    fn visit_method_call_expr_mut(&mut self, node: &mut MethodCallExpr) {
+        visit_method_call_expr_mut(self, node);
+    }
+
+    fn visit_for_expr_mut(&mut self, node: &mut ForExpr) {
+        visit_for_expr_mut(self, node);
+    }
+
     fn visit_type_decl(&mut self, _node: &mut TypeDecl) {
         /* terminal */
     }
@@ -108,6 +163,10 @@ pub trait VisitMut: Sized {
     fn visit_unit_expr_mut(&mut self) {
         /* terminal */
     }
+
+    fn visit_while_expr_mut(&mut self, node: &mut WhileExpr) {
+        visit_while_expr_mut(self, node);
+    }
 }
 
 pub fn visit_module_mut(v: &mut impl VisitMut, node: &mut Module) {
@@ -130,6 +189,12 @@ pub fn visit_block_expr_mut(v: &mut impl VisitMut, node: &mut BlockExpr) {
     }
 }
 
+pub fn visit_break_expr_mut(v: &mut impl VisitMut, node: &mut BreakExpr) {
+    if let Some(expr) = &mut node.expr {
+        v.visit_expr_mut(expr);
+    }
+}
+
 pub fn visit_call_expr_mut(v: &mut impl VisitMut, node: &mut CallExpr) {
     v.visit_expr_mut(&mut node.caller);
     for arg in &mut node.args {
@@ -137,11 +202,21 @@ pub fn visit_call_expr_mut(v: &mut impl VisitMut, node: &mut CallExpr) {
     }
 }
 
+pub fn visit_convert_expr_mut(v: &mut impl VisitMut, node: &mut ConvertExpr) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+pub fn visit_cast_expr_mut(v: &mut impl VisitMut, node: &mut CastExpr) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
 pub fn visit_expr_mut(v: &mut impl VisitMut, node: &mut Expr) {
     match &mut node.kind {
         ExprKind::Unit => v.visit_unit_expr_mut(),
         ExprKind::Integer(value) => v.visit_int_expr_mut(value),
+        ExprKind::Float(value) => v.visit_float_expr_mut(value),
         ExprKind::Bool(value) => v.visit_bool_expr_mut(value),
+        ExprKind::Str(value) => v.visit_str_expr_mut(value),
         ExprKind::VarIdent(node) => v.visit_var_ident_expr_mut(node),
         ExprKind::FuncIdent(node) => v.visit_func_ident_expr_mut(node),
         ExprKind::Unary(node) => v.visit_unary_expr_mut(node),
@@ -151,8 +226,20 @@ pub fn visit_expr_mut(v: &mut impl VisitMut, node: &mut Expr) {
         ExprKind::Return(node) => v.visit_return_expr_mut(node),
         ExprKind::If(node) => v.visit_if_expr_mut(node),
         ExprKind::Loop(node) => v.visit_loop_expr_mut(node),
+        ExprKind::While(node) => v.visit_while_expr_mut(node),
+        ExprKind::For(node) => v.visit_for_expr_mut(node),
+        ExprKind::Break(node) => v.visit_break_expr_mut(node),
+        ExprKind::Continue(node) => v.visit_continue_expr_mut(node),
         ExprKind::StructInit(node) => v.visit_struct_init_expr_mut(node),
+        ExprKind::EnumInit(node) => v.visit_enum_init_expr_mut(node),
         ExprKind::MemberAccess(node) => v.visit_member_access_expr_mut(node),
+        ExprKind::Convert(node) => v.visit_convert_expr_mut(node),
+        ExprKind::Cast(node) => v.visit_cast_expr_mut(node),
+        ExprKind::Match(node) => v.visit_match_expr_mut(node),
+        ExprKind::Array(node) => v.visit_array_expr_mut(node),
+        ExprKind::ArrayRepeat(node) => v.visit_array_repeat_expr_mut(node),
+        ExprKind::Index(node) => v.visit_index_expr_mut(node),
+        ExprKind::MethodCall(node) => v.visit_method_call_expr_mut(node),
     };
 }
 
@@ -168,7 +255,7 @@ pub fn visit_if_expr_mut(v: &mut impl VisitMut, node: &mut IfExpr) {
     v.visit_expr_mut(&mut node.cond);
     v.visit_block_expr_mut(&mut node.then);
     if let Some(else_) = &mut node.else_ {
-        v.visit_block_expr_mut(else_);
+        v.visit_expr_mut(else_);
     }
 }
 
@@ -188,6 +275,16 @@ pub fn visit_loop_expr_mut(v: &mut impl VisitMut, node: &mut LoopExpr) {
     v.visit_block_expr_mut(&mut node.body);
 }
 
+pub fn visit_match_expr_mut(v: &mut impl VisitMut, node: &mut MatchExpr) {
+    v.visit_expr_mut(&mut node.expr);
+    for arm in &mut node.arms {
+        if let Some(guard) = &mut arm.guard {
+            v.visit_expr_mut(guard);
+        }
+        v.visit_expr_mut(&mut arm.expr);
+    }
+}
+
 pub fn visit_member_access_expr_mut(v: &mut impl VisitMut, node: &mut MemberAccessExpr) {
     v.visit_expr_mut(&mut node.expr);
 }
@@ -211,6 +308,44 @@ pub fn visit_struct_init_expr_mut(v: &mut impl VisitMut, node: &mut StructInitEx
     }
 }
 
+pub fn visit_enum_init_expr_mut(v: &mut impl VisitMut, node: &mut EnumInitExpr) {
+    for field in &mut node.fields {
+        v.visit_expr_mut(&mut field.1);
+    }
+}
+
+pub fn visit_array_expr_mut(v: &mut impl VisitMut, node: &mut ArrayExpr) {
+    for element in &mut node.elements {
+        v.visit_expr_mut(element);
+    }
+}
+
+pub fn visit_array_repeat_expr_mut(v: &mut impl VisitMut, node: &mut ArrayRepeatExpr) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+pub fn visit_index_expr_mut(v: &mut impl VisitMut, node: &mut IndexExpr) {
+    v.visit_expr_mut(&mut node.expr);
+    v.visit_expr_mut(&mut node.index);
+}
+
+pub fn visit_method_call_expr_mut(v: &mut impl VisitMut, node: &mut MethodCallExpr) {
+    v.visit_expr_mut(&mut node.receiver);
+    for arg in &mut node.args {
+        v.visit_expr_mut(arg);
+    }
+}
+
 pub fn visit_unary_expr_mut(v: &mut impl VisitMut, node: &mut UnaryExpr) {
     v.visit_expr_mut(&mut node.expr);
 }
+
+pub fn visit_while_expr_mut(v: &mut impl VisitMut, node: &mut WhileExpr) {
+    v.visit_expr_mut(&mut node.cond);
+    v.visit_block_expr_mut(&mut node.body);
+}
+
+pub fn visit_for_expr_mut(v: &mut impl VisitMut, node: &mut ForExpr) {
+    v.visit_expr_mut(&mut node.iter);
+    v.visit_block_expr_mut(&mut node.body);
+}
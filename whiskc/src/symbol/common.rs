@@ -1,24 +1,94 @@
 use super::{
     ty::{Primitive, TypeKind},
-    SymbolTable, TypeId,
+    FuncId, SymbolTable, TypeId,
 };
 
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Common {
     pub ty: CommonType,
+    pub func: CommonFunc,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
 pub struct CommonType {
+    pub error: TypeId,
     pub never: TypeId,
     pub unit: TypeId,
     pub int: TypeId,
+    pub i8: TypeId,
+    pub i16: TypeId,
+    pub i32: TypeId,
+    pub i64: TypeId,
+    pub u8: TypeId,
+    pub u16: TypeId,
+    pub u32: TypeId,
+    pub u64: TypeId,
+    pub float: TypeId,
     pub bool: TypeId,
+    pub str: TypeId,
+}
+impl CommonType {
+    /// Returns `int` followed by the integer types of a fixed size.
+    pub fn int_types(&self) -> [TypeId; 9] {
+        [
+            self.int, self.i8, self.i16, self.i32, self.i64, self.u8, self.u16, self.u32, self.u64,
+        ]
+    }
+}
+
+/// Built-in functions of the prelude, implemented by the runtime.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct CommonFunc {
+    /// `print(value)`, where the value is a number, `bool` or `str`.
+    pub print: FuncId,
+    /// same as `print`, followed by a newline.
+    pub println: FuncId,
+    /// `read_line() str`, reading a line from the standard input without its line break.
+    pub read_line: FuncId,
+    /// `exit(status int) never`, terminating the program with the status.
+    pub exit: FuncId,
+}
+impl CommonFunc {
+    /// Returns whether the function accepts a value of any printable type.
+    pub fn is_print(&self, fid: FuncId) -> bool {
+        fid == self.print || fid == self.println
+    }
 }
 
 pub fn inject_symbol_table(table: &mut SymbolTable) -> Common {
     let common_ty = inject_primitive_types(table);
-    Common { ty: common_ty }
+    let common_func = inject_builtin_funcs(table, &common_ty);
+    Common {
+        ty: common_ty,
+        func: common_func,
+    }
+}
+
+fn inject_builtin_funcs(table: &mut SymbolTable, common_ty: &CommonType) -> CommonFunc {
+    let mut f = |name: &str, params: &[(&str, TypeId)], ret_ty: TypeId| {
+        let fid = table.new_function(name.to_owned()).unwrap();
+        let block = table.new_block(fid);
+        let params = params
+            .iter()
+            .map(|(name, ty)| {
+                let vid = table.new_variable(name.to_string(), block).unwrap();
+                vid.sym_mut(table).ty = *ty;
+                vid
+            })
+            .collect();
+        let sym = fid.sym_mut(table);
+        sym.params = params;
+        sym.ret_ty = ret_ty;
+        sym.entry_block = block;
+        fid
+    };
+    CommonFunc {
+        // the parameter type of print functions is checked by the resolver of calls instead.
+        print: f("print", &[("value", common_ty.str)], common_ty.unit),
+        println: f("println", &[("value", common_ty.str)], common_ty.unit),
+        read_line: f("read_line", &[], common_ty.str),
+        exit: f("exit", &[("status", common_ty.int)], common_ty.never),
+    }
 }
 
 fn inject_primitive_types(table: &mut SymbolTable) -> CommonType {
@@ -29,9 +99,20 @@ fn inject_primitive_types(table: &mut SymbolTable) -> CommonType {
         id
     };
     CommonType {
+        error: f(Primitive::Error),
         never: f(Primitive::Never),
         unit: f(Primitive::Unit),
         int: f(Primitive::Int),
+        i8: f(Primitive::I8),
+        i16: f(Primitive::I16),
+        i32: f(Primitive::I32),
+        i64: f(Primitive::I64),
+        u8: f(Primitive::U8),
+        u16: f(Primitive::U16),
+        u32: f(Primitive::U32),
+        u64: f(Primitive::U64),
+        float: f(Primitive::Float),
         bool: f(Primitive::Bool),
+        str: f(Primitive::Str),
     }
 }
@@ -6,6 +6,8 @@ pub mod ty;
 pub use symbol_id::*;
 pub use symbol_table::SymbolTable;
 
+use crate::ast::location::Span;
+
 use self::ty::TypeKind;
 
 #[derive(Debug, Clone)]
@@ -13,6 +15,8 @@ pub struct TypeSymbol {
     id: TypeId,
     pub name: String,
     pub kind: Option<TypeKind>,
+    /// where the type is declared, default for built-in types.
+    pub origin: Span,
 }
 impl TypeSymbol {
     pub fn get_id(&self) -> TypeId {
@@ -31,6 +35,7 @@ pub struct FuncSymbol {
     pub params: Vec<VarId>,
     pub ret_ty: TypeId,
     pub entry_block: BlockId,
+    pub origin: Span,
 }
 impl FuncSymbol {
     pub fn get_id(&self) -> FuncId {
@@ -56,6 +61,7 @@ pub struct VarSymbol {
     pub block: BlockId,
     pub name: String,
     pub ty: TypeId,
+    pub origin: Span,
 }
 impl VarSymbol {
     pub fn get_id(&self) -> VarId {
@@ -4,7 +4,8 @@ use std::collections::HashMap;
 use crate::interner::StringInterner;
 
 use super::{
-    common::{inject_symbol_table, Common, CommonType},
+    common::{inject_symbol_table, Common, CommonFunc, CommonType},
+    ty::{ArrayType, CastKind, Primitive, TypeKind, VecType},
     BlockId, BlockSymbol, FuncId, FuncSymbol, TypeId, TypeSymbol, VarId, VarSymbol,
 };
 
@@ -50,11 +51,17 @@ impl SymbolTable {
                 id: tyid,
                 name,
                 kind: None,
+                origin: Default::default(),
             },
         );
         Some(tyid)
     }
 
+    /// Returns the type of the id, or `None` if the id is not in the table.
+    pub fn get_type(&self, ty: TypeId) -> Option<&TypeSymbol> {
+        self.types.get(&ty)
+    }
+
     pub fn get_type_by_name(&self, name: &str) -> Option<&TypeSymbol> {
         self.get_type_id(name).map(|v| v.sym(self))
     }
@@ -72,6 +79,65 @@ impl SymbolTable {
         }
     }
 
+    /// Returns the array type of the element type and the length, adding it to the table if it
+    /// is not there yet. An array of the error type is the error type itself.
+    pub fn get_array_type(&mut self, elem_ty: TypeId, len: usize) -> TypeId {
+        if elem_ty == self.common_type().error {
+            return elem_ty;
+        }
+        let name = format!("[{}; {}]", elem_ty.sym(self).name, len);
+        if let Some(ty) = self.get_type_id(&name) {
+            return ty;
+        }
+        let ty = self
+            .new_type(name)
+            .expect("array type should not be in the table");
+        ty.sym_mut(self).kind = Some(TypeKind::Array(ArrayType { elem_ty, len }));
+        ty
+    }
+
+    /// Returns the array type of the type, looking through aliases.
+    pub fn get_array(&self, ty: TypeId) -> Option<&ArrayType> {
+        match &self.types.get(&self.underlying_type(ty))?.kind {
+            Some(TypeKind::Array(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the vector type of the element type, adding it to the table if it is not there
+    /// yet. A vector of the error type is the error type itself.
+    pub fn get_vec_type(&mut self, elem_ty: TypeId) -> TypeId {
+        if elem_ty == self.common_type().error {
+            return elem_ty;
+        }
+        let name = format!("[{}]", elem_ty.sym(self).name);
+        if let Some(ty) = self.get_type_id(&name) {
+            return ty;
+        }
+        let ty = self
+            .new_type(name)
+            .expect("vector type should not be in the table");
+        ty.sym_mut(self).kind = Some(TypeKind::Vec(VecType { elem_ty }));
+        ty
+    }
+
+    /// Returns the vector type of the type, looking through aliases.
+    pub fn get_vec(&self, ty: TypeId) -> Option<&VecType> {
+        match &self.types.get(&self.underlying_type(ty))?.kind {
+            Some(TypeKind::Vec(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the primitive of the integer type, or of the integer type the alias is declared
+    /// with.
+    pub fn get_int(&self, ty: TypeId) -> Option<Primitive> {
+        match &self.types.get(&self.underlying_type(ty))?.kind {
+            Some(TypeKind::Primitive(v)) if v.int_layout().is_some() => Some(*v),
+            _ => None,
+        }
+    }
+
     /// Add the function to the function symbol table, returning its id if there is no name collision.
     /// None is returned if there is a function with the same name presented in the table.
     pub fn new_function(&mut self, name: String) -> Option<FuncId> {
@@ -87,6 +153,7 @@ impl SymbolTable {
                 params: vec![],
                 ret_ty: Default::default(),
                 entry_block: Default::default(),
+                origin: Default::default(),
             },
         );
         Some(fid)
@@ -143,6 +210,7 @@ impl SymbolTable {
                 block: parent_block,
                 name,
                 ty: Default::default(),
+                origin: Default::default(),
             },
         );
         Some(vid)
@@ -188,11 +256,126 @@ impl SymbolTable {
         &self.common().ty
     }
 
+    pub fn common_func(&self) -> &CommonFunc {
+        &self.common().func
+    }
+
+    /// Returns whether values of the type can be printed by the built-in print functions.
+    /// Aliases are printed as their underlying type.
+    pub fn is_type_printable(&self, ty: TypeId) -> bool {
+        let ty = self.underlying_type(ty);
+        let common_ty = self.common_type();
+        [
+            common_ty.error,
+            common_ty.never,
+            common_ty.float,
+            common_ty.bool,
+            common_ty.str,
+        ]
+        .contains(&ty)
+            || common_ty.int_types().contains(&ty)
+    }
+
+    /// Returns whether a value of type `from` can be used where `to` is expected.
+    /// The error type is coercible from and to any type, so that it does not produce follow-on
+    /// errors. Arrays of the same length are coercible if their elements are, so that the empty
+    /// array `[]` can be used as an array of any type. Likewise, a vector of `never`, which has no
+    /// elements, is coercible to a vector of any type.
     pub fn is_type_coercible(&self, from: TypeId, to: TypeId) -> bool {
-        if from == self.common_type().never {
-            true
-        } else {
-            from == to
+        let common_ty = self.common_type();
+        if from == common_ty.never || from == common_ty.error || to == common_ty.error {
+            return true;
+        }
+        if from == to {
+            return true;
+        }
+        match (self.array_kind(from), self.array_kind(to)) {
+            (Some(from), Some(to)) => {
+                from.len == to.len && self.is_type_coercible(from.elem_ty, to.elem_ty)
+            }
+            _ => match (self.vec_kind(from), self.vec_kind(to)) {
+                (Some(from), Some(_)) => from.elem_ty == common_ty.never,
+                _ => false,
+            },
+        }
+    }
+
+    fn vec_kind(&self, ty: TypeId) -> Option<&VecType> {
+        match &self.types.get(&ty)?.kind {
+            Some(TypeKind::Vec(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn array_kind(&self, ty: TypeId) -> Option<&ArrayType> {
+        match &self.types.get(&ty)?.kind {
+            Some(TypeKind::Array(v)) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the type the alias is declared with, looking through aliases of aliases.
+    /// Other types are returned as they are, and cyclic aliases resolve to the error type.
+    pub fn underlying_type(&self, ty: TypeId) -> TypeId {
+        let mut ty = ty;
+        for _ in 0..self.types.len() {
+            match self.aliased_type(ty) {
+                Some(v) => ty = v,
+                None => return ty,
+            }
+        }
+        self.common_type().error
+    }
+
+    /// Returns whether a value of type `from` can be explicitly converted to `to`, which is the case
+    /// for coercible types and between an alias and any type in its chain of aliases.
+    pub fn is_type_convertible(&self, from: TypeId, to: TypeId) -> bool {
+        self.is_type_coercible(from, to) || self.is_alias_of(from, to) || self.is_alias_of(to, from)
+    }
+
+    /// Returns the conversion of a cast with `as` from `from` to `to`, or `None` if the cast is
+    /// not allowed. Every explicit conversion is also a cast, and casts look through aliases, so
+    /// that aliases of the same type can be cast to each other.
+    pub fn get_cast_kind(&self, from: TypeId, to: TypeId) -> Option<CastKind> {
+        if self.is_type_convertible(from, to) {
+            return Some(CastKind::Identity);
+        }
+        let (from, to) = (self.underlying_type(from), self.underlying_type(to));
+        if from == to {
+            return Some(CastKind::Identity);
+        }
+        let common_ty = self.common_type();
+        let mut casts = Vec::new();
+        for int_ty in common_ty.int_types() {
+            casts.push((int_ty, common_ty.bool, CastKind::IntToBool));
+            casts.push((common_ty.bool, int_ty, CastKind::BoolToInt));
+            casts.push((int_ty, common_ty.float, CastKind::IntToFloat));
+            casts.push((common_ty.float, int_ty, CastKind::FloatToInt));
+            for other_ty in common_ty.int_types() {
+                casts.push((int_ty, other_ty, CastKind::IntToInt));
+            }
+        }
+        casts.iter().find(|v| v.0 == from && v.1 == to).map(|v| v.2)
+    }
+
+    /// Returns whether `target` is in the chain of aliases `alias` is declared with.
+    pub fn is_alias_of(&self, alias: TypeId, target: TypeId) -> bool {
+        let mut ty = alias;
+        for _ in 0..self.types.len() {
+            match self.aliased_type(ty) {
+                Some(v) if v == target => return true,
+                Some(v) => ty = v,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Returns the type the alias is declared with, or `None` if the type is not an alias.
+    pub fn aliased_type(&self, ty: TypeId) -> Option<TypeId> {
+        match self.types.get(&ty)?.kind {
+            Some(TypeKind::Ident(v)) => Some(v),
+            _ => None,
         }
     }
 
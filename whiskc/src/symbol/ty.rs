@@ -7,6 +7,13 @@ use super::{SymbolTable, TypeId};
 pub enum TypeKind {
     Primitive(Primitive),
     Struct(StructType),
+    Enum(EnumType),
+    Array(ArrayType),
+    Vec(VecType),
+    /// an alias declared with `type Alias = T;`. Aliases are nominal: an alias and `T` are not
+    /// coercible to each other, and are converted explicitly with `Alias(v)` and `T(v)`, which has
+    /// no runtime cost. The operators of the underlying type carry over to operands of the same
+    /// alias, and aliases of printable types can be printed.
     Ident(TypeId),
 }
 impl TypeKind {
@@ -14,26 +21,104 @@ impl TypeKind {
         match self {
             TypeKind::Primitive(v) => Some(v.get_size()),
             TypeKind::Struct(v) => v.get_size(table),
+            TypeKind::Enum(v) => Some(v.get_size()),
+            TypeKind::Array(v) => v.get_size(table),
+            TypeKind::Vec(v) => Some(v.get_size()),
             TypeKind::Ident(v) => v.sym(table).get_size(table),
         }
     }
 }
 
+/// Conversion performed by a cast with `as`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CastKind {
+    /// the value is left as it is, such as between an alias and its underlying type.
+    Identity,
+    /// any integer other than 0 is `true`.
+    IntToBool,
+    /// `true` is 1 and `false` is 0.
+    BoolToInt,
+    /// between integer types, truncating the values which do not fit into the target type.
+    IntToInt,
+    /// the nearest `float` to the integer.
+    IntToFloat,
+    /// rounded toward zero, saturating at the bounds of the integer type. NaN is 0.
+    FloatToInt,
+}
+impl fmt::Display for CastKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Identity => "identity",
+                Self::IntToBool => "int_to_bool",
+                Self::BoolToInt => "bool_to_int",
+                Self::IntToInt => "int_to_int",
+                Self::IntToFloat => "int_to_float",
+                Self::FloatToInt => "float_to_int",
+            }
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Primitive {
+    /// the type of an expression or declaration that failed to resolve. It is compatible with
+    /// every type so that a single mistake is only reported once.
+    Error,
     Never,
     Unit,
     Bool,
     Int,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    /// 64-bit IEEE 754 floating-point number.
+    Float,
+    Str,
 }
 impl Primitive {
     pub fn get_size(&self) -> usize {
         match self {
-            Self::Never | Self::Unit => 0,
+            Self::Error | Self::Never | Self::Unit => 0,
             Self::Bool => size_of::<bool>(),
-            Self::Int => size_of::<i64>(),
+            Self::Float => size_of::<f64>(),
+            Self::Str => size_of::<usize>(),
+            _ => self.int_layout().expect("integer type").1 as usize / 8,
         }
     }
+
+    /// Returns the signedness and the size in bits of integer types. `int` is a 64-bit signed
+    /// integer, like `i64`.
+    pub fn int_layout(&self) -> Option<(bool, u16)> {
+        Some(match self {
+            Self::Int | Self::I64 => (true, 64),
+            Self::I8 => (true, 8),
+            Self::I16 => (true, 16),
+            Self::I32 => (true, 32),
+            Self::U8 => (false, 8),
+            Self::U16 => (false, 16),
+            Self::U32 => (false, 32),
+            Self::U64 => (false, 64),
+            _ => return None,
+        })
+    }
+
+    /// Returns the smallest and the largest values of integer types.
+    pub fn int_range(&self) -> Option<(i128, i128)> {
+        let (is_signed, size) = self.int_layout()?;
+        Some(if is_signed {
+            (-(1 << (size - 1)), (1 << (size - 1)) - 1)
+        } else {
+            (0, (1 << size) - 1)
+        })
+    }
 }
 impl fmt::Display for Primitive {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -41,10 +126,21 @@ impl fmt::Display for Primitive {
             f,
             "{}",
             match self {
+                Self::Error => "{error}",
                 Self::Never => "never",
                 Self::Unit => "()",
                 Self::Int => "int",
+                Self::I8 => "i8",
+                Self::I16 => "i16",
+                Self::I32 => "i32",
+                Self::I64 => "i64",
+                Self::U8 => "u8",
+                Self::U16 => "u16",
+                Self::U32 => "u32",
+                Self::U64 => "u64",
+                Self::Float => "float",
                 Self::Bool => "bool",
+                Self::Str => "str",
             }
         )
     }
@@ -67,3 +163,75 @@ impl StructType {
         Some(sz)
     }
 }
+
+/// Fixed-size array type, such as `[int; 4]`. Array types are created on demand and are
+/// identified by their name, so `[int; 4]` refers to the same type wherever it is written.
+#[derive(Debug, Clone)]
+pub struct ArrayType {
+    pub elem_ty: TypeId,
+    pub len: usize,
+}
+impl ArrayType {
+    pub fn get_size(&self, table: &SymbolTable) -> Option<usize> {
+        Some(self.elem_ty.sym(table).get_size(table)? * self.len)
+    }
+}
+
+/// Growable vector type, such as `[int]`. Like arrays, vector types are created on demand and
+/// are identified by their name.
+#[derive(Debug, Clone)]
+pub struct VecType {
+    pub elem_ty: TypeId,
+}
+impl VecType {
+    /// Returns the size of the reference to the elements, which are shared by the copies of a
+    /// vector.
+    pub fn get_size(&self) -> usize {
+        size_of::<usize>()
+    }
+}
+
+/// Sum type whose values are one of its variants, each of which may carry a payload.
+#[derive(Debug, Clone)]
+pub struct EnumType {
+    pub variants: Vec<Variant>,
+}
+impl EnumType {
+    /// Returns the index of the variant, which is its tag at runtime, along with the variant.
+    pub fn get_variant(&self, name: &str) -> Option<(usize, &Variant)> {
+        self.variants
+            .iter()
+            .enumerate()
+            .find(|(_, v)| v.name == name)
+    }
+
+    /// Returns the size of the tag and the reference to the payload. The payload is stored
+    /// separately, so that an enum can have itself in its payload.
+    pub fn get_size(&self) -> usize {
+        size_of::<usize>() * 2
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub name: String,
+    pub kind: VariantKind,
+}
+impl Variant {
+    /// Returns the types of the payload in the order they are declared.
+    pub fn field_types(&self) -> Vec<TypeId> {
+        match &self.kind {
+            VariantKind::Unit => vec![],
+            VariantKind::Tuple(v) => v.clone(),
+            VariantKind::Struct(v) => v.fields.iter().map(|v| v.1).collect(),
+        }
+    }
+}
+
+/// Payload of a variant, such as `Empty`, `Circle(int)` or `Rect { w int, h int }`.
+#[derive(Debug, Clone)]
+pub enum VariantKind {
+    Unit,
+    Tuple(Vec<TypeId>),
+    Struct(StructType),
+}
@@ -0,0 +1,49 @@
+// every test crate includes the helpers, but not every one of them uses all of them.
+#![allow(dead_code)]
+
+use std::{env, fs, process};
+
+use whiskc::{ast, codegen::codegen_wsk_vm, diagnostics::Diagnostic, module};
+use wsk_vm::{builtins::register_builtins, program::Program, verify::verify, VM};
+
+/// Parses the source, returning the tree or the codes of the reported errors.
+pub fn parse(name: &str, source: &str) -> Result<ast::AST, Vec<&'static str>> {
+    let path = env::temp_dir().join(format!("whiskc-test-{}-{}.wsk", process::id(), name));
+    fs::write(&path, source).unwrap();
+    let ast = ast::parse(&path);
+    fs::remove_file(&path).unwrap();
+
+    ast.map_err(|errors| {
+        errors
+            .iter()
+            .map(|e| Diagnostic::from(e).code)
+            .collect::<Vec<_>>()
+    })
+}
+
+/// Compiles a parsed tree, returning the program or the codes of the reported errors.
+pub fn compile_ast(name: &str, ast: &ast::AST) -> Result<Program, Vec<&'static str>> {
+    let module = module::resolve(ast, name.to_owned()).map_err(|(errors, table)| {
+        errors
+            .iter()
+            .map(|e| Diagnostic::from_resolve_error(e, &table).code)
+            .collect::<Vec<_>>()
+    })?;
+    codegen_wsk_vm(&module).map_err(|e| vec![Diagnostic::from(&e).code])
+}
+
+/// Compiles the source, returning the program or the codes of the reported errors.
+pub fn compile(name: &str, source: &str) -> Result<Program, Vec<&'static str>> {
+    compile_ast(name, &parse(name, source)?)
+}
+
+/// Compiles, verifies and runs the source, returning the status returned by its `main`.
+pub fn run(name: &str, source: &str) -> i64 {
+    let program = compile(name, source).unwrap();
+    verify(&program).unwrap();
+
+    let mut vm = VM::default();
+    register_builtins(&mut vm);
+    vm.execute(program).unwrap();
+    vm.exit_status()
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::{compile, run};
+
+#[test]
+fn unknown_label() {
+    let source = "
+func main() int {
+    'outer: loop {
+        while true {
+            if false {
+                break 'inner;
+            }
+        }
+        continue 'missing;
+    }
+    0
+}
+";
+    assert_eq!(
+        compile("unknown_label", source).unwrap_err(),
+        ["E0308", "E0308"]
+    );
+}
+
+#[test]
+fn label_outside_of_any_loop() {
+    let source = "
+func main() int {
+    break 'outer;
+}
+";
+    assert_eq!(
+        compile("label_outside_loop", source).unwrap_err(),
+        ["E0502"]
+    );
+}
+
+#[test]
+fn shadowed_label() {
+    let source = "
+func main() int {
+    'a: loop {
+        'b: while true {
+            'a: loop {
+                break 'a;
+            }
+            break 'b;
+        }
+        break 'a;
+    }
+    0
+}
+";
+    assert_eq!(compile("shadowed_label", source).unwrap_err(), ["E0309"]);
+}
+
+#[test]
+fn label_reused_by_sibling_loops() {
+    let source = "
+func main() int {
+    let n = 0;
+    'a: loop {
+        n = n + 1;
+        break 'a;
+    }
+    'a: loop {
+        n = n + 10;
+        break 'a;
+    }
+    n
+}
+";
+    assert_eq!(run("sibling_labels", source), 11);
+}
+
+#[test]
+fn continue_label_through_nested_for() {
+    let source = "
+func main() int {
+    let sum = 0;
+    'rows: for i in [0, 1, 2, 3] {
+        for j in [0, 1, 2, 3] {
+            if j > i {
+                continue 'rows;
+            }
+            if i == 3 {
+                break 'rows;
+            }
+            sum = sum + 10 * i + j;
+        }
+    }
+    sum
+}
+";
+    // the pairs (1, 0), (1, 1), (2, 0), (2, 1) and (2, 2) are added after (0, 0).
+    assert_eq!(run("continue_label_for", source), 10 + 11 + 20 + 21 + 22);
+}
+
+#[test]
+fn break_label_with_value_through_nested_loops() {
+    let source = "
+func main() int {
+    let i = 0;
+    let found = 'search: loop {
+        for j in [0, 1, 2, 3, 4, 5, 6, 7, 8, 9] {
+            if i * j == 42 {
+                break 'search i * 100 + j;
+            }
+        }
+        i = i + 1;
+    };
+    found
+}
+";
+    assert_eq!(run("break_label_value", source), 607);
+}
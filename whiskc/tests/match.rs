@@ -0,0 +1,243 @@
+mod common;
+
+use common::{compile, run};
+
+#[test]
+fn payloads_split_across_arms_are_exhaustive() {
+    let source = "
+type T = enum { P(bool), R { a bool }, C { x int, y bool } };
+
+func f(t T) int {
+    match t {
+        T::P(true) => 1,
+        T::P(false) => 2,
+        T::R { a: true } => 3,
+        T::R { a: false } => 4,
+        T::C { x, y: false } => x,
+        T::C { x, y: true } => x * 10,
+    }
+}
+
+func main() int {
+    f(T::P(false)) + f(T::R { a: true }) + f(T::C { x: 5, y: true })
+}
+";
+    assert_eq!(run("split_payloads", source), 2 + 3 + 50);
+}
+
+#[test]
+fn nested_payloads_are_checked() {
+    let source = "
+type O = enum { None, Some(bool) };
+type T = enum { P(O, bool) };
+
+func f(t T) int {
+    match t {
+        T::P(O::None, _) => 1,
+        T::P(O::Some(true), true) => 2,
+        T::P(O::Some(_), false) => 3,
+        T::P(O::Some(true), true) => 4,
+        T::P(O::Some(false), true) => 5,
+    }
+}
+
+func main() int {
+    f(T::P(O::Some(false), true))
+}
+";
+    assert_eq!(compile("nested_payloads", source).unwrap_err(), ["E0506"]);
+}
+
+#[test]
+fn payload_left_partly_uncovered() {
+    let source = "
+type T = enum { P(bool), Q };
+
+func main() int {
+    match T::Q {
+        T::P(true) => 1,
+        T::Q => 2,
+    }
+}
+";
+    assert_eq!(compile("partial_payload", source).unwrap_err(), ["E0505"]);
+}
+
+#[test]
+fn payload_covered_by_earlier_arms_is_unreachable() {
+    let source = "
+type T = enum { P(bool) };
+
+func main() int {
+    match T::P(true) {
+        T::P(true) => 1,
+        T::P(false) => 2,
+        T::P(_) => 3,
+    }
+}
+";
+    assert_eq!(compile("covered_payload", source).unwrap_err(), ["E0506"]);
+}
+
+#[test]
+fn pattern_out_of_range_is_not_reported_as_uncovered() {
+    let source = "
+func main() int {
+    let a i8 = 3;
+    match a {
+        300 => 1,
+        0..=127 => 2,
+    }
+}
+";
+    assert_eq!(
+        compile("pattern_out_of_range", source).unwrap_err(),
+        ["E0201"]
+    );
+}
+
+#[test]
+fn range_patterns() {
+    let source = "
+func f(a i8) int {
+    match a {
+        ..-10 => 1,
+        -10..0 => 2,
+        0 => 3,
+        1..=126 => 4,
+        127.. => 5,
+    }
+}
+
+func main() int {
+    f(-128) + f(-10) * 10 + f(0) * 100 + f(126) * 1000 + f(127) * 10000
+}
+";
+    assert_eq!(run("range_patterns", source), 54321);
+}
+
+#[test]
+fn empty_range_pattern() {
+    let source = "
+func main() int {
+    match 3 {
+        5..5 => 1,
+        3..=2 => 2,
+        _ => 3,
+    }
+}
+";
+    assert_eq!(
+        compile("empty_range", source).unwrap_err(),
+        ["E0205", "E0205"]
+    );
+}
+
+#[test]
+fn overlapping_ranges_are_reachable() {
+    let source = "
+func f(a u8) int {
+    match a {
+        0..=100 => 1,
+        50..=200 => 2,
+        150..=255 => 3,
+    }
+}
+
+func main() int {
+    f(75) + f(175) * 10 + f(255) * 100
+}
+";
+    assert_eq!(run("overlapping_ranges", source), 321);
+}
+
+#[test]
+fn arms_covered_by_earlier_ranges_are_unreachable() {
+    let source = "
+func main() int {
+    let a u8 = 3;
+    match a {
+        0..=100 => 1,
+        101.. => 2,
+        7 => 3,
+        50..=60 => 4,
+        _ => 5,
+    }
+}
+";
+    assert_eq!(
+        compile("covered_ranges", source).unwrap_err(),
+        ["E0506", "E0506", "E0506"]
+    );
+
+    let source = "
+func main() int {
+    match true {
+        true => 1,
+        _ => 2,
+        false => 3,
+    }
+}
+";
+    assert_eq!(compile("after_wildcard", source).unwrap_err(), ["E0506"]);
+}
+
+#[test]
+fn int_match_without_every_value() {
+    let source = "
+func main() int {
+    let a u8 = 3;
+    match a {
+        0..=100 => 1,
+        102..=255 => 2,
+    }
+}
+";
+    assert_eq!(compile("int_gap", source).unwrap_err(), ["E0505"]);
+
+    let source = "
+func main() int {
+    match 3 {
+        ..0 => 1,
+        0..=1000 => 2,
+    }
+}
+";
+    assert_eq!(compile("int_open_end", source).unwrap_err(), ["E0505"]);
+
+    let source = "
+func main() int {
+    let a i8 = 3;
+    match a {
+        -128..=-1 => 1,
+        0..127 => 2,
+    }
+}
+";
+    assert_eq!(compile("int_missing_max", source).unwrap_err(), ["E0505"]);
+}
+
+#[test]
+fn int_match_with_every_value() {
+    let source = "
+func f(a int) int {
+    match a {
+        ..0 => 1,
+        0 => 2,
+        1.. => 3,
+    }
+}
+
+func g(a u8) int {
+    match a {
+        0..128 => 1,
+        128..=255 => 2,
+    }
+}
+
+func main() int {
+    f(-5) + f(0) * 10 + f(9) * 100 + g(200) * 1000
+}
+";
+    assert_eq!(run("int_every_value", source), 2321);
+}
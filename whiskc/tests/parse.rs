@@ -0,0 +1,140 @@
+mod common;
+
+use common::parse;
+use whiskc::ast::nodes::{expr::Expr, item::Item};
+
+/// Returns the codes of the errors reported for the source, which must fail to parse.
+fn errors(name: &str, source: &str) -> Vec<&'static str> {
+    parse(name, source).expect_err("source should not parse")
+}
+
+#[test]
+fn unclosed_array_is_reported_once() {
+    let source = "
+func main() int {
+    let a = [1, 2;
+    0
+}
+";
+    assert_eq!(errors("unclosed_array", source), ["E0102"]);
+}
+
+#[test]
+fn mismatched_closing_delimiter_is_reported_once() {
+    let source = "
+func main() int {
+    let a = [1, 2);
+    0
+}
+";
+    assert_eq!(errors("mismatched_delimiter", source), ["E0102"]);
+}
+
+#[test]
+fn missing_separator_resumes_the_list() {
+    let source = "
+func f(a int, b int) int {
+    a + b
+}
+
+func main() int {
+    f(1 2) + f(3 4)
+}
+";
+    assert_eq!(errors("missing_separator", source), ["E0102", "E0102"]);
+}
+
+#[test]
+fn invalid_item_resumes_at_the_next_separator() {
+    let source = "
+func main() int {
+    let a = [1, +, 3];
+    let b = [;
+    0
+}
+";
+    assert_eq!(errors("invalid_item", source), ["E0150", "E0150"]);
+}
+
+#[test]
+fn malformed_parameters_abandon_the_function() {
+    let source = "
+func f( int {
+    0
+}
+
+func main() int {
+    0
+}
+";
+    assert_eq!(errors("malformed_params", source), ["E0103"]);
+}
+
+#[test]
+fn missing_field_separator() {
+    let source = "
+type V = struct { x int y int };
+
+func main() int {
+    0
+}
+";
+    assert_eq!(errors("missing_field_separator", source), ["E0102"]);
+}
+
+#[test]
+fn malformed_integer_literals() {
+    for (literal, code) in [
+        ("0x", "E0165"),
+        ("0b", "E0165"),
+        ("0b2", "E0166"),
+        ("0o8", "E0166"),
+        ("0xfg", "E0166"),
+        ("1__0", "E0167"),
+        ("1__", "E0167"),
+        ("1_", "E0167"),
+        ("0xf_", "E0167"),
+        ("1_.5", "E0167"),
+        ("18446744073709551616", "E0164"),
+        ("0x1_0000_0000_0000_0000", "E0164"),
+        ("1i7", "E0162"),
+        ("1.5u8", "E0163"),
+    ] {
+        let source = format!("func main() int {{\n    let a = {};\n    0\n}}\n", literal);
+        assert_eq!(errors("malformed_literal", &source), [code], "{}", literal);
+    }
+}
+
+/// Returns the binary operations of the expression with their operands in parentheses.
+fn grouping(expr: &Expr) -> String {
+    match expr {
+        Expr::Binary(binary) => format!(
+            "({} {} {})",
+            grouping(&binary.left),
+            binary.op.0,
+            grouping(&binary.right)
+        ),
+        _ => "_".to_owned(),
+    }
+}
+
+#[test]
+fn bitwise_and_shift_precedence() {
+    for (expr, expected) in [
+        ("a | b & c", "(_ | (_ & _))"),
+        ("a & b | c", "((_ & _) | _)"),
+        ("a ^ b | c & d", "((_ ^ _) | (_ & _))"),
+        ("1 << 2 + 1", "(_ << (_ + _))"),
+        ("a >> b * 2 & c", "((_ >> (_ * _)) & _)"),
+        ("a << b << c", "((_ << _) << _)"),
+        ("a & b == c", "((_ & _) == _)"),
+    ] {
+        let source = format!("func main() int {{ {} }}", expr);
+        let ast = parse("precedence", &source).expect("source should parse");
+        let Some(Item::Function(func)) = ast.items.first() else {
+            panic!("expected a function");
+        };
+        let tail = func.body.eval_expr.as_deref().expect("tail expression");
+        assert_eq!(grouping(tail), expected, "{}", expr);
+    }
+}
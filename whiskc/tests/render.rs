@@ -0,0 +1,91 @@
+use std::{env, fs, process};
+
+/// Compiles the source as `main.wsk` with `whiskc --plain`, returning what it printed to stderr
+/// with the directory of the source left out.
+fn compile_plain(name: &str, source: &str) -> String {
+    let dir = env::temp_dir().join(format!("whiskc-render-{}-{}", process::id(), name));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("main.wsk");
+    fs::write(&path, source).unwrap();
+    let output = process::Command::new(env!("CARGO_BIN_EXE_whiskc"))
+        .arg(&path)
+        .arg("--plain")
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(!output.status.success(), "source should not compile");
+    String::from_utf8(output.stderr)
+        .unwrap()
+        .replace(&path.display().to_string(), "main.wsk")
+}
+
+#[test]
+fn resolve_errors() {
+    let source = "\
+func main() int {
+\tlet a = b;
+    let c bool = 1;
+    0
+}
+";
+    let expected = "\
+error[E0303]: cannot find `b` in this scope
+ --> main.wsk:2:10
+  |
+2 |     let a = b;
+  |             ^ not found in this scope
+
+error[E0405]: mismatched types in assignment
+ --> main.wsk:3:18
+  |
+3 |     let c bool = 1;
+  |           ---- this is `bool`
+  |                  ^ expected `bool`, found `int`
+
+error: could not compile `main` due to 2 previous errors
+
+";
+    assert_eq!(compile_plain("resolve_errors", source), expected);
+}
+
+#[test]
+fn error_at_the_end_of_the_file() {
+    let source = "\
+func main() int {
+    0
+";
+    let expected = "\
+error[E0102]: expected `}`
+ --> main.wsk:2:6
+  |
+2 |     0
+  |      ^ expected `}` here
+
+error: could not compile `main` due to 1 previous error
+
+";
+    assert_eq!(compile_plain("end_of_file", source), expected);
+}
+
+#[test]
+fn codegen_error() {
+    let source = "\
+func main(a int) int {
+    a
+}
+";
+    let expected = "\
+error[E0903]: `main` function has an unsupported signature
+ --> main.wsk:1:6
+  |
+1 | func main(a int) int {
+  |      ^^^^
+  |
+  = note: `main` must take no parameters and return `int`
+
+error: could not compile `main` due to 1 previous error
+
+";
+    assert_eq!(compile_plain("codegen_error", source), expected);
+}
@@ -0,0 +1,406 @@
+mod common;
+
+use common::{compile, compile_ast, parse, run};
+use whiskc::ast::{
+    self,
+    nodes::{expr::Expr, item::Item},
+    parsing::token::Operator,
+};
+
+#[test]
+fn call_function_declared_later() {
+    let source = "
+func main() int {
+    g(1, 2) + h()
+}
+
+func g(a int, b int) int {
+    a + b
+}
+
+func h() int {
+    4
+}
+";
+    assert_eq!(run("forward_call", source), 7);
+}
+
+#[test]
+fn call_function_declared_later_with_wrong_arguments() {
+    let source = "
+func main() int {
+    g(1)
+}
+
+func g(a int, b int) int {
+    a + b
+}
+";
+    assert_eq!(compile("forward_call_args", source).unwrap_err(), ["E0202"]);
+}
+
+#[test]
+fn type_alias_is_not_a_value() {
+    let source = "
+type Int = int;
+
+func main() int {
+    let a = (Int)(5);
+    let b = Int;
+    int(Int(1))
+}
+";
+    assert_eq!(
+        compile("alias_value", source).unwrap_err(),
+        ["E0306", "E0306"]
+    );
+}
+
+#[test]
+fn function_is_not_a_value() {
+    let source = "
+func main() int {
+    let f = main;
+    0
+}
+";
+    assert_eq!(compile("func_value", source).unwrap_err(), ["E0313"]);
+}
+
+#[test]
+fn unresolved_block_value_does_not_cascade() {
+    let function_body = "
+func main() int {
+    undefined_x
+}
+";
+    assert_eq!(
+        compile("cascade_func", function_body).unwrap_err(),
+        ["E0303"]
+    );
+
+    let if_branch = "
+func main() int {
+    if true { undefined_x } else { 1 }
+}
+";
+    assert_eq!(compile("cascade_if", if_branch).unwrap_err(), ["E0303"]);
+
+    let let_value = "
+func main() int {
+    let a int = { undefined_x };
+    a
+}
+";
+    assert_eq!(compile("cascade_let", let_value).unwrap_err(), ["E0303"]);
+}
+
+#[test]
+fn compare_values_without_a_value() {
+    let source = "
+func main() int {
+    if () == () { 1 } else { 0 }
+}
+";
+    assert_eq!(compile("compare_unit", source).unwrap_err(), ["E0435"]);
+}
+
+#[test]
+fn array_literal_as_vector() {
+    let source = "
+func sum(v [int]) int {
+    let total = 0;
+    for x in v {
+        total = total + x;
+    }
+    total
+}
+
+func main() int {
+    let v [int] = [];
+    v.push(3);
+    v.push(4);
+    let nested [[u8]] = [[], [5]];
+    sum(v) + v.len() + nested.len() + sum([10, 20]) + sum([])
+}
+";
+    assert_eq!(run("vec_literal", source), 41);
+}
+
+#[test]
+fn string_length_and_indexing() {
+    let source = r#"
+func main() int {
+    let s = "héllo";
+    s.len() + s[0]
+}
+"#;
+    assert_eq!(run("str_index", source), 6 + 104);
+}
+
+#[test]
+fn string_bytes_are_not_assignable() {
+    let source = r#"
+func main() int {
+    let s = "hello";
+    s[0] = 72;
+    0
+}
+"#;
+    assert_eq!(compile("str_assign", source).unwrap_err(), ["E0404"]);
+}
+
+/// Returns the value of the body of the first function.
+fn tail_expr(ast: &mut ast::AST) -> &mut Expr {
+    let Some(Item::Function(func)) = ast.items.first_mut() else {
+        panic!("expected a function");
+    };
+    func.body.eval_expr.as_deref_mut().expect("tail expression")
+}
+
+#[test]
+fn operators_outside_of_expressions_are_rejected() {
+    let mut ast = parse("binary_range", "func main() int { 1 + 2 }").unwrap();
+    let Expr::Binary(binary) = tail_expr(&mut ast) else {
+        panic!("expected a binary expression");
+    };
+    binary.op.0 = Operator::Range;
+    assert_eq!(compile_ast("binary_range", &ast).unwrap_err(), ["E0436"]);
+
+    let mut ast = parse("unary_range", "func main() int { ~1 }").unwrap();
+    let Expr::Unary(unary) = tail_expr(&mut ast) else {
+        panic!("expected a unary expression");
+    };
+    unary.op.0 = Operator::Range;
+    assert_eq!(compile_ast("unary_range", &ast).unwrap_err(), ["E0437"]);
+}
+
+#[test]
+fn array_of_unresolved_elements_does_not_cascade() {
+    let source = "
+func f(a int) int { a }
+
+func main() int {
+    let a = [undefined_fn];
+    let b bool = a[0];
+    [f][0](1)
+}
+";
+    assert_eq!(
+        compile("array_unresolved", source).unwrap_err(),
+        ["E0303", "E0313"]
+    );
+}
+
+#[test]
+fn empty_array_needs_an_element_type() {
+    let source = "
+func main() int {
+    let a [int; 0] = [];
+    let b = [];
+    a.len()
+}
+";
+    assert_eq!(compile("array_empty", source).unwrap_err(), ["E0438"]);
+}
+
+#[test]
+fn array_with_diverging_element() {
+    let source = "
+func main() int {
+    let a = [1, return 4, 3];
+    a[0]
+}
+";
+    assert_eq!(run("array_diverging", source), 4);
+}
+
+#[test]
+fn array_length_past_the_limit() {
+    let source = "
+func main() int {
+    let a = [1; 9223372036854775807];
+    let b [int; 16777217] = [0; 16777217];
+    0
+}
+";
+    assert_eq!(
+        compile("array_too_long", source).unwrap_err(),
+        ["E0208", "E0208", "E0208"]
+    );
+}
+
+#[test]
+fn conversion_of_literals_to_sized_integers() {
+    let source = "
+type U = u8;
+
+func main() int {
+    let a = u8(3);
+    let b = U(4);
+    let c = i16(-5);
+    a as int + u8(b) as int + c as int + u8(250) as int
+}
+";
+    assert_eq!(run("convert_literal", source), 3 + 4 - 5 + 250);
+
+    let source = "
+func main() int {
+    u8(300) as int
+}
+";
+    assert_eq!(
+        compile("convert_literal_range", source).unwrap_err(),
+        ["E0201"]
+    );
+}
+
+#[test]
+fn index_of_any_integer_type() {
+    let source = r#"
+type Idx = u16;
+
+func main() int {
+    let i u8 = 1;
+    let j u64 = 2;
+    let k = Idx(0);
+    let a = [10, 20, 30];
+    let v [int] = [1, 2, 3];
+    a[i] = 5;
+    v[j] = 7;
+    a[i] + a[k] + v[j] + "ab"[i]
+}
+"#;
+    assert_eq!(run("index_int_types", source), 5 + 10 + 7 + 98);
+
+    let source = "
+func main() int {
+    [1, 2][true]
+}
+";
+    assert_eq!(compile("index_bool", source).unwrap_err(), ["E0418"]);
+}
+
+#[test]
+fn cyclic_aliases() {
+    let source = "
+type A = B;
+type B = C;
+type C = A;
+type S = S;
+
+func f(a A) int { 0 }
+
+func main() int {
+    0
+}
+";
+    assert_eq!(
+        compile("cyclic_alias", source).unwrap_err(),
+        ["E0439", "E0439"]
+    );
+}
+
+#[test]
+fn jumps_outside_of_loops_diverge() {
+    let source = "
+func f() int {
+    break;
+}
+
+func g() int {
+    let a = 1;
+    continue;
+}
+
+func main() int {
+    0
+}
+";
+    assert_eq!(
+        compile("jump_outside_loop", source).unwrap_err(),
+        ["E0502", "E0503"]
+    );
+}
+
+#[test]
+fn integer_literals_out_of_range() {
+    let source = "
+func main() int {
+    let a i8 = 128;
+    let b u8 = 0x100;
+    let c = 9223372036854775808;
+    let d = 0b1_0000_0000u8;
+    0
+}
+";
+    assert_eq!(
+        compile("literals_out_of_range", source).unwrap_err(),
+        ["E0201", "E0201", "E0201", "E0201"]
+    );
+}
+
+#[test]
+fn integer_literals_at_the_bounds() {
+    let source = "
+func main() int {
+    let a i8 = -128;
+    let b u64 = 18446744073709551615;
+    let c = 0xffu8 as int + 0b1010 + 0o17 + 1_000 + 0x_10;
+    a as int + c
+}
+";
+    assert_eq!(
+        run("literals_at_bounds", source),
+        -128 + 255 + 10 + 15 + 1000 + 16
+    );
+}
+
+#[test]
+fn bitwise_operands_must_be_integers() {
+    let source = "
+func main() int {
+    let a = 1 & true;
+    let b = 1.0 << 2;
+    let c = 1 | 2u8;
+    let d = 1u8 << 2;
+    0
+}
+";
+    assert_eq!(
+        compile("bitwise_operands", source).unwrap_err(),
+        ["E0434", "E0434"]
+    );
+}
+
+#[test]
+fn bitwise_and_shift_precedence() {
+    let source = "
+func main() int {
+    let a = 1;
+    let b = 2;
+    let c = 4;
+    (a | b & c) * 100 + (1 << 2 + 1) * 10 + (a ^ b | c)
+}
+";
+    assert_eq!(run("bitwise_precedence", source), 100 + 80 + 7);
+}
+
+#[test]
+fn shifts_past_the_bit_width() {
+    let source = "
+func main() int {
+    let s = 64;
+    let x u8 = 3;
+    let y i8 = -100;
+    let a = 1 << s;
+    let b = -8 >> 70;
+    let c = 8 >> s;
+    let d = (x << 8) as int;
+    let e = (y >> 8u8) as int;
+    let f = (1 << 63) >> 63;
+    a * 10000 + b * 1000 + c * 100 + d * 10 + e + f
+}
+";
+    assert_eq!(run("shift_past_width", source), -1000 - 1 - 1);
+}
@@ -0,0 +1,169 @@
+mod common;
+
+use std::{fs, path::Path};
+
+use common::{compile, run};
+use wsk_vm::verify::verify;
+
+/// Sample programs, each with the status returned by its `main`.
+const SAMPLES: [(&str, &str, i64); 6] = [
+    (
+        "structs",
+        r#"
+type Vec2 = struct {
+    x int,
+    y int,
+};
+type Line = struct {
+    name str,
+    from Vec2,
+    to Vec2,
+};
+
+func len2(l Line) int {
+    let dx = l.to.x - l.from.x;
+    let dy = l.to.y - l.from.y;
+    dx * dx + dy * dy
+}
+
+func main() int {
+    let l = Line { name: "diag", from: Vec2 { x: 0, y: 0 }, to: Vec2 { x: 3, y: 4 } };
+    let copy = l;
+    l.to.x = 6;
+    l.name = "moved";
+    println(l.name == copy.name);
+    len2(copy)
+}
+"#,
+        25,
+    ),
+    (
+        "enums",
+        r#"
+type Shape = enum {
+    Empty,
+    Square(int),
+    Rect { w int, h int },
+};
+
+func area(s Shape) int {
+    match s {
+        Shape::Empty => 0,
+        Shape::Square(n) if n < 0 => 0,
+        Shape::Square(n) => n * n,
+        Shape::Rect { w, h } => w * h,
+    }
+}
+
+func main() int {
+    area(Shape::Empty) + area(Shape::Square(3)) + area(Shape::Rect { w: 2, h: 5 })
+}
+"#,
+        19,
+    ),
+    (
+        "loops",
+        r#"
+func main() int {
+    let i = 0;
+    let sum = 0;
+    while i < 10 {
+        i = i + 1;
+        if i % 2 == 0 {
+            continue;
+        }
+        sum = sum + i;
+    }
+    let found = 'outer: loop {
+        for v in [1, 2, 3] {
+            if v == 2 {
+                break 'outer v;
+            }
+        }
+        break 0;
+    };
+    sum + found
+}
+"#,
+        27,
+    ),
+    (
+        "containers",
+        r#"
+func main() int {
+    let a [int; 3] = [1, 2, 3];
+    a[0] = 4;
+    let r = [0u8; 4];
+    let v = a.to_vec();
+    v.push(5);
+    let last = v.pop();
+    let total = 0;
+    for x in v {
+        total = total + x;
+    }
+    total + last + a.len() + r.len()
+}
+"#,
+        21,
+    ),
+    (
+        "numbers",
+        r#"
+func half(v float) float {
+    v / 2.0
+}
+
+func main() int {
+    let a = 250u8;
+    let b = a.wrapping_add(10) | 0b1000 ^ ~0x0fu8 & a << 1;
+    let c i8 = -128;
+    let d = half(5.0) as int + (c >> 2) as int;
+    let m = match 0x10 { 0x0..=0xf => 1, 0b1_0000 => 2, _ => 3 };
+    b as int + d + m + (3.99 as u8) as int
+}
+"#,
+        227,
+    ),
+    (
+        "strings",
+        r#"
+func greet(name str) str {
+    return "hello, " + name + "\t\"!\"\n";
+}
+
+func main() int {
+    let same = greet("wörld") == greet("wörld");
+    print(greet("x"));
+    if same { 1 } else { 0 }
+}
+"#,
+        1,
+    ),
+];
+
+#[test]
+fn compiled_samples_are_well_formed() {
+    for (name, source, _) in SAMPLES {
+        let program = compile(name, source).unwrap_or_else(|e| panic!("{}: {:?}", name, e));
+        assert!(verify(&program).is_ok(), "{} is not well-formed", name);
+    }
+}
+
+#[test]
+fn compiled_samples_run() {
+    for (name, source, status) in SAMPLES {
+        assert_eq!(
+            run(name, source),
+            status,
+            "{} returned another status",
+            name
+        );
+    }
+}
+
+#[test]
+fn compiled_test_program_is_well_formed() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../test/test.wsk");
+    let program = compile("test", &fs::read_to_string(path).unwrap()).unwrap();
+    assert!(verify(&program).is_ok());
+}
@@ -0,0 +1,700 @@
+//! Textual assembly format of programs.
+//!
+//! ```text
+//! ; comments start with a semicolon
+//! entry $1
+//!
+//! const #0 "hello"
+//!
+//! import print(str)
+//!
+//! func main() -> 1:
+//!     push true
+//!     jfl L4
+//!     pushc #0
+//!     calln print
+//! L4:
+//!     push 0
+//!     ret
+//!
+//! func $1():
+//!     call main
+//!     halt
+//! ```
+//!
+//! Functions are numbered in the order of declaration, and can be referred to by either their
+//! name or their index (`$0`). The header of a function declares its number of parameters and
+//! return values, both of which default to zero. Host functions are declared with their signatures
+//! by `import`, and called by name with `calln`. Jump targets are labels local to the function,
+//! or relative offsets (`+2`, `-3`) for targets which are not within the function.
+
+use std::{cell::RefCell, collections::HashMap, fmt, num::ParseIntError, rc::Rc};
+
+use crate::{
+    inst::ArithOp,
+    native::{Import, NativeSig, ValueType},
+    program::{Arity, Function, Program},
+    value::IntType,
+    Cmp, Inst, Value,
+};
+
+/// Returns the assembly of the program, which assembles back to the same program.
+pub fn disassemble(prog: &Program) -> String {
+    let mut out = String::new();
+    let func_ref = |fi: usize| match prog.get(fi).and_then(|v| v.get_name()) {
+        Some(name) => name.to_owned(),
+        None => format!("${}", fi),
+    };
+
+    let import_ref = |idx: usize| match prog.get_import(idx) {
+        Some(import) => import.name.clone(),
+        None => format!("@{}", idx),
+    };
+
+    out.push_str(&format!("entry {}\n", func_ref(prog.get_entry_point())));
+
+    if !prog.get_consts().is_empty() {
+        out.push('\n');
+        for (i, value) in prog.get_consts().iter().enumerate() {
+            out.push_str(&format!("const #{} {}\n", i, value));
+        }
+    }
+
+    if !prog.get_imports().is_empty() {
+        out.push('\n');
+        for import in prog.get_imports() {
+            out.push_str(&format!("import {}{}\n", import.name, import.sig));
+        }
+    }
+
+    for (fi, func) in prog.get_funcs().iter().enumerate() {
+        let arity = func.get_arity();
+        out.push_str(&format!("\nfunc {}({})", func_ref(fi), arity.params));
+        if arity.rets != 0 {
+            out.push_str(&format!(" -> {}", arity.rets));
+        }
+        out.push_str(":\n");
+        write_func_body(&mut out, func, &func_ref, &import_ref);
+    }
+    out
+}
+
+fn write_func_body(
+    out: &mut String,
+    func: &Function,
+    func_ref: &impl Fn(usize) -> String,
+    import_ref: &impl Fn(usize) -> String,
+) {
+    let insts = func.get_insts();
+    let jump_target = |i: usize, offset: isize| {
+        i.checked_add_signed(offset)
+            .filter(|target| *target <= insts.len())
+    };
+
+    let mut labels: Vec<usize> = insts
+        .iter()
+        .enumerate()
+        .filter_map(|(i, inst)| match inst {
+            Inst::Jmp(offset) | Inst::JmpTrue(offset) | Inst::JmpFalse(offset) => {
+                jump_target(i, *offset)
+            }
+            _ => None,
+        })
+        .collect();
+    labels.sort();
+    labels.dedup();
+
+    for i in 0..=insts.len() {
+        if labels.binary_search(&i).is_ok() {
+            out.push_str(&format!("L{}:\n", i));
+        }
+        let Some(inst) = insts.get(i) else {
+            break;
+        };
+
+        let jump = |offset: isize| match jump_target(i, offset) {
+            Some(target) => format!("L{}", target),
+            None => format!("{:+}", offset),
+        };
+        let line = match inst {
+            Inst::Halt => "halt".to_owned(),
+            Inst::Push(value) => format!("push {}", value),
+            Inst::PushConst(idx) => format!("pushc #{}", idx),
+            Inst::Pop => "pop".to_owned(),
+            Inst::Load(idx) => format!("load r{}", idx),
+            Inst::Store(idx) => format!("store r{}", idx),
+            Inst::Add => "add".to_owned(),
+            Inst::Sub => "sub".to_owned(),
+            Inst::Mul => "mul".to_owned(),
+            Inst::Div => "div".to_owned(),
+            Inst::Mod => "mod".to_owned(),
+            Inst::And => "and".to_owned(),
+            Inst::Or => "or".to_owned(),
+            Inst::Cmp(cmp) => format!(
+                "cmp {}",
+                match cmp {
+                    Cmp::Equal => "equ",
+                    Cmp::Less => "lt",
+                    Cmp::Greater => "gt",
+                    Cmp::LessEqual => "le",
+                    Cmp::GreaterEqual => "ge",
+                }
+            ),
+            Inst::Wrapping(op) => format!("wrap {}", op),
+            Inst::Saturating(op) => format!("sat {}", op),
+            Inst::BitAnd => "band".to_owned(),
+            Inst::BitOr => "bor".to_owned(),
+            Inst::BitXor => "bxor".to_owned(),
+            Inst::Shl => "shl".to_owned(),
+            Inst::Shr => "shr".to_owned(),
+            Inst::Neg => "neg".to_owned(),
+            Inst::Not => "not".to_owned(),
+            Inst::BitNot => "bnot".to_owned(),
+            Inst::Concat => "concat".to_owned(),
+            Inst::StrLen => "strlen".to_owned(),
+            Inst::StrIndex => "stridx".to_owned(),
+            Inst::MakeStruct(cnt) => format!("mkstruct {}", cnt),
+            Inst::GetField(idx) => format!("getfield {}", idx),
+            Inst::SetField(idx) => format!("setfield {}", idx),
+            Inst::MakeEnum(tag, cnt) => format!("mkenum {} {}", tag, cnt),
+            Inst::GetTag => "gettag".to_owned(),
+            Inst::MakeArray(cnt) => format!("mkarray {}", cnt),
+            Inst::RepeatArray(cnt) => format!("reparray {}", cnt),
+            Inst::GetIndex => "getidx".to_owned(),
+            Inst::SetIndex => "setidx".to_owned(),
+            Inst::ToVec => "tovec".to_owned(),
+            Inst::VecPush => "vecpush".to_owned(),
+            Inst::VecPop => "vecpop".to_owned(),
+            Inst::VecLen => "veclen".to_owned(),
+            Inst::VecGet => "vecget".to_owned(),
+            Inst::VecSet => "vecset".to_owned(),
+            Inst::IntToBool => "itob".to_owned(),
+            Inst::BoolToInt => "btoi".to_owned(),
+            Inst::IntCast(ty) => format!("icast {}", ty),
+            Inst::IntToFloat => "itof".to_owned(),
+            Inst::FloatToInt(ty) => format!("ftoi {}", ty),
+            Inst::Jmp(offset) => format!("jmp {}", jump(*offset)),
+            Inst::JmpTrue(offset) => format!("jtr {}", jump(*offset)),
+            Inst::JmpFalse(offset) => format!("jfl {}", jump(*offset)),
+            Inst::Call(fi) => format!("call {}", func_ref(*fi)),
+            Inst::CallNative(idx) => format!("calln {}", import_ref(*idx)),
+            Inst::Ret => "ret".to_owned(),
+        };
+        out.push_str(&format!("    {}\n", line));
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        write_func_body(&mut out, self, &|fi| format!("${}", fi), &|idx| {
+            format!("@{}", idx)
+        });
+        write!(f, "{}", out)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsmError {
+    /// 1-based line number of the source.
+    pub line: usize,
+    pub kind: AsmErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AsmErrorKind {
+    UnknownMnemonic(String),
+    MissingOperand,
+    UnexpectedOperand(String),
+    InvalidOperand(String),
+    InvalidValue(String),
+    UnknownLabel(String),
+    DuplicateLabel(String),
+    UnknownFunction(String),
+    DuplicateFunction(String),
+    UnknownImport(String),
+    DuplicateImport(String),
+    /// `$N` function headers and `#N` constants must be declared in order.
+    OutOfOrderIndex(String),
+    DuplicateEntry,
+    OutsideFunction,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            AsmErrorKind::UnknownMnemonic(v) => write!(f, "unknown instruction `{}`", v),
+            AsmErrorKind::MissingOperand => write!(f, "missing operand"),
+            AsmErrorKind::UnexpectedOperand(v) => write!(f, "unexpected operand `{}`", v),
+            AsmErrorKind::InvalidOperand(v) => write!(f, "invalid operand `{}`", v),
+            AsmErrorKind::InvalidValue(v) => write!(f, "invalid value `{}`", v),
+            AsmErrorKind::UnknownLabel(v) => write!(f, "unknown label `{}`", v),
+            AsmErrorKind::DuplicateLabel(v) => write!(f, "label `{}` is defined twice", v),
+            AsmErrorKind::UnknownFunction(v) => write!(f, "unknown function `{}`", v),
+            AsmErrorKind::DuplicateFunction(v) => write!(f, "function `{}` is defined twice", v),
+            AsmErrorKind::UnknownImport(v) => write!(f, "unknown import `{}`", v),
+            AsmErrorKind::DuplicateImport(v) => write!(f, "import `{}` is declared twice", v),
+            AsmErrorKind::OutOfOrderIndex(v) => write!(f, "`{}` is declared out of order", v),
+            AsmErrorKind::DuplicateEntry => write!(f, "entry is declared twice"),
+            AsmErrorKind::OutsideFunction => write!(f, "instruction or label outside function"),
+        }
+    }
+}
+
+struct FuncDraft<'a> {
+    name: Option<&'a str>,
+    arity: Arity,
+    labels: HashMap<&'a str, usize>,
+    /// line number, mnemonic and operand.
+    insts: Vec<(usize, &'a str, &'a str)>,
+}
+
+/// Assembles the program from its assembly.
+pub fn assemble(src: &str) -> Result<Program, AsmError> {
+    let mut entry: Option<(usize, &str)> = None;
+    let mut consts: Vec<Value> = Vec::new();
+    let mut imports: Vec<Import> = Vec::new();
+    let mut drafts: Vec<FuncDraft> = Vec::new();
+
+    for (line_no, line) in src.lines().enumerate() {
+        let line_no = line_no + 1;
+        let err = |kind| AsmError {
+            line: line_no,
+            kind,
+        };
+
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (word, operand) = line
+            .split_once(char::is_whitespace)
+            .map(|(w, o)| (w, o.trim()))
+            .unwrap_or((line, ""));
+
+        match word {
+            "entry" => {
+                if entry.is_some() {
+                    return Err(err(AsmErrorKind::DuplicateEntry));
+                }
+                entry = Some((line_no, operand));
+            }
+            "const" => {
+                let (idx, value) = operand
+                    .split_once(char::is_whitespace)
+                    .ok_or(err(AsmErrorKind::MissingOperand))?;
+                if parse_prefixed(idx, '#') != Ok(consts.len()) {
+                    return Err(err(AsmErrorKind::OutOfOrderIndex(idx.to_owned())));
+                }
+                consts.push(parse_value(value.trim()).map_err(err)?);
+            }
+            "import" => {
+                let import = parse_import(operand)
+                    .ok_or(err(AsmErrorKind::InvalidOperand(operand.to_owned())))?;
+                if imports.iter().any(|v| v.name == import.name) {
+                    return Err(err(AsmErrorKind::DuplicateImport(import.name)));
+                }
+                imports.push(import);
+            }
+            "func" => {
+                let (name, arity) = operand
+                    .strip_suffix(':')
+                    .and_then(parse_func_header)
+                    .ok_or(err(AsmErrorKind::InvalidOperand(operand.to_owned())))?;
+                let name = if name.starts_with('$') {
+                    if parse_prefixed(name, '$') != Ok(drafts.len()) {
+                        return Err(err(AsmErrorKind::OutOfOrderIndex(name.to_owned())));
+                    }
+                    None
+                } else {
+                    if drafts.iter().any(|v| v.name == Some(name)) {
+                        return Err(err(AsmErrorKind::DuplicateFunction(name.to_owned())));
+                    }
+                    Some(name)
+                };
+                drafts.push(FuncDraft {
+                    name,
+                    arity,
+                    labels: HashMap::new(),
+                    insts: Vec::new(),
+                });
+            }
+            _ => {
+                let Some(func) = drafts.last_mut() else {
+                    return Err(err(AsmErrorKind::OutsideFunction));
+                };
+                if let Some(label) = line.strip_suffix(':') {
+                    let label = label.trim();
+                    if func.labels.insert(label, func.insts.len()).is_some() {
+                        return Err(err(AsmErrorKind::DuplicateLabel(label.to_owned())));
+                    }
+                } else {
+                    func.insts.push((line_no, word, operand));
+                }
+            }
+        }
+    }
+
+    let func_idx = |name: &str| -> Option<usize> {
+        if name.starts_with('$') {
+            parse_prefixed(name, '$')
+                .ok()
+                .filter(|fi| *fi < drafts.len())
+        } else {
+            drafts.iter().position(|v| v.name == Some(name))
+        }
+    };
+
+    let import_idx = |name: &str| -> Option<usize> {
+        if name.starts_with('@') {
+            parse_prefixed(name, '@')
+                .ok()
+                .filter(|idx| *idx < imports.len())
+        } else {
+            imports.iter().position(|v| v.name == name)
+        }
+    };
+
+    let entry_point = match entry {
+        Some((line, name)) => func_idx(name).ok_or(AsmError {
+            line,
+            kind: AsmErrorKind::UnknownFunction(name.to_owned()),
+        })?,
+        None => 0,
+    };
+
+    let mut prog = Program::new(entry_point);
+    for value in consts {
+        prog.add_const(value);
+    }
+    for import in imports.iter().cloned() {
+        prog.add_import(import);
+    }
+    for draft in &drafts {
+        let mut func = Function::default();
+        func.set_arity(draft.arity);
+        if let Some(name) = draft.name {
+            func.set_name(name);
+        }
+        for (i, (line, mnemonic, operand)) in draft.insts.iter().enumerate() {
+            let inst = parse_inst(mnemonic, operand, i, draft, &func_idx, &import_idx)
+                .map_err(|kind| AsmError { line: *line, kind })?;
+            func.push_inst(inst);
+        }
+        prog.add_func(func);
+    }
+    Ok(prog)
+}
+
+fn parse_inst(
+    mnemonic: &str,
+    operand: &str,
+    index: usize,
+    func: &FuncDraft,
+    func_idx: &impl Fn(&str) -> Option<usize>,
+    import_idx: &impl Fn(&str) -> Option<usize>,
+) -> Result<Inst, AsmErrorKind> {
+    let invalid = || AsmErrorKind::InvalidOperand(operand.to_owned());
+    let jump = || -> Result<isize, AsmErrorKind> {
+        if operand.starts_with(['+', '-']) {
+            return operand.parse::<isize>().map_err(|_| invalid());
+        }
+        let target = func
+            .labels
+            .get(operand)
+            .ok_or(AsmErrorKind::UnknownLabel(operand.to_owned()))?;
+        Ok(*target as isize - index as isize)
+    };
+
+    let inst = match mnemonic {
+        "push" => Inst::Push(parse_value(operand)?),
+        "pushc" => Inst::PushConst(parse_prefixed(operand, '#').map_err(|_| invalid())?),
+        "load" => Inst::Load(parse_prefixed(operand, 'r').map_err(|_| invalid())?),
+        "store" => Inst::Store(parse_prefixed(operand, 'r').map_err(|_| invalid())?),
+        "cmp" => Inst::Cmp(match operand {
+            "equ" => Cmp::Equal,
+            "lt" => Cmp::Less,
+            "gt" => Cmp::Greater,
+            "le" => Cmp::LessEqual,
+            "ge" => Cmp::GreaterEqual,
+            _ => return Err(invalid()),
+        }),
+        "wrap" => Inst::Wrapping(parse_arith_op(operand).ok_or(invalid())?),
+        "sat" => Inst::Saturating(parse_arith_op(operand).ok_or(invalid())?),
+        "icast" => Inst::IntCast(parse_int_type(operand).ok_or(invalid())?),
+        "ftoi" => Inst::FloatToInt(parse_int_type(operand).ok_or(invalid())?),
+        "mkstruct" => Inst::MakeStruct(operand.parse().map_err(|_| invalid())?),
+        "getfield" => Inst::GetField(operand.parse().map_err(|_| invalid())?),
+        "setfield" => Inst::SetField(operand.parse().map_err(|_| invalid())?),
+        "mkarray" => Inst::MakeArray(operand.parse().map_err(|_| invalid())?),
+        "reparray" => Inst::RepeatArray(operand.parse().map_err(|_| invalid())?),
+        "mkenum" => {
+            let (tag, cnt) = operand.split_once(char::is_whitespace).ok_or(invalid())?;
+            Inst::MakeEnum(
+                tag.parse().map_err(|_| invalid())?,
+                cnt.trim().parse().map_err(|_| invalid())?,
+            )
+        }
+        "jmp" => Inst::Jmp(jump()?),
+        "jtr" => Inst::JmpTrue(jump()?),
+        "jfl" => Inst::JmpFalse(jump()?),
+        "calln" => Inst::CallNative(
+            import_idx(operand).ok_or(AsmErrorKind::UnknownImport(operand.to_owned()))?,
+        ),
+        "call" => {
+            Inst::Call(func_idx(operand).ok_or(AsmErrorKind::UnknownFunction(operand.to_owned()))?)
+        }
+        _ => {
+            let inst = match mnemonic {
+                "halt" => Inst::Halt,
+                "pop" => Inst::Pop,
+                "add" => Inst::Add,
+                "sub" => Inst::Sub,
+                "mul" => Inst::Mul,
+                "div" => Inst::Div,
+                "mod" => Inst::Mod,
+                "and" => Inst::And,
+                "or" => Inst::Or,
+                "band" => Inst::BitAnd,
+                "bor" => Inst::BitOr,
+                "bxor" => Inst::BitXor,
+                "shl" => Inst::Shl,
+                "shr" => Inst::Shr,
+                "neg" => Inst::Neg,
+                "not" => Inst::Not,
+                "bnot" => Inst::BitNot,
+                "concat" => Inst::Concat,
+                "strlen" => Inst::StrLen,
+                "stridx" => Inst::StrIndex,
+                "gettag" => Inst::GetTag,
+                "getidx" => Inst::GetIndex,
+                "setidx" => Inst::SetIndex,
+                "tovec" => Inst::ToVec,
+                "vecpush" => Inst::VecPush,
+                "vecpop" => Inst::VecPop,
+                "veclen" => Inst::VecLen,
+                "vecget" => Inst::VecGet,
+                "vecset" => Inst::VecSet,
+                "itob" => Inst::IntToBool,
+                "btoi" => Inst::BoolToInt,
+                "itof" => Inst::IntToFloat,
+                "ret" => Inst::Ret,
+                _ => return Err(AsmErrorKind::UnknownMnemonic(mnemonic.to_owned())),
+            };
+            if !operand.is_empty() {
+                return Err(AsmErrorKind::UnexpectedOperand(operand.to_owned()));
+            }
+            return Ok(inst);
+        }
+    };
+    if operand.is_empty() {
+        return Err(AsmErrorKind::MissingOperand);
+    }
+    Ok(inst)
+}
+
+/// Parses `name(int, str) -> bool` where the return type is optional.
+fn parse_import(s: &str) -> Option<Import> {
+    let (sig, ret) = match s.split_once("->") {
+        Some((sig, ret)) => (sig, Some(parse_value_type(ret.trim())?)),
+        None => (s, None),
+    };
+    let (name, params) = sig.trim().strip_suffix(')')?.split_once('(')?;
+    let name = name.trim();
+    let params = if params.trim().is_empty() {
+        vec![]
+    } else {
+        params
+            .split(',')
+            .map(|v| parse_value_type(v.trim()))
+            .collect::<Option<Vec<_>>>()?
+    };
+    (!name.is_empty()).then(|| Import::new(name, NativeSig::new(params, ret)))
+}
+
+fn parse_value_type(s: &str) -> Option<ValueType> {
+    match s {
+        "bool" => Some(ValueType::Bool),
+        "float" => Some(ValueType::Float),
+        "str" => Some(ValueType::Str),
+        _ => parse_int_type(s).map(ValueType::Int),
+    }
+}
+
+fn parse_int_type(s: &str) -> Option<IntType> {
+    IntType::ALL.into_iter().find(|v| v.to_string() == s)
+}
+
+fn parse_arith_op(s: &str) -> Option<ArithOp> {
+    [ArithOp::Add, ArithOp::Sub, ArithOp::Mul]
+        .into_iter()
+        .find(|v| v.to_string() == s)
+}
+
+/// Parses `name(params) -> rets` where the parameter and return counts are optional.
+fn parse_func_header(header: &str) -> Option<(&str, Arity)> {
+    let (header, rets) = match header.split_once("->") {
+        Some((header, rets)) => (header, rets.trim().parse().ok()?),
+        None => (header, 0),
+    };
+    let header = header.trim();
+    let (name, params) = match header.split_once('(') {
+        Some((name, params)) => {
+            let params = params.strip_suffix(')')?.trim();
+            let params = if params.is_empty() {
+                0
+            } else {
+                params.parse().ok()?
+            };
+            (name.trim(), params)
+        }
+        None => (header, 0),
+    };
+    (!name.is_empty()).then_some((name, Arity::new(params, rets)))
+}
+
+fn parse_prefixed(s: &str, prefix: char) -> Result<usize, ParseIntError> {
+    s.strip_prefix(prefix).unwrap_or("-").parse()
+}
+
+fn parse_value(s: &str) -> Result<Value, AsmErrorKind> {
+    if s.is_empty() {
+        return Err(AsmErrorKind::MissingOperand);
+    }
+    match parse_value_prefix(s) {
+        Some((value, rest)) if rest.trim().is_empty() => Ok(value),
+        _ => Err(AsmErrorKind::InvalidValue(s.to_owned())),
+    }
+}
+
+/// Parses the value at the start of the string, returning it with the rest of the string.
+/// Structs are written as their fields in braces, such as `{1, "a", {true}}`, enums as their
+/// tag followed by their payload, such as `#1{5}`, arrays as their elements in brackets,
+/// such as `[1, 2]`, and vectors as arrays prefixed by `vec`, such as `vec[1, 2]`.
+fn parse_value_prefix(s: &str) -> Option<(Value, &str)> {
+    let s = s.trim_start();
+    if let Some(rest) = s.strip_prefix('#') {
+        let (tag, payload) = rest.split_at(rest.find('{')?);
+        let (Value::Struct(payload), rest) = parse_value_prefix(payload)? else {
+            return None;
+        };
+        return Some((Value::Enum(tag.parse().ok()?, payload), rest));
+    }
+    if let Some(rest) = s.strip_prefix('{') {
+        let (fields, rest) = parse_values_until(rest, '}')?;
+        return Some((Value::Struct(fields.into()), rest));
+    }
+    if let Some(rest) = s.strip_prefix('[') {
+        let (elements, rest) = parse_values_until(rest, ']')?;
+        return Some((Value::Array(elements.into()), rest));
+    }
+    if let Some(rest) = s.strip_prefix("vec[") {
+        let (elements, rest) = parse_values_until(rest, ']')?;
+        return Some((Value::Vec(Rc::new(RefCell::new(elements))), rest));
+    }
+    if s.starts_with('"') {
+        let (value, rest) = parse_str(s)?;
+        return Some((Value::from(value.as_str()), rest));
+    }
+
+    let end = s
+        .find(|c: char| c == ',' || c == '}' || c == ']' || c.is_whitespace())
+        .unwrap_or(s.len());
+    let (word, rest) = s.split_at(end);
+    let value = match word {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => parse_int(word).or_else(|| parse_float(word))?,
+    };
+    Some((value, rest))
+}
+
+/// Parses an integer, which is an `int` unless it has the suffix of its type, such as `255u8`.
+fn parse_int(s: &str) -> Option<Value> {
+    let (digits, suffix) = s.split_at(s.find(['i', 'u']).unwrap_or(s.len()));
+    if suffix.is_empty() {
+        return Some(Value::Int(digits.parse().ok()?));
+    }
+    Some(match parse_int_type(suffix)? {
+        IntType::Int => Value::Int(digits.parse().ok()?),
+        IntType::I8 => Value::I8(digits.parse().ok()?),
+        IntType::I16 => Value::I16(digits.parse().ok()?),
+        IntType::I32 => Value::I32(digits.parse().ok()?),
+        IntType::U8 => Value::U8(digits.parse().ok()?),
+        IntType::U16 => Value::U16(digits.parse().ok()?),
+        IntType::U32 => Value::U32(digits.parse().ok()?),
+        IntType::U64 => Value::U64(digits.parse().ok()?),
+    })
+}
+
+/// Parses a float, which has a point or an exponent, such as `1.0` and `1e20`, or is `inf`,
+/// `-inf` or `NaN`.
+fn parse_float(s: &str) -> Option<Value> {
+    let is_float = s.contains(['.', 'e']) || ["inf", "-inf", "NaN"].contains(&s);
+    is_float.then(|| s.parse().ok().map(Value::Float))?
+}
+
+/// Parses the comma-separated values up to the closing character, returning them with the rest
+/// of the string.
+fn parse_values_until(mut s: &str, close: char) -> Option<(Vec<Value>, &str)> {
+    let mut values = Vec::new();
+    if let Some(rest) = s.trim_start().strip_prefix(close) {
+        return Some((values, rest));
+    }
+    loop {
+        let (value, next) = parse_value_prefix(s)?;
+        values.push(value);
+        let next = next.trim_start();
+        match next.strip_prefix(',') {
+            Some(next) => s = next,
+            None => return Some((values, next.strip_prefix(close)?)),
+        }
+    }
+}
+
+/// Parses a quoted string with the escapes produced by `str::escape_debug`, returning it with
+/// the rest of the string.
+fn parse_str(s: &str) -> Option<(String, &str)> {
+    let mut chars = s.strip_prefix('"')?.chars();
+    let mut value = String::new();
+    loop {
+        match chars.next()? {
+            '"' => break,
+            '\\' => value.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                '0' => '\0',
+                '\\' => '\\',
+                '"' => '"',
+                '\'' => '\'',
+                'u' => {
+                    let rest = chars.as_str().strip_prefix('{')?;
+                    let (hex, rest) = rest.split_once('}')?;
+                    chars = rest.chars();
+                    char::from_u32(u32::from_str_radix(hex, 16).ok()?)?
+                }
+                _ => return None,
+            }),
+            c => value.push(c),
+        }
+    }
+    Some((value, chars.as_str()))
+}
+
+/// Removes the comment of the line, ignoring semicolons within string literals.
+fn strip_comment(line: &str) -> &str {
+    let mut in_str = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_str => escaped = true,
+            '"' => in_str = !in_str,
+            ';' if !in_str => return &line[..i],
+            _ => (),
+        }
+    }
+    line
+}
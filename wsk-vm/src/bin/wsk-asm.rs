@@ -0,0 +1,36 @@
+use std::{env, fs, path::Path, process::ExitCode};
+
+use wsk_vm::asm::assemble;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (input, output) = match args.as_slice() {
+        [input] => (input, Path::new(input).with_extension("wc")),
+        [input, flag, output] if flag == "-o" => (input, output.into()),
+        _ => {
+            eprintln!("usage: wsk-asm <input.wsa> [-o <output.wc>]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let src = match fs::read_to_string(input) {
+        Ok(src) => src,
+        Err(e) => {
+            eprintln!("cannot read {}: {}", input, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let prog = match assemble(&src) {
+        Ok(prog) => prog,
+        Err(e) => {
+            eprintln!("{}: {}", input, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Err(e) = fs::write(&output, prog.to_bin()) {
+        eprintln!("cannot write {}: {}", output.display(), e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
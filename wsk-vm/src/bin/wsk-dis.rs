@@ -0,0 +1,42 @@
+use std::{env, fs, process::ExitCode};
+
+use wsk_vm::{asm::disassemble, program::Program};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (input, output) = match args.as_slice() {
+        [input] => (input, None),
+        [input, flag, output] if flag == "-o" => (input, Some(output)),
+        _ => {
+            eprintln!("usage: wsk-dis <input.wc> [-o <output.wsa>]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let bytes = match fs::read(input) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("cannot read {}: {}", input, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let prog = match Program::from_bytes(&bytes) {
+        Ok(prog) => prog,
+        Err(e) => {
+            eprintln!("{}: {}", input, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let asm = disassemble(&prog);
+    match output {
+        Some(output) => {
+            if let Err(e) = fs::write(output, asm) {
+                eprintln!("cannot write {}: {}", output, e);
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", asm),
+    }
+    ExitCode::SUCCESS
+}
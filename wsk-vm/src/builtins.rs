@@ -0,0 +1,72 @@
+//! Host functions of the standard prelude, bound to the imports emitted for the built-in
+//! functions of whisk programs.
+
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    native::{NativeError, NativeSig, ValueType},
+    value::IntType,
+    Value, VM,
+};
+
+/// Registers `print_<ty>` and `println_<ty>` for every integer type, float, bool and str,
+/// `read_line` and `exit`.
+pub fn register_builtins(vm: &mut VM) {
+    let int_types = IntType::ALL.map(ValueType::Int);
+    for ty in int_types
+        .into_iter()
+        .chain([ValueType::Float, ValueType::Bool, ValueType::Str])
+    {
+        let sig = NativeSig::new([ty], None);
+        vm.register_native(format!("print_{}", ty), sig.clone(), |args| {
+            write_stdout(&output_of(&args[0]))
+        });
+        vm.register_native(format!("println_{}", ty), sig, |args| {
+            write_stdout(&(output_of(&args[0]) + "\n"))
+        });
+    }
+
+    vm.register_native(
+        "read_line",
+        NativeSig::new([], Some(ValueType::Str)),
+        |_| {
+            let mut line = String::new();
+            io::stdin()
+                .lock()
+                .read_line(&mut line)
+                .map_err(|e| NativeError::Failed(e.to_string()))?;
+            let len = line.trim_end_matches(['\n', '\r']).len();
+            line.truncate(len);
+            Ok(Some(Value::from(line.as_str())))
+        },
+    );
+
+    let exit_sig = NativeSig::new([ValueType::Int(IntType::Int)], None);
+    vm.register_native("exit", exit_sig, |args| {
+        let Value::Int(status) = args[0] else {
+            unreachable!("argument type is checked by the VM");
+        };
+        Err(NativeError::Exit(status))
+    });
+}
+
+/// Returns the printed form of the value, which is unquoted for strings and has no type suffix
+/// for integers.
+fn output_of(value: &Value) -> String {
+    match value {
+        Value::Str(v) => v.to_string(),
+        v => match v.to_i128() {
+            Some(int) => int.to_string(),
+            None => v.to_string(),
+        },
+    }
+}
+
+fn write_stdout(s: &str) -> Result<Option<Value>, NativeError> {
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(s.as_bytes())
+        .and_then(|_| stdout.flush())
+        .map_err(|e| NativeError::Failed(e.to_string()))?;
+    Ok(None)
+}
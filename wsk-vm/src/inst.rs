@@ -1,13 +1,19 @@
+use std::fmt;
+
 use crate::{
+    native::NativeError,
     program::ProgramParseError,
-    value::{OpError, Value},
+    value::{IntType, OpError, Value},
+    verify::VerifyError,
     vm::{VMError, VM},
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Inst {
     Halt,
     Push(Value),
+    /// pushes the value at the index of the program's constant pool.
+    PushConst(usize),
     Pop,
     Load(usize),
     Store(usize),
@@ -20,15 +26,84 @@ pub enum Inst {
     And,
     Or,
     Cmp(Cmp),
+    /// same as the arithmetic instruction, except that integers wrap around at the bounds of
+    /// their type instead of overflowing.
+    Wrapping(ArithOp),
+    /// same as the arithmetic instruction, except that integers saturate at the bounds of their
+    /// type instead of overflowing.
+    Saturating(ArithOp),
+    BitAnd,
+    BitOr,
+    BitXor,
+    /// pops the amount and the integer, pushing the integer shifted left by the amount. Shifting
+    /// by the width of the type or more pushes 0.
+    Shl,
+    /// pops the amount and the integer, pushing the integer shifted right by the amount, with
+    /// the sign bit of signed integers filling the vacated bits. Shifting by the width of the
+    /// type or more pushes 0, or -1 for negative values.
+    Shr,
 
     Neg,
     Not,
+    BitNot,
+
+    Concat,
+    StrLen,
+    StrIndex,
+
+    /// pops the number of values, pushing a struct with them as fields in the pushed order.
+    MakeStruct(usize),
+    GetField(usize),
+    /// pops the value and the struct, pushing the struct with the field replaced by the value.
+    SetField(usize),
+    /// pops the number of values, pushing a variant with the tag and them as its payload in the
+    /// pushed order.
+    MakeEnum(usize, usize),
+    /// pops the variant, pushing its tag.
+    GetTag,
+    /// pops the number of values, pushing an array with them as elements in the pushed order.
+    MakeArray(usize),
+    /// pops the value, pushing an array with the number of copies of it.
+    RepeatArray(usize),
+    /// pops the index and the array, pushing the element at the index.
+    GetIndex,
+    /// pops the value, the index and the array, pushing the array with the element at the
+    /// index replaced by the value.
+    SetIndex,
+    /// pops the array, pushing a new vector with its elements.
+    ToVec,
+    /// pops the value and the vector, appending the value to the vector.
+    VecPush,
+    /// pops the vector, removing its last element and pushing it.
+    VecPop,
+    /// pops the vector, pushing the number of its elements.
+    VecLen,
+    /// pops the index and the vector, pushing the element at the index.
+    VecGet,
+    /// pops the value, the index and the vector, replacing the element at the index by the
+    /// value.
+    VecSet,
+
+    /// pops the integer, pushing whether it is not zero.
+    IntToBool,
+    /// pops the boolean, pushing 1 if it is `true` and 0 otherwise.
+    BoolToInt,
+    /// pops the integer, pushing it converted to the type. Values which do not fit are
+    /// truncated to its width.
+    IntCast(IntType),
+    /// pops the integer, pushing the nearest float to it.
+    IntToFloat,
+    /// pops the float, pushing it rounded toward zero as an integer of the type. Values which
+    /// do not fit saturate at its bounds, and NaN is 0.
+    FloatToInt(IntType),
 
     Jmp(isize),
     JmpTrue(isize),
     JmpFalse(isize),
 
     Call(usize),
+    /// calls the host function bound to the import at the index of the program.
+    CallNative(usize),
     Ret,
 }
 impl RunInst for Inst {
@@ -36,6 +111,10 @@ impl RunInst for Inst {
         match self {
             Self::Halt => vm.halt(),
             Inst::Push(v) => vm.push(v),
+            Inst::PushConst(idx) => {
+                let v = vm.get_const(idx)?;
+                vm.push(v);
+            }
             Inst::Pop => vm.pop().map(|_| ())?,
             Inst::Load(idx) => {
                 let v = vm.load(idx)?;
@@ -54,9 +133,141 @@ impl RunInst for Inst {
             Inst::And => impl_macros::binary_op!(vm, &),
             Inst::Or => impl_macros::binary_op!(vm, |),
             Inst::Cmp(cmp) => cmp.run(vm)?,
+            Inst::Wrapping(op) => {
+                let rhs = vm.pop()?;
+                let lhs = vm.pop()?;
+                vm.push(match op {
+                    ArithOp::Add => lhs.wrapping_add(rhs)?,
+                    ArithOp::Sub => lhs.wrapping_sub(rhs)?,
+                    ArithOp::Mul => lhs.wrapping_mul(rhs)?,
+                });
+            }
+            Inst::Saturating(op) => {
+                let rhs = vm.pop()?;
+                let lhs = vm.pop()?;
+                vm.push(match op {
+                    ArithOp::Add => lhs.saturating_add(rhs)?,
+                    ArithOp::Sub => lhs.saturating_sub(rhs)?,
+                    ArithOp::Mul => lhs.saturating_mul(rhs)?,
+                });
+            }
+            Inst::BitAnd => impl_macros::binary_fn!(vm, bit_and),
+            Inst::BitOr => impl_macros::binary_fn!(vm, bit_or),
+            Inst::BitXor => impl_macros::binary_fn!(vm, bit_xor),
+            Inst::Shl => impl_macros::binary_fn!(vm, shift_left),
+            Inst::Shr => impl_macros::binary_fn!(vm, shift_right),
 
             Inst::Neg => impl_macros::unary_op!(vm, -),
             Inst::Not => impl_macros::unary_op!(vm, !),
+            Inst::BitNot => {
+                let val = vm.pop()?;
+                vm.push(val.bit_not()?);
+            }
+
+            Inst::Concat => {
+                let rhs = vm.pop()?;
+                let lhs = vm.pop()?;
+                vm.push(lhs.concat(rhs)?);
+            }
+            Inst::StrLen => {
+                let val = vm.pop()?;
+                vm.push(val.str_len()?);
+            }
+            Inst::StrIndex => {
+                let index = vm.pop()?;
+                let val = vm.pop()?;
+                vm.push(val.str_index(index)?);
+            }
+
+            Inst::MakeStruct(cnt) => {
+                let fields = vm.pop_n(cnt)?;
+                vm.push(Value::Struct(fields.into()));
+            }
+            Inst::GetField(idx) => {
+                let val = vm.pop()?;
+                vm.push(val.get_field(idx)?);
+            }
+            Inst::SetField(idx) => {
+                let field = vm.pop()?;
+                let val = vm.pop()?;
+                vm.push(val.set_field(idx, field)?);
+            }
+            Inst::MakeEnum(tag, cnt) => {
+                let payload = vm.pop_n(cnt)?;
+                vm.push(Value::Enum(tag, payload.into()));
+            }
+            Inst::GetTag => {
+                let val = vm.pop()?;
+                vm.push(val.get_tag()?);
+            }
+            Inst::MakeArray(cnt) => {
+                let elements = vm.pop_n(cnt)?;
+                vm.push(Value::Array(elements.into()));
+            }
+            Inst::RepeatArray(cnt) => {
+                let val = vm.pop()?;
+                vm.push(val.repeat(cnt)?);
+            }
+            Inst::GetIndex => {
+                let index = vm.pop()?;
+                let val = vm.pop()?;
+                vm.push(val.get_index(index)?);
+            }
+            Inst::SetIndex => {
+                let element = vm.pop()?;
+                let index = vm.pop()?;
+                let val = vm.pop()?;
+                vm.push(val.set_index(index, element)?);
+            }
+            Inst::ToVec => {
+                let val = vm.pop()?;
+                vm.push(val.to_vec()?);
+            }
+            Inst::VecPush => {
+                let element = vm.pop()?;
+                let val = vm.pop()?;
+                val.vec_push(element)?;
+            }
+            Inst::VecPop => {
+                let val = vm.pop()?;
+                vm.push(val.vec_pop()?);
+            }
+            Inst::VecLen => {
+                let val = vm.pop()?;
+                vm.push(val.vec_len()?);
+            }
+            Inst::VecGet => {
+                let index = vm.pop()?;
+                let val = vm.pop()?;
+                vm.push(val.vec_get(index)?);
+            }
+            Inst::VecSet => {
+                let element = vm.pop()?;
+                let index = vm.pop()?;
+                let val = vm.pop()?;
+                val.vec_set(index, element)?;
+            }
+
+            Inst::IntToBool => {
+                let val = vm.pop()?;
+                vm.push(val.int_to_bool()?);
+            }
+            Inst::BoolToInt => {
+                let val = vm.pop()?;
+                vm.push(val.bool_to_int()?);
+            }
+            Inst::IntCast(ty) => {
+                let val = vm.pop()?;
+                vm.push(val.int_cast(ty)?);
+            }
+            Inst::IntToFloat => {
+                let val = vm.pop()?;
+                vm.push(val.int_to_float()?);
+            }
+            Inst::FloatToInt(ty) => {
+                let val = vm.pop()?;
+                vm.push(val.float_to_int(ty)?);
+            }
 
             Inst::Jmp(offset) => vm.jump(offset),
             Inst::JmpTrue(offset) => {
@@ -76,18 +287,21 @@ impl RunInst for Inst {
                 }
             }
 
-            Inst::Call(fi) => vm.call(fi),
+            Inst::Call(fi) => vm.call(fi)?,
+            Inst::CallNative(idx) => vm.call_native(idx)?,
             Inst::Ret => vm.ret()?,
         };
         Ok(())
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Cmp {
     Equal,
     Less,
     Greater,
+    LessEqual,
+    GreaterEqual,
 }
 impl RunInst for Cmp {
     fn run(self, vm: &mut VM) -> Result<(), RunError> {
@@ -97,17 +311,25 @@ impl RunInst for Cmp {
                 let lhs = vm.pop()?;
                 vm.push((lhs == rhs).into());
             }
-            Cmp::Less | Cmp::Greater => {
+            Cmp::Less | Cmp::Greater | Cmp::LessEqual | Cmp::GreaterEqual => {
                 let rhs = vm.pop()?;
                 let lhs = vm.pop()?;
-                let ord = match (lhs, rhs) {
-                    (Value::Int(lhs), Value::Int(rhs)) => PartialOrd::partial_cmp(&lhs, &rhs),
-                    _ => return Err(OpError::InvalidTypeForOp.into()),
+                // only numbers of the same type are ordered. NaN is unordered with every float,
+                // so each of the comparisons with it is false.
+                let ord = match (&lhs, &rhs) {
+                    (Value::Float(lhs), Value::Float(rhs)) => lhs.partial_cmp(rhs),
+                    _ => match lhs.partial_cmp(&rhs) {
+                        Some(ord) => Some(ord),
+                        None => return Err(OpError::InvalidTypeForOp.into()),
+                    },
                 };
-                let yes = match self {
-                    Cmp::Less => matches!(ord, Some(std::cmp::Ordering::Less)),
-                    Cmp::Greater => matches!(ord, Some(std::cmp::Ordering::Greater)),
-                    _ => unreachable!(),
+                let yes = match (self, ord) {
+                    (_, None) => false,
+                    (Cmp::Less, Some(ord)) => ord.is_lt(),
+                    (Cmp::Greater, Some(ord)) => ord.is_gt(),
+                    (Cmp::LessEqual, Some(ord)) => ord.is_le(),
+                    (Cmp::GreaterEqual, Some(ord)) => ord.is_ge(),
+                    (Cmp::Equal, _) => unreachable!(),
                 };
                 vm.push(yes.into());
             }
@@ -116,11 +338,34 @@ impl RunInst for Cmp {
     }
 }
 
+/// Integer arithmetic with a choice of behavior at the bounds of the type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+}
+impl fmt::Display for ArithOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Add => "add",
+                Self::Sub => "sub",
+                Self::Mul => "mul",
+            }
+        )
+    }
+}
+
 #[derive(Debug)]
 pub enum RunError {
     VMError(VMError),
     OpError(OpError),
     ParseError(ProgramParseError),
+    VerifyError(VerifyError),
+    NativeError(NativeError),
     MissingSourcefile,
 }
 
@@ -144,6 +389,16 @@ impl From<OpError> for RunError {
         Self::OpError(value)
     }
 }
+impl From<NativeError> for RunError {
+    fn from(value: NativeError) -> Self {
+        Self::NativeError(value)
+    }
+}
+impl From<VerifyError> for RunError {
+    fn from(value: VerifyError) -> Self {
+        Self::VerifyError(value)
+    }
+}
 impl From<ProgramParseError> for RunError {
     fn from(value: ProgramParseError) -> Self {
         Self::ParseError(value)
@@ -158,6 +413,13 @@ mod impl_macros {
         $vm.push((lhs $sym rhs)?);
     }};
 }
+    macro_rules! binary_fn {
+        ($vm:expr, $fn:ident) => {{
+            let rhs = $vm.pop()?;
+            let lhs = $vm.pop()?;
+            $vm.push(lhs.$fn(rhs)?);
+        }};
+    }
     macro_rules! unary_op {
     ($vm:expr, $sym:tt) => {{
         let val = $vm.pop()?;
@@ -165,6 +427,7 @@ mod impl_macros {
     }};
 }
 
+    pub(super) use binary_fn;
     pub(super) use binary_op;
     pub(super) use unary_op;
 }
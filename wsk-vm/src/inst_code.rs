@@ -1,13 +1,14 @@
-use std::{io::Read, mem::size_of};
+use std::{cell::RefCell, io::Read, mem::size_of, rc::Rc};
 
 use crate::{
-    inst::{Cmp, Inst},
+    inst::{ArithOp, Cmp, Inst},
+    native::ValueType,
     program::ProgramParseError,
-    value::Value,
+    value::{IntType, Value},
 };
 
 impl Inst {
-    pub fn encode(self, out: &mut Vec<u8>) {
+    pub fn encode(&self, out: &mut Vec<u8>) {
         match self {
             Inst::Halt => out.push(0x00),
             Inst::Push(value) => match value {
@@ -19,7 +20,19 @@ impl Inst {
                     true => out.push(0x02),
                     false => out.push(0x03),
                 },
+                Value::Str(v) => {
+                    out.push(0x07);
+                    encode_str(v, out);
+                }
+                _ => {
+                    out.push(0x09);
+                    value.encode(out);
+                }
             },
+            Inst::PushConst(i) => {
+                out.push(0x08);
+                out.extend(i.to_le_bytes());
+            }
             Inst::Pop => out.push(0x04),
             Inst::Load(i) => {
                 out.push(0x05);
@@ -40,9 +53,75 @@ impl Inst {
                 Cmp::Equal => out.push(0x17),
                 Cmp::Less => out.push(0x18),
                 Cmp::Greater => out.push(0x19),
+                Cmp::LessEqual => out.push(0x1a),
+                Cmp::GreaterEqual => out.push(0x1b),
+            },
+            Inst::Wrapping(op) => match op {
+                ArithOp::Add => out.push(0x22),
+                ArithOp::Sub => out.push(0x23),
+                ArithOp::Mul => out.push(0x24),
+            },
+            Inst::Saturating(op) => match op {
+                ArithOp::Add => out.push(0x25),
+                ArithOp::Sub => out.push(0x26),
+                ArithOp::Mul => out.push(0x27),
             },
+            Inst::BitAnd => out.push(0x28),
+            Inst::BitOr => out.push(0x29),
+            Inst::BitXor => out.push(0x2a),
+            Inst::Shl => out.push(0x2b),
+            Inst::Shr => out.push(0x2c),
             Inst::Neg => out.push(0x20),
             Inst::Not => out.push(0x21),
+            Inst::BitNot => out.push(0x2d),
+            Inst::Concat => out.push(0x50),
+            Inst::StrLen => out.push(0x51),
+            Inst::StrIndex => out.push(0x52),
+            Inst::MakeStruct(cnt) => {
+                out.push(0x60);
+                out.extend(cnt.to_le_bytes());
+            }
+            Inst::GetField(idx) => {
+                out.push(0x61);
+                out.extend(idx.to_le_bytes());
+            }
+            Inst::SetField(idx) => {
+                out.push(0x62);
+                out.extend(idx.to_le_bytes());
+            }
+            Inst::MakeEnum(tag, cnt) => {
+                out.push(0x63);
+                out.extend(tag.to_le_bytes());
+                out.extend(cnt.to_le_bytes());
+            }
+            Inst::GetTag => out.push(0x64),
+            Inst::MakeArray(cnt) => {
+                out.push(0x65);
+                out.extend(cnt.to_le_bytes());
+            }
+            Inst::RepeatArray(cnt) => {
+                out.push(0x66);
+                out.extend(cnt.to_le_bytes());
+            }
+            Inst::GetIndex => out.push(0x67),
+            Inst::SetIndex => out.push(0x68),
+            Inst::ToVec => out.push(0x69),
+            Inst::VecPush => out.push(0x6a),
+            Inst::VecPop => out.push(0x6b),
+            Inst::VecLen => out.push(0x6c),
+            Inst::VecGet => out.push(0x6d),
+            Inst::VecSet => out.push(0x6e),
+            Inst::IntToBool => out.push(0x70),
+            Inst::BoolToInt => out.push(0x71),
+            Inst::IntCast(ty) => {
+                out.push(0x72);
+                ty.encode(out);
+            }
+            Inst::IntToFloat => out.push(0x73),
+            Inst::FloatToInt(ty) => {
+                out.push(0x74);
+                ty.encode(out);
+            }
             Inst::Jmp(offset) => {
                 out.push(0x30);
                 out.extend(offset.to_le_bytes());
@@ -59,18 +138,16 @@ impl Inst {
                 out.push(0x40);
                 out.extend(fi.to_le_bytes());
             }
+            Inst::CallNative(idx) => {
+                out.push(0x42);
+                out.extend(idx.to_le_bytes());
+            }
             Inst::Ret => out.push(0x41),
         }
     }
 
-    fn next_bytes<const N: usize>(it: &mut &[u8]) -> Result<[u8; N], ProgramParseError>
-    where
-        [u8; N]: Default,
-    {
-        let mut le_bytes: [u8; N] = Default::default();
-        it.read(&mut le_bytes)
-            .map_err(|_| ProgramParseError::InsufficientBytes)?;
-        Ok(le_bytes)
+    fn next_bytes<const N: usize>(it: &mut &[u8]) -> Result<[u8; N], ProgramParseError> {
+        read_bytes(it)
     }
 
     pub fn decode(bytes: &mut &[u8]) -> Result<Self, ProgramParseError> {
@@ -78,10 +155,7 @@ impl Inst {
         const ISIZE_BYTES: usize = size_of::<isize>();
         const I64_BYTES: usize = size_of::<i64>();
 
-        let mut byte: [u8; 1] = [0];
-        bytes
-            .read(&mut byte)
-            .map_err(|_| ProgramParseError::InsufficientBytes)?;
+        let byte = read_bytes::<1>(bytes)?;
         Ok(match byte[0] {
             0x00 => Inst::Halt,
             0x01 => {
@@ -91,6 +165,12 @@ impl Inst {
             0x02 => Inst::Push(true.into()),
             0x03 => Inst::Push(false.into()),
             0x04 => Inst::Pop,
+            0x07 => Inst::Push(Value::Str(decode_str(bytes)?)),
+            0x09 => Inst::Push(Value::decode(bytes)?),
+            0x08 => {
+                let index_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                Inst::PushConst(usize::from_le_bytes(index_bytes))
+            }
             0x05 => {
                 let index_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
                 Inst::Load(usize::from_le_bytes(index_bytes))
@@ -109,8 +189,67 @@ impl Inst {
             0x17 => Cmp::Equal.into(),
             0x18 => Cmp::Less.into(),
             0x19 => Cmp::Greater.into(),
+            0x1a => Cmp::LessEqual.into(),
+            0x1b => Cmp::GreaterEqual.into(),
+            0x22 => Inst::Wrapping(ArithOp::Add),
+            0x23 => Inst::Wrapping(ArithOp::Sub),
+            0x24 => Inst::Wrapping(ArithOp::Mul),
+            0x25 => Inst::Saturating(ArithOp::Add),
+            0x26 => Inst::Saturating(ArithOp::Sub),
+            0x27 => Inst::Saturating(ArithOp::Mul),
+            0x28 => Inst::BitAnd,
+            0x29 => Inst::BitOr,
+            0x2a => Inst::BitXor,
+            0x2b => Inst::Shl,
+            0x2c => Inst::Shr,
             0x20 => Inst::Neg,
             0x21 => Inst::Not,
+            0x2d => Inst::BitNot,
+            0x50 => Inst::Concat,
+            0x51 => Inst::StrLen,
+            0x52 => Inst::StrIndex,
+            0x60 => {
+                let cnt_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                Inst::MakeStruct(usize::from_le_bytes(cnt_bytes))
+            }
+            0x61 => {
+                let index_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                Inst::GetField(usize::from_le_bytes(index_bytes))
+            }
+            0x62 => {
+                let index_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                Inst::SetField(usize::from_le_bytes(index_bytes))
+            }
+            0x63 => {
+                let tag_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                let cnt_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                Inst::MakeEnum(
+                    usize::from_le_bytes(tag_bytes),
+                    usize::from_le_bytes(cnt_bytes),
+                )
+            }
+            0x64 => Inst::GetTag,
+            0x65 => {
+                let cnt_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                Inst::MakeArray(usize::from_le_bytes(cnt_bytes))
+            }
+            0x66 => {
+                let cnt_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                Inst::RepeatArray(usize::from_le_bytes(cnt_bytes))
+            }
+            0x67 => Inst::GetIndex,
+            0x68 => Inst::SetIndex,
+            0x69 => Inst::ToVec,
+            0x6a => Inst::VecPush,
+            0x6b => Inst::VecPop,
+            0x6c => Inst::VecLen,
+            0x6d => Inst::VecGet,
+            0x6e => Inst::VecSet,
+            0x70 => Inst::IntToBool,
+            0x71 => Inst::BoolToInt,
+            0x72 => Inst::IntCast(IntType::decode(bytes)?),
+            0x73 => Inst::IntToFloat,
+            0x74 => Inst::FloatToInt(IntType::decode(bytes)?),
             0x30 => {
                 let index_bytes = Self::next_bytes::<ISIZE_BYTES>(bytes)?;
                 Inst::Jmp(isize::from_le_bytes(index_bytes))
@@ -128,9 +267,241 @@ impl Inst {
                 Inst::Call(usize::from_le_bytes(index_bytes))
             }
             0x41 => Inst::Ret,
-            _ => {
-                unimplemented!("unimplemented inst byte {:#04x}", byte[0]);
+            0x42 => {
+                let index_bytes = Self::next_bytes::<USIZE_BYTES>(bytes)?;
+                Inst::CallNative(usize::from_le_bytes(index_bytes))
+            }
+            opcode => return Err(ProgramParseError::UnknownOpcode(opcode)),
+        })
+    }
+}
+
+impl Value {
+    /// Encodes the value as an entry of the constant pool.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            Value::Int(v) => {
+                out.push(0x01);
+                out.extend(v.to_le_bytes());
+            }
+            Value::I8(v) => {
+                out.push(0x09);
+                out.extend(v.to_le_bytes());
+            }
+            Value::I16(v) => {
+                out.push(0x0a);
+                out.extend(v.to_le_bytes());
+            }
+            Value::I32(v) => {
+                out.push(0x0b);
+                out.extend(v.to_le_bytes());
+            }
+            Value::U8(v) => {
+                out.push(0x0c);
+                out.extend(v.to_le_bytes());
+            }
+            Value::U16(v) => {
+                out.push(0x0d);
+                out.extend(v.to_le_bytes());
+            }
+            Value::U32(v) => {
+                out.push(0x0e);
+                out.extend(v.to_le_bytes());
+            }
+            Value::U64(v) => {
+                out.push(0x0f);
+                out.extend(v.to_le_bytes());
+            }
+            Value::Float(v) => {
+                out.push(0x10);
+                out.extend(v.to_le_bytes());
+            }
+            Value::Bool(v) => out.push(if *v { 0x02 } else { 0x03 }),
+            Value::Str(v) => {
+                out.push(0x04);
+                encode_str(v, out);
+            }
+            Value::Struct(fields) => {
+                out.push(0x05);
+                out.extend((fields.len() as u64).to_le_bytes());
+                for field in fields.iter() {
+                    field.encode(out);
+                }
+            }
+            Value::Enum(tag, payload) => {
+                out.push(0x06);
+                out.extend((*tag as u64).to_le_bytes());
+                out.extend((payload.len() as u64).to_le_bytes());
+                for value in payload.iter() {
+                    value.encode(out);
+                }
+            }
+            Value::Array(elements) => {
+                out.push(0x07);
+                out.extend((elements.len() as u64).to_le_bytes());
+                for element in elements.iter() {
+                    element.encode(out);
+                }
+            }
+            Value::Vec(elements) => {
+                let elements = elements.borrow();
+                out.push(0x08);
+                out.extend((elements.len() as u64).to_le_bytes());
+                for element in elements.iter() {
+                    element.encode(out);
+                }
+            }
+        }
+    }
+
+    pub fn decode(bytes: &mut &[u8]) -> Result<Self, ProgramParseError> {
+        let mut tag: [u8; 1] = [0];
+        bytes
+            .read_exact(&mut tag)
+            .map_err(|_| ProgramParseError::InsufficientBytes)?;
+        Ok(match tag[0] {
+            0x01 => {
+                let mut int_bytes = [0; size_of::<i64>()];
+                bytes
+                    .read_exact(&mut int_bytes)
+                    .map_err(|_| ProgramParseError::InsufficientBytes)?;
+                Value::Int(i64::from_le_bytes(int_bytes))
+            }
+            0x02 => Value::Bool(true),
+            0x03 => Value::Bool(false),
+            0x04 => Value::Str(decode_str(bytes)?),
+            0x05 => {
+                let cnt = u64::from_le_bytes(read_bytes(bytes)?);
+                let mut fields = Vec::new();
+                for _ in 0..cnt {
+                    fields.push(Value::decode(bytes)?);
+                }
+                Value::Struct(fields.into())
+            }
+            0x06 => {
+                let tag = u64::from_le_bytes(read_bytes(bytes)?);
+                let cnt = u64::from_le_bytes(read_bytes(bytes)?);
+                let mut payload = Vec::new();
+                for _ in 0..cnt {
+                    payload.push(Value::decode(bytes)?);
+                }
+                Value::Enum(tag as usize, payload.into())
             }
+            0x07 => {
+                let cnt = u64::from_le_bytes(read_bytes(bytes)?);
+                let mut elements = Vec::new();
+                for _ in 0..cnt {
+                    elements.push(Value::decode(bytes)?);
+                }
+                Value::Array(elements.into())
+            }
+            0x08 => {
+                let cnt = u64::from_le_bytes(read_bytes(bytes)?);
+                let mut elements = Vec::new();
+                for _ in 0..cnt {
+                    elements.push(Value::decode(bytes)?);
+                }
+                Value::Vec(Rc::new(RefCell::new(elements)))
+            }
+            0x09 => Value::I8(i8::from_le_bytes(read_bytes(bytes)?)),
+            0x0a => Value::I16(i16::from_le_bytes(read_bytes(bytes)?)),
+            0x0b => Value::I32(i32::from_le_bytes(read_bytes(bytes)?)),
+            0x0c => Value::U8(u8::from_le_bytes(read_bytes(bytes)?)),
+            0x0d => Value::U16(u16::from_le_bytes(read_bytes(bytes)?)),
+            0x0e => Value::U32(u32::from_le_bytes(read_bytes(bytes)?)),
+            0x0f => Value::U64(u64::from_le_bytes(read_bytes(bytes)?)),
+            0x10 => Value::Float(f64::from_le_bytes(read_bytes(bytes)?)),
+            _ => return Err(ProgramParseError::InvalidConstant),
         })
     }
 }
+
+pub(crate) fn read_bytes<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N], ProgramParseError> {
+    let mut buf = [0; N];
+    bytes
+        .read_exact(&mut buf)
+        .map_err(|_| ProgramParseError::InsufficientBytes)?;
+    Ok(buf)
+}
+
+impl IntType {
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.push(match self {
+            IntType::Int => 0x00,
+            IntType::I8 => 0x01,
+            IntType::I16 => 0x02,
+            IntType::I32 => 0x03,
+            IntType::U8 => 0x04,
+            IntType::U16 => 0x05,
+            IntType::U32 => 0x06,
+            IntType::U64 => 0x07,
+        });
+    }
+
+    pub fn decode(bytes: &mut &[u8]) -> Result<Self, ProgramParseError> {
+        let byte = read_bytes::<1>(bytes)?[0];
+        IntType::ALL
+            .get(byte as usize)
+            .copied()
+            .ok_or(ProgramParseError::InvalidIntType(byte))
+    }
+}
+
+impl ValueType {
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        out.push(match self {
+            ValueType::Int(IntType::Int) => 0x01,
+            ValueType::Bool => 0x02,
+            ValueType::Str => 0x03,
+            ValueType::Int(IntType::I8) => 0x04,
+            ValueType::Int(IntType::I16) => 0x05,
+            ValueType::Int(IntType::I32) => 0x06,
+            ValueType::Int(IntType::U8) => 0x07,
+            ValueType::Int(IntType::U16) => 0x08,
+            ValueType::Int(IntType::U32) => 0x09,
+            ValueType::Int(IntType::U64) => 0x0a,
+            ValueType::Float => 0x0b,
+        });
+    }
+
+    /// Decodes the optional type, where `0x00` stands for no type.
+    pub fn decode_opt(bytes: &mut &[u8]) -> Result<Option<Self>, ProgramParseError> {
+        Ok(Some(match read_bytes::<1>(bytes)?[0] {
+            0x00 => return Ok(None),
+            0x01 => ValueType::Int(IntType::Int),
+            0x02 => ValueType::Bool,
+            0x03 => ValueType::Str,
+            0x04 => ValueType::Int(IntType::I8),
+            0x05 => ValueType::Int(IntType::I16),
+            0x06 => ValueType::Int(IntType::I32),
+            0x07 => ValueType::Int(IntType::U8),
+            0x08 => ValueType::Int(IntType::U16),
+            0x09 => ValueType::Int(IntType::U32),
+            0x0a => ValueType::Int(IntType::U64),
+            0x0b => ValueType::Float,
+            _ => return Err(ProgramParseError::InvalidImport),
+        }))
+    }
+}
+
+/// Encodes the string as its byte length followed by its UTF-8 bytes.
+pub(crate) fn encode_str(s: &str, out: &mut Vec<u8>) {
+    out.extend((s.len() as u64).to_le_bytes());
+    out.extend(s.as_bytes());
+}
+
+pub(crate) fn decode_str(bytes: &mut &[u8]) -> Result<Rc<str>, ProgramParseError> {
+    let mut len_bytes = [0; size_of::<u64>()];
+    bytes
+        .read_exact(&mut len_bytes)
+        .map_err(|_| ProgramParseError::InsufficientBytes)?;
+    let len = u64::from_le_bytes(len_bytes) as usize;
+    if bytes.len() < len {
+        return Err(ProgramParseError::InsufficientBytes);
+    }
+    let (str_bytes, rest) = bytes.split_at(len);
+    *bytes = rest;
+    std::str::from_utf8(str_bytes)
+        .map(Rc::from)
+        .map_err(|_| ProgramParseError::InvalidConstant)
+}
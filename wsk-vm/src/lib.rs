@@ -1,7 +1,11 @@
+pub mod asm;
+pub mod builtins;
 pub mod inst;
 pub mod inst_code;
+pub mod native;
 pub mod program;
 pub mod value;
+pub mod verify;
 pub mod vm;
 
 pub use inst::{Cmp, Inst, RunError};
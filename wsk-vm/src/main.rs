@@ -1,27 +1,39 @@
 use std::env;
 use std::fs;
+use std::process::ExitCode;
 
+use wsk_vm::builtins::register_builtins;
 use wsk_vm::program::Program;
+use wsk_vm::verify::verify;
 use wsk_vm::RunError;
 use wsk_vm::VM;
 
-fn main() -> Result<(), RunError> {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
+/// Runs the program, exiting with the status returned by its `main` or given to `exit`.
+/// With `--dump`, the program and the final VM state are printed to stderr.
+fn main() -> Result<ExitCode, RunError> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let dump = args.iter().any(|v| v == "--dump");
+    let Some(path) = args.iter().find(|v| *v != "--dump") else {
         return Err(RunError::MissingSourcefile);
-    }
+    };
 
-    let bytes = fs::read(args[1].clone()).unwrap();
+    let bytes = fs::read(path).unwrap();
     let program = Program::from_bytes(&bytes)?;
-    println!("{}", program);
+    verify(&program)?;
+    if dump {
+        eprintln!("{}", program);
+    }
 
     let mut vm = VM::default();
-    vm.execute(program).map_err(|e| {
+    register_builtins(&mut vm);
+    vm.execute(program).inspect_err(|_| {
         eprintln!("{:#?}", vm);
-        e
     })?;
 
-    println!("{:#?}", vm);
+    if dump {
+        eprintln!("{:#?}", vm);
+    }
 
-    Ok(())
+    // statuses out of the range of the platform are truncated, as with `exit` in C.
+    Ok(ExitCode::from(vm.exit_status() as u8))
 }
@@ -0,0 +1,103 @@
+//! Host functions callable from programs.
+//!
+//! Programs declare the host functions they use as imports, each with a name and a signature.
+//! Embedders register the implementations on the [`VM`](crate::VM), and imports are bound to them
+//! by name when the program is executed.
+
+use std::fmt;
+
+use crate::value::{IntType, Value};
+
+/// Runtime type of a value crossing the host boundary.
+/// Structs, enums, arrays and vectors cannot be passed to or returned from host functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    Int(IntType),
+    Float,
+    Bool,
+    Str,
+}
+impl ValueType {
+    pub fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Float(_) => Some(Self::Float),
+            Value::Bool(_) => Some(Self::Bool),
+            Value::Str(_) => Some(Self::Str),
+            Value::Struct(_) | Value::Enum(..) | Value::Array(_) | Value::Vec(_) => None,
+            _ => value.int_type().map(Self::Int),
+        }
+    }
+}
+impl fmt::Display for ValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueType::Int(ty) => write!(f, "{}", ty),
+            ValueType::Float => write!(f, "float"),
+            ValueType::Bool => write!(f, "bool"),
+            ValueType::Str => write!(f, "str"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NativeSig {
+    pub params: Vec<ValueType>,
+    /// `None` if the function returns no value.
+    pub ret: Option<ValueType>,
+}
+impl NativeSig {
+    pub fn new(params: impl IntoIterator<Item = ValueType>, ret: Option<ValueType>) -> Self {
+        Self {
+            params: Vec::from_iter(params),
+            ret,
+        }
+    }
+}
+impl fmt::Display for NativeSig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params: Vec<String> = self.params.iter().map(|v| v.to_string()).collect();
+        write!(f, "({})", params.join(", "))?;
+        if let Some(ret) = self.ret {
+            write!(f, " -> {}", ret)?;
+        }
+        Ok(())
+    }
+}
+
+/// A host function the program expects to be registered on the VM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    pub name: String,
+    pub sig: NativeSig,
+}
+impl Import {
+    pub fn new(name: impl Into<String>, sig: NativeSig) -> Self {
+        Self {
+            name: name.into(),
+            sig,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum NativeError {
+    /// the host function failed with the message.
+    Failed(String),
+    /// stops the program with the exit status, rather than failing.
+    Exit(i64),
+}
+
+/// Implementation of a host function. The arguments match the types of its signature, and the
+/// returned value must match its return type.
+pub type NativeFn = Box<dyn FnMut(&[Value]) -> Result<Option<Value>, NativeError>>;
+
+pub struct NativeFunc {
+    pub name: String,
+    pub sig: NativeSig,
+    pub func: NativeFn,
+}
+impl fmt::Debug for NativeFunc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.name, self.sig)
+    }
+}
@@ -1,9 +1,48 @@
-use std::{fmt::Display, io::Read, mem::size_of};
-
-use crate::{Cmp, Inst};
+use std::fmt::{self, Display};
+
+use crate::{
+    asm::disassemble,
+    inst_code::{decode_str, encode_str, read_bytes},
+    native::{Import, NativeSig, ValueType},
+    Inst, Value,
+};
+
+/// Leading bytes of every program binary.
+pub const MAGIC: [u8; 4] = *b"WSKC";
+/// Version of the binary format, bumped on every incompatible change.
+pub const FORMAT_VERSION: u16 = 3;
+
+/// magic, version, reserved, checksum, entry point and section count.
+const HEADER_SIZE: usize = 4 + 2 + 2 + 4 + 8 + 4;
+/// kind, offset and length.
+const SECTION_ENTRY_SIZE: usize = 4 + 8 + 8;
+
+/// Sections of the program binary, located by the section table after the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionKind {
+    Code = 1,
+    Consts = 2,
+    /// function names for disassembly and debugging.
+    Names = 3,
+    /// host functions the program calls.
+    Imports = 4,
+}
+impl SectionKind {
+    fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(Self::Code),
+            2 => Some(Self::Consts),
+            3 => Some(Self::Names),
+            4 => Some(Self::Imports),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Program {
+    consts: Vec<Value>,
+    imports: Vec<Import>,
     funcs: Vec<Function>,
     entry_point: usize,
 }
@@ -15,33 +54,112 @@ impl Default for Program {
 impl Program {
     pub fn new(entry_point: usize) -> Self {
         Self {
+            consts: vec![],
+            imports: vec![],
             funcs: vec![],
             entry_point,
         }
     }
 
-    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, ProgramParseError> {
-        const U64_SIZE: usize = size_of::<u64>();
-        let (fn_cnt, entry_fi) = {
-            let mut header_bytes: [u8; U64_SIZE * 2] = Default::default();
-            bytes
-                .read(&mut header_bytes)
-                .map_err(|_| ProgramParseError::InsufficientBytes)?;
-            (
-                u64::from_le_bytes(header_bytes[0..U64_SIZE].try_into().unwrap()),
-                u64::from_le_bytes(header_bytes[U64_SIZE..].try_into().unwrap()),
-            )
-        };
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramParseError> {
+        let mut header = bytes;
+        if read_bytes::<4>(&mut header)? != MAGIC {
+            return Err(ProgramParseError::BadMagic);
+        }
+        let version = u16::from_le_bytes(read_bytes(&mut header)?);
+        if version != FORMAT_VERSION {
+            return Err(ProgramParseError::UnsupportedVersion(version));
+        }
+        let _reserved = read_bytes::<2>(&mut header)?;
+        let checksum = u32::from_le_bytes(read_bytes(&mut header)?);
+        if checksum != crc32(header) {
+            return Err(ProgramParseError::ChecksumMismatch);
+        }
+
+        let entry_fi = u64::from_le_bytes(read_bytes(&mut header)?);
+        let section_cnt = u32::from_le_bytes(read_bytes(&mut header)?);
+
+        let mut prog = Self::new(entry_fi as usize);
+        let mut has_code = false;
+        for _ in 0..section_cnt {
+            let kind = u32::from_le_bytes(read_bytes(&mut header)?);
+            let offset = u64::from_le_bytes(read_bytes(&mut header)?) as usize;
+            let len = u64::from_le_bytes(read_bytes(&mut header)?) as usize;
+            // sections unknown to this version carry optional data, so they are skipped.
+            let Some(kind) = SectionKind::from_u32(kind) else {
+                continue;
+            };
+            let mut data = offset
+                .checked_add(len)
+                .and_then(|end| bytes.get(offset..end))
+                .ok_or(ProgramParseError::TruncatedSection(kind))?;
+
+            let result = match kind {
+                SectionKind::Code => {
+                    has_code = true;
+                    prog.decode_code(&mut data)
+                }
+                SectionKind::Consts => prog.decode_consts(&mut data),
+                SectionKind::Names => prog.decode_names(&mut data),
+                SectionKind::Imports => prog.decode_imports(&mut data),
+            };
+            result.map_err(|e| match e {
+                ProgramParseError::InsufficientBytes => ProgramParseError::TruncatedSection(kind),
+                e => e,
+            })?;
+        }
+
+        if !has_code {
+            return Err(ProgramParseError::MissingSection(SectionKind::Code));
+        }
+        Ok(prog)
+    }
 
-        let mut funcs = Vec::new();
+    fn decode_code(&mut self, bytes: &mut &[u8]) -> Result<(), ProgramParseError> {
+        let fn_cnt = u64::from_le_bytes(read_bytes(bytes)?);
         for _ in 0..fn_cnt {
-            funcs.push(Function::from_bytes(&mut bytes)?);
+            self.funcs.push(Function::from_bytes(bytes)?);
         }
+        Ok(())
+    }
 
-        Ok(Self {
-            funcs,
-            entry_point: entry_fi as usize,
-        })
+    fn decode_consts(&mut self, bytes: &mut &[u8]) -> Result<(), ProgramParseError> {
+        let const_cnt = u64::from_le_bytes(read_bytes(bytes)?);
+        for _ in 0..const_cnt {
+            self.consts.push(Value::decode(bytes)?);
+        }
+        Ok(())
+    }
+
+    fn decode_imports(&mut self, bytes: &mut &[u8]) -> Result<(), ProgramParseError> {
+        let import_cnt = u64::from_le_bytes(read_bytes(bytes)?);
+        for _ in 0..import_cnt {
+            let name = decode_str(bytes)?;
+            let param_cnt = u64::from_le_bytes(read_bytes(bytes)?);
+            let mut params = Vec::new();
+            for _ in 0..param_cnt {
+                params.push(ValueType::decode_opt(bytes)?.ok_or(ProgramParseError::InvalidImport)?);
+            }
+            let ret = ValueType::decode_opt(bytes)?;
+            self.imports
+                .push(Import::new(&*name, NativeSig::new(params, ret)));
+        }
+        Ok(())
+    }
+
+    /// Decodes the function names. It must come after the code section.
+    fn decode_names(&mut self, bytes: &mut &[u8]) -> Result<(), ProgramParseError> {
+        let name_cnt = u64::from_le_bytes(read_bytes(bytes)?);
+        for _ in 0..name_cnt {
+            let fi = u64::from_le_bytes(read_bytes(bytes)?) as usize;
+            let name = decode_str(bytes)?;
+            let func = self
+                .funcs
+                .get_mut(fi)
+                .ok_or(ProgramParseError::InvalidFunctionName)?;
+            func.set_name(&*name);
+        }
+        Ok(())
     }
 
     pub fn set_entry_point(&mut self, index: usize) {
@@ -49,12 +167,52 @@ impl Program {
         self.entry_point = index;
     }
 
+    /// Adds the value to the constant pool, returning its index.
+    /// Equal values share the same entry.
+    pub fn add_const(&mut self, value: Value) -> usize {
+        if let Some(idx) = self.consts.iter().position(|v| *v == value) {
+            return idx;
+        }
+        self.consts.push(value);
+        self.consts.len() - 1
+    }
+
+    pub fn get_const(&self, index: usize) -> Option<&Value> {
+        self.consts.get(index)
+    }
+
+    pub fn get_consts(&self) -> &[Value] {
+        &self.consts
+    }
+
+    /// Adds the import, returning its index.
+    /// Imports with the same name share the same entry.
+    pub fn add_import(&mut self, import: Import) -> usize {
+        if let Some(idx) = self.imports.iter().position(|v| v.name == import.name) {
+            return idx;
+        }
+        self.imports.push(import);
+        self.imports.len() - 1
+    }
+
+    pub fn get_import(&self, index: usize) -> Option<&Import> {
+        self.imports.get(index)
+    }
+
+    pub fn get_imports(&self) -> &[Import] {
+        &self.imports
+    }
+
     pub fn add_func(&mut self, func: Function) -> usize {
         let id = self.funcs.len();
         self.funcs.push(func);
         id
     }
 
+    pub fn get_funcs(&self) -> &[Function] {
+        &self.funcs
+    }
+
     pub fn get(&self, index: usize) -> Option<&Function> {
         self.funcs.get(index)
     }
@@ -68,52 +226,131 @@ impl Program {
     }
 
     pub fn to_bin(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
+        let mut code = Vec::new();
+        code.extend((self.funcs.len() as u64).to_le_bytes());
+        for func in &self.funcs {
+            func.to_bin(&mut code);
+        }
 
-        // func count
-        bytes.extend((self.funcs.len() as u64).to_le_bytes());
+        let mut consts = Vec::new();
+        consts.extend((self.consts.len() as u64).to_le_bytes());
+        for value in &self.consts {
+            value.encode(&mut consts);
+        }
 
-        // entry fi
-        bytes.extend((self.entry_point as u64).to_le_bytes());
+        let mut names = Vec::new();
+        let named_funcs: Vec<_> = self
+            .funcs
+            .iter()
+            .enumerate()
+            .filter_map(|(fi, func)| Some((fi, func.get_name()?)))
+            .collect();
+        names.extend((named_funcs.len() as u64).to_le_bytes());
+        for (fi, name) in named_funcs {
+            names.extend((fi as u64).to_le_bytes());
+            encode_str(name, &mut names);
+        }
 
-        for func in &self.funcs {
-            func.to_bin(&mut bytes);
+        let mut imports = Vec::new();
+        imports.extend((self.imports.len() as u64).to_le_bytes());
+        for import in &self.imports {
+            encode_str(&import.name, &mut imports);
+            imports.extend((import.sig.params.len() as u64).to_le_bytes());
+            for param in &import.sig.params {
+                param.encode(&mut imports);
+            }
+            match import.sig.ret {
+                Some(ret) => ret.encode(&mut imports),
+                None => imports.push(0x00),
+            }
         }
 
+        let sections = [
+            (SectionKind::Code, code),
+            (SectionKind::Consts, consts),
+            (SectionKind::Names, names),
+            (SectionKind::Imports, imports),
+        ];
+
+        // the checksum covers everything after itself.
+        let mut body = Vec::new();
+        body.extend((self.entry_point as u64).to_le_bytes());
+        body.extend((sections.len() as u32).to_le_bytes());
+        let mut offset = HEADER_SIZE + sections.len() * SECTION_ENTRY_SIZE;
+        for (kind, data) in &sections {
+            body.extend((*kind as u32).to_le_bytes());
+            body.extend((offset as u64).to_le_bytes());
+            body.extend((data.len() as u64).to_le_bytes());
+            offset += data.len();
+        }
+        for (_, data) in &sections {
+            body.extend(data);
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend(MAGIC);
+        bytes.extend(FORMAT_VERSION.to_le_bytes());
+        bytes.extend([0; 2]);
+        bytes.extend(crc32(&body).to_le_bytes());
+        bytes.extend(body);
         bytes
     }
 }
 
 impl Display for Program {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "entry: ${}\n", self.entry_point)?;
-        for (i, func) in self.funcs.iter().enumerate() {
-            writeln!(f, "func ${}:\n{}", i, func)?;
-        }
-        Ok(())
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", disassemble(self))
+    }
+}
+
+/// Number of values a function takes from and leaves on the caller's stack.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Arity {
+    pub params: usize,
+    pub rets: usize,
+}
+impl Arity {
+    pub fn new(params: usize, rets: usize) -> Self {
+        Self { params, rets }
     }
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct Function {
+    /// name of the function for disassembly, it has no effect on execution.
+    name: Option<String>,
+    arity: Arity,
     insts: Vec<Inst>,
 }
 impl Function {
     pub fn from_insts(insts: impl IntoIterator<Item = Inst>) -> Self {
         Self {
+            name: None,
+            arity: Arity::default(),
             insts: Vec::from_iter(insts),
         }
     }
 
+    pub fn set_arity(&mut self, arity: Arity) {
+        self.arity = arity;
+    }
+
+    pub fn get_arity(&self) -> Arity {
+        self.arity
+    }
+
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = Some(name.into());
+    }
+
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     pub fn from_bytes(bytes: &mut &[u8]) -> Result<Self, ProgramParseError> {
-        const U64_SIZE: usize = size_of::<u64>();
-        let inst_cnt = {
-            let mut header_bytes: [u8; U64_SIZE] = Default::default();
-            bytes
-                .read(&mut header_bytes)
-                .map_err(|_| ProgramParseError::InsufficientBytes)?;
-            u64::from_le_bytes(header_bytes)
-        };
+        let params = u64::from_le_bytes(read_bytes(bytes)?) as usize;
+        let rets = u64::from_le_bytes(read_bytes(bytes)?) as usize;
+        let inst_cnt = u64::from_le_bytes(read_bytes(bytes)?);
 
         let mut insts = Vec::new();
 
@@ -122,7 +359,11 @@ impl Function {
             insts.push(inst);
         }
 
-        Ok(Self { insts })
+        Ok(Self {
+            name: None,
+            arity: Arity::new(params, rets),
+            insts,
+        })
     }
 
     pub fn push_inst(&mut self, inst: impl Into<Inst>) {
@@ -137,6 +378,11 @@ impl Function {
         self.insts.insert(idx, inst.into());
     }
 
+    /// Replaces the instruction, such as a jump whose target was not known when it was pushed.
+    pub fn set_inst(&mut self, idx: usize, inst: impl Into<Inst>) {
+        self.insts[idx] = inst.into();
+    }
+
     pub fn len(&self) -> usize {
         self.insts.len()
     }
@@ -154,6 +400,9 @@ impl Function {
     }
 
     pub fn to_bin(&self, out: &mut Vec<u8>) {
+        out.extend((self.arity.params as u64).to_le_bytes());
+        out.extend((self.arity.rets as u64).to_le_bytes());
+
         // inst count
         out.extend((self.insts.len() as u64).to_le_bytes());
 
@@ -162,46 +411,59 @@ impl Function {
         }
     }
 }
-impl Display for Function {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (i, inst) in self.insts.iter().enumerate() {
-            let inst_str = match inst {
-                Inst::Halt => "halt".to_owned(),
-                Inst::Push(value) => format!("push\t\t{}", value),
-                Inst::Pop => "pop".to_owned(),
-                Inst::Load(offset) => format!("load\t\tr{}", offset),
-                Inst::Store(offset) => format!("store\t\tr{}", offset),
-                Inst::Add => "add".to_owned(),
-                Inst::Sub => "sub".to_owned(),
-                Inst::Mul => "mul".to_owned(),
-                Inst::Div => "div".to_owned(),
-                Inst::Mod => "mod".to_owned(),
-                Inst::And => "and".to_owned(),
-                Inst::Or => "or".to_owned(),
-                Inst::Cmp(cmp) => format!(
-                    "cmp\t\t{}",
-                    match cmp {
-                        Cmp::Equal => "equ",
-                        Cmp::Less => "lt",
-                        Cmp::Greater => "gt",
-                    }
-                ),
-                Inst::Neg => "neg".to_owned(),
-                Inst::Not => "not".to_owned(),
-                Inst::Jmp(offset) => format!("jmp\t\t{}:", i.wrapping_add_signed(*offset)),
-                Inst::JmpTrue(offset) => format!("jtr\t\t{}:", i.wrapping_add_signed(*offset)),
-                Inst::JmpFalse(offset) => format!("jfl\t\t{}:", i.wrapping_add_signed(*offset)),
-                Inst::Call(fi) => format!("call\t\t${}", fi),
-                Inst::Ret => "ret".to_owned(),
-            };
-
-            writeln!(f, "\t{:>4}:\t{}", i, inst_str)?;
+#[derive(Debug)]
+pub enum ProgramParseError {
+    InsufficientBytes,
+    InvalidConstant,
+    BadMagic,
+    UnsupportedVersion(u16),
+    ChecksumMismatch,
+    UnknownOpcode(u8),
+    TruncatedSection(SectionKind),
+    MissingSection(SectionKind),
+    InvalidFunctionName,
+    InvalidImport,
+    /// operand of an instruction which names no integer type.
+    InvalidIntType(u8),
+}
+impl Display for ProgramParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgramParseError::InsufficientBytes => write!(f, "unexpected end of file"),
+            ProgramParseError::InvalidConstant => write!(f, "invalid constant"),
+            ProgramParseError::BadMagic => write!(f, "not a wsk program"),
+            ProgramParseError::UnsupportedVersion(v) => write!(
+                f,
+                "unsupported format version {} (expected {})",
+                v, FORMAT_VERSION
+            ),
+            ProgramParseError::ChecksumMismatch => write!(f, "checksum mismatch"),
+            ProgramParseError::UnknownOpcode(op) => write!(f, "unknown opcode {:#04x}", op),
+            ProgramParseError::TruncatedSection(kind) => write!(f, "truncated {:?} section", kind),
+            ProgramParseError::MissingSection(kind) => write!(f, "missing {:?} section", kind),
+            ProgramParseError::InvalidFunctionName => {
+                write!(f, "function name refers to no function")
+            }
+            ProgramParseError::InvalidImport => write!(f, "invalid import"),
+            ProgramParseError::InvalidIntType(ty) => {
+                write!(f, "invalid integer type {:#04x}", ty)
+            }
         }
-        Ok(())
     }
 }
 
-#[derive(Debug)]
-pub enum ProgramParseError {
-    InsufficientBytes,
+/// CRC-32 (IEEE) of the bytes, which is the checksum of the binary after its checksum field.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
 }
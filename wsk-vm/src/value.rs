@@ -1,37 +1,477 @@
 use std::{
+    cell::RefCell,
     fmt::Display,
     mem::discriminant,
     ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Not, Rem, Sub},
+    rc::Rc,
 };
 
-#[derive(Debug, Clone, Copy)]
+/// Most elements an array made by repeating a value can have, which bounds the memory it takes.
+pub const MAX_ARRAY_LEN: usize = 1 << 24;
+
+#[derive(Debug, Clone)]
 pub enum Value {
     Int(i64),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    /// 64-bit IEEE 754 floating-point number.
+    Float(f64),
     Bool(bool),
+    /// immutable, reference-counted string, cloning it does not copy the contents.
+    Str(Rc<str>),
+    /// fields of a struct. It has value semantics, the fields are copied on the first write
+    /// to a shared struct.
+    Struct(Rc<Vec<Value>>),
+    /// variant of an enum, which is the index of the variant followed by the values of its
+    /// payload. The payload is accessed like the fields of a struct.
+    Enum(usize, Rc<Vec<Value>>),
+    /// elements of a fixed-size array, with the same value semantics as a struct.
+    Array(Rc<Vec<Value>>),
+    /// elements of a growable vector. It has reference semantics, copies of it share the same
+    /// elements.
+    Vec(Rc<RefCell<Vec<Value>>>),
+}
+impl Value {
+    pub fn get_field(self, index: usize) -> Result<Self, OpError> {
+        let (Self::Struct(fields) | Self::Enum(_, fields)) = self else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        fields.get(index).cloned().ok_or(OpError::IndexOutOfBounds {
+            index: index as i64,
+            len: fields.len(),
+        })
+    }
+
+    /// Returns the index of the variant of the enum.
+    pub fn get_tag(self) -> Result<Self, OpError> {
+        match self {
+            Self::Enum(tag, _) => Ok(Self::Int(tag as i64)),
+            _ => Err(OpError::InvalidTypeForOp),
+        }
+    }
+
+    /// Returns the struct with the field at the index replaced by the value.
+    pub fn set_field(self, index: usize, value: Self) -> Result<Self, OpError> {
+        let Self::Struct(mut fields) = self else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        let len = fields.len();
+        let field = Rc::make_mut(&mut fields)
+            .get_mut(index)
+            .ok_or(OpError::IndexOutOfBounds {
+                index: index as i64,
+                len,
+            })?;
+        *field = value;
+        Ok(Self::Struct(fields))
+    }
+
+    /// Returns an array of the value repeated the number of times.
+    pub fn repeat(self, len: usize) -> Result<Self, OpError> {
+        if len > MAX_ARRAY_LEN {
+            return Err(OpError::ArrayTooLong(len));
+        }
+        Ok(Self::Array(vec![self; len].into()))
+    }
+
+    /// Returns the element of the array at the index.
+    pub fn get_index(self, index: Self) -> Result<Self, OpError> {
+        let (Self::Array(elements), Self::Int(index)) = (self, index) else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| elements.get(i))
+            .cloned()
+            .ok_or(OpError::IndexOutOfBounds {
+                index,
+                len: elements.len(),
+            })
+    }
+
+    /// Returns the array with the element at the index replaced by the value.
+    pub fn set_index(self, index: Self, value: Self) -> Result<Self, OpError> {
+        let (Self::Array(mut elements), Self::Int(index)) = (self, index) else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        let len = elements.len();
+        let element = usize::try_from(index)
+            .ok()
+            .and_then(|i| Rc::make_mut(&mut elements).get_mut(i))
+            .ok_or(OpError::IndexOutOfBounds { index, len })?;
+        *element = value;
+        Ok(Self::Array(elements))
+    }
+
+    /// Returns a new vector with the elements of the array.
+    pub fn to_vec(self) -> Result<Self, OpError> {
+        match self {
+            Self::Array(elements) => Ok(Self::Vec(Rc::new(RefCell::new(elements.to_vec())))),
+            _ => Err(OpError::InvalidTypeForOp),
+        }
+    }
+
+    /// Appends the value to the end of the vector.
+    pub fn vec_push(self, value: Self) -> Result<(), OpError> {
+        let Self::Vec(elements) = self else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        elements.borrow_mut().push(value);
+        Ok(())
+    }
+
+    /// Removes the last element of the vector and returns it.
+    pub fn vec_pop(self) -> Result<Self, OpError> {
+        let Self::Vec(elements) = self else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        let popped = elements.borrow_mut().pop();
+        popped.ok_or(OpError::PopFromEmptyVec)
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn vec_len(self) -> Result<Self, OpError> {
+        match self {
+            Self::Vec(elements) => Ok(Self::Int(elements.borrow().len() as i64)),
+            _ => Err(OpError::InvalidTypeForOp),
+        }
+    }
+
+    /// Returns the element of the vector at the index.
+    pub fn vec_get(self, index: Self) -> Result<Self, OpError> {
+        let (Self::Vec(elements), Self::Int(index)) = (self, index) else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        let elements = elements.borrow();
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| elements.get(i))
+            .cloned()
+            .ok_or(OpError::IndexOutOfBounds {
+                index,
+                len: elements.len(),
+            })
+    }
+
+    /// Replaces the element of the vector at the index by the value.
+    pub fn vec_set(self, index: Self, value: Self) -> Result<(), OpError> {
+        let (Self::Vec(elements), Self::Int(index)) = (self, index) else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        let mut elements = elements.borrow_mut();
+        let len = elements.len();
+        let element = usize::try_from(index)
+            .ok()
+            .and_then(|i| elements.get_mut(i))
+            .ok_or(OpError::IndexOutOfBounds { index, len })?;
+        *element = value;
+        Ok(())
+    }
+
+    /// Returns the type of the integer, or `None` if the value is not an integer.
+    pub fn int_type(&self) -> Option<IntType> {
+        Some(match self {
+            Self::Int(_) => IntType::Int,
+            Self::I8(_) => IntType::I8,
+            Self::I16(_) => IntType::I16,
+            Self::I32(_) => IntType::I32,
+            Self::U8(_) => IntType::U8,
+            Self::U16(_) => IntType::U16,
+            Self::U32(_) => IntType::U32,
+            Self::U64(_) => IntType::U64,
+            _ => return None,
+        })
+    }
+
+    /// Returns the integer widened to `i128`, which holds the values of every integer type.
+    pub fn to_i128(&self) -> Option<i128> {
+        Some(match *self {
+            Self::Int(v) => v as i128,
+            Self::I8(v) => v as i128,
+            Self::I16(v) => v as i128,
+            Self::I32(v) => v as i128,
+            Self::U8(v) => v as i128,
+            Self::U16(v) => v as i128,
+            Self::U32(v) => v as i128,
+            Self::U64(v) => v as i128,
+            _ => return None,
+        })
+    }
+
+    /// Returns the integer converted to the type. Values which do not fit are truncated to the
+    /// width of the type, and their bits are reinterpreted with its signedness.
+    pub fn int_cast(self, ty: IntType) -> Result<Self, OpError> {
+        let v = self.to_i128().ok_or(OpError::InvalidTypeForOp)?;
+        Ok(match ty {
+            IntType::Int => Self::Int(v as i64),
+            IntType::I8 => Self::I8(v as i8),
+            IntType::I16 => Self::I16(v as i16),
+            IntType::I32 => Self::I32(v as i32),
+            IntType::U8 => Self::U8(v as u8),
+            IntType::U16 => Self::U16(v as u16),
+            IntType::U32 => Self::U32(v as u32),
+            IntType::U64 => Self::U64(v as u64),
+        })
+    }
+
+    /// Returns the nearest float to the integer.
+    pub fn int_to_float(self) -> Result<Self, OpError> {
+        match self.to_i128() {
+            Some(v) => Ok(Self::Float(v as f64)),
+            None => Err(OpError::InvalidTypeForOp),
+        }
+    }
+
+    /// Returns the float rounded toward zero as an integer of the type. Values which do not fit
+    /// saturate at the bounds of the type, and NaN is 0.
+    pub fn float_to_int(self, ty: IntType) -> Result<Self, OpError> {
+        let Self::Float(v) = self else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        Ok(match ty {
+            IntType::Int => Self::Int(v as i64),
+            IntType::I8 => Self::I8(v as i8),
+            IntType::I16 => Self::I16(v as i16),
+            IntType::I32 => Self::I32(v as i32),
+            IntType::U8 => Self::U8(v as u8),
+            IntType::U16 => Self::U16(v as u16),
+            IntType::U32 => Self::U32(v as u32),
+            IntType::U64 => Self::U64(v as u64),
+        })
+    }
+
+    /// Returns whether the integer is not zero.
+    pub fn int_to_bool(self) -> Result<Self, OpError> {
+        match self.to_i128() {
+            Some(v) => Ok(Self::Bool(v != 0)),
+            None => Err(OpError::InvalidTypeForOp),
+        }
+    }
+
+    /// Returns 1 for `true` and 0 for `false`.
+    pub fn bool_to_int(self) -> Result<Self, OpError> {
+        match self {
+            Self::Bool(v) => Ok(Self::Int(v as i64)),
+            _ => Err(OpError::InvalidTypeForOp),
+        }
+    }
+
+    /// Returns the integer with its bits inverted.
+    pub fn bit_not(self) -> Result<Self, OpError> {
+        Ok(match self {
+            Self::Int(v) => Self::Int(!v),
+            Self::I8(v) => Self::I8(!v),
+            Self::I16(v) => Self::I16(!v),
+            Self::I32(v) => Self::I32(!v),
+            Self::U8(v) => Self::U8(!v),
+            Self::U16(v) => Self::U16(!v),
+            Self::U32(v) => Self::U32(!v),
+            Self::U64(v) => Self::U64(!v),
+            _ => return Err(OpError::InvalidTypeForOp),
+        })
+    }
+
+    /// Returns the integer shifted left by the amount, which may be of any integer type. Every
+    /// bit is shifted out by an amount of the width of the type or more, which gives 0.
+    pub fn shift_left(self, amount: Self) -> Result<Self, OpError> {
+        let amount = shift_amount(amount)?;
+        Ok(match self {
+            Self::Int(v) => Self::Int(v.checked_shl(amount).unwrap_or(0)),
+            Self::I8(v) => Self::I8(v.checked_shl(amount).unwrap_or(0)),
+            Self::I16(v) => Self::I16(v.checked_shl(amount).unwrap_or(0)),
+            Self::I32(v) => Self::I32(v.checked_shl(amount).unwrap_or(0)),
+            Self::U8(v) => Self::U8(v.checked_shl(amount).unwrap_or(0)),
+            Self::U16(v) => Self::U16(v.checked_shl(amount).unwrap_or(0)),
+            Self::U32(v) => Self::U32(v.checked_shl(amount).unwrap_or(0)),
+            Self::U64(v) => Self::U64(v.checked_shl(amount).unwrap_or(0)),
+            _ => return Err(OpError::InvalidTypeForOp),
+        })
+    }
+
+    /// Returns the integer shifted right by the amount, which may be of any integer type.
+    /// Signed integers are shifted arithmetically, so an amount of the width of the type or more
+    /// gives -1 for negative values and 0 otherwise.
+    pub fn shift_right(self, amount: Self) -> Result<Self, OpError> {
+        let amount = shift_amount(amount)?;
+        Ok(match self {
+            Self::Int(v) => Self::Int(v.checked_shr(amount).unwrap_or(v >> (i64::BITS - 1))),
+            Self::I8(v) => Self::I8(v.checked_shr(amount).unwrap_or(v >> (i8::BITS - 1))),
+            Self::I16(v) => Self::I16(v.checked_shr(amount).unwrap_or(v >> (i16::BITS - 1))),
+            Self::I32(v) => Self::I32(v.checked_shr(amount).unwrap_or(v >> (i32::BITS - 1))),
+            Self::U8(v) => Self::U8(v.checked_shr(amount).unwrap_or(0)),
+            Self::U16(v) => Self::U16(v.checked_shr(amount).unwrap_or(0)),
+            Self::U32(v) => Self::U32(v.checked_shr(amount).unwrap_or(0)),
+            Self::U64(v) => Self::U64(v.checked_shr(amount).unwrap_or(0)),
+            _ => return Err(OpError::InvalidTypeForOp),
+        })
+    }
+
+    pub fn concat(self, rhs: Self) -> Result<Self, OpError> {
+        match (self, rhs) {
+            (Self::Str(lhs), Self::Str(rhs)) => Ok(Self::Str(format!("{}{}", lhs, rhs).into())),
+            (Self::Str(_), _) => Err(OpError::TypeMismatched),
+            _ => Err(OpError::InvalidTypeForOp),
+        }
+    }
+
+    /// Returns the length of the string in bytes.
+    pub fn str_len(self) -> Result<Self, OpError> {
+        match self {
+            Self::Str(s) => Ok(Self::Int(s.len() as i64)),
+            _ => Err(OpError::InvalidTypeForOp),
+        }
+    }
+
+    /// Returns the byte of the string at the index.
+    pub fn str_index(self, index: Self) -> Result<Self, OpError> {
+        let (Self::Str(s), Self::Int(index)) = (self, index) else {
+            return Err(OpError::InvalidTypeForOp);
+        };
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| s.as_bytes().get(i))
+            .map(|b| Self::Int(*b as i64))
+            .ok_or(OpError::IndexOutOfBounds {
+                index,
+                len: s.len(),
+            })
+    }
+}
+
+/// Returns the amount of a shift, clamped to `u32::MAX` which is past the width of every type.
+fn shift_amount(amount: Value) -> Result<u32, OpError> {
+    let amount = amount.to_i128().ok_or(OpError::InvalidTypeForOp)?;
+    if amount < 0 {
+        return Err(OpError::NegativeShift);
+    }
+    Ok(u32::try_from(amount).unwrap_or(u32::MAX))
+}
+
+/// Type of an integer value, which determines its width and signedness. `Int` is the 64-bit
+/// signed integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntType {
+    Int,
+    I8,
+    I16,
+    I32,
+    U8,
+    U16,
+    U32,
+    U64,
+}
+impl IntType {
+    pub const ALL: [Self; 8] = [
+        Self::Int,
+        Self::I8,
+        Self::I16,
+        Self::I32,
+        Self::U8,
+        Self::U16,
+        Self::U32,
+        Self::U64,
+    ];
+}
+impl Display for IntType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Int => "int",
+                Self::I8 => "i8",
+                Self::I16 => "i16",
+                Self::I32 => "i32",
+                Self::U8 => "u8",
+                Self::U16 => "u16",
+                Self::U32 => "u32",
+                Self::U64 => "u64",
+            }
+        )
+    }
 }
 
 #[derive(Debug)]
 pub enum OpError {
     TypeMismatched,
     InvalidTypeForOp,
+    IndexOutOfBounds {
+        index: i64,
+        len: usize,
+    },
+    /// popped from a vector without elements.
+    PopFromEmptyVec,
+    /// integer division or remainder by zero.
+    DivisionByZero,
+    /// integer arithmetic whose result is out of the bounds of the type.
+    Overflow,
+    /// integer shifted by a negative amount.
+    NegativeShift,
+    /// array repeating a value more than [`MAX_ARRAY_LEN`] times.
+    ArrayTooLong(usize),
 }
 
-impl_macros::impl_math_bin_op!(Add, add, +);
-impl_macros::impl_math_bin_op!(Sub, sub, -);
-impl_macros::impl_math_bin_op!(Mul, mul, *);
-impl_macros::impl_math_bin_op!(Div, div, /);
-impl_macros::impl_math_bin_op!(Rem, rem, %);
+// integer arithmetic fails instead of going out of the bounds of the type, and floats follow
+// IEEE 754.
+impl_macros::impl_math_bin_op!(Add, add, checked_add, +);
+impl_macros::impl_math_bin_op!(Sub, sub, checked_sub, -);
+impl_macros::impl_math_bin_op!(Mul, mul, checked_mul, *);
+impl_macros::impl_math_bin_op!(Div, div, checked_div, /);
+impl_macros::impl_math_bin_op!(Rem, rem, checked_rem, %);
+impl_macros::impl_int_bin_fn!(wrapping_add, wrapping_sub, wrapping_mul);
+impl_macros::impl_int_bin_fn!(saturating_add, saturating_sub, saturating_mul);
+impl_macros::impl_bitwise_bin_fn!(bit_and, &);
+impl_macros::impl_bitwise_bin_fn!(bit_or, |);
+impl_macros::impl_bitwise_bin_fn!(bit_xor, ^);
 impl_macros::impl_logic_bin_op!(BitAnd, bitand, &&);
 impl_macros::impl_logic_bin_op!(BitOr, bitor, ||);
 
-impl_macros::impl_math_unary_op!(Neg, neg, -);
 impl_macros::impl_logic_unary_op!(Not, not, !);
 
+impl Neg for Value {
+    type Output = Result<Self, OpError>;
+
+    /// Negates a signed integer or a float. Negating the smallest value of an integer type
+    /// overflows.
+    fn neg(self) -> Self::Output {
+        let result = match self {
+            Self::Float(val) => Some(Self::Float(-val)),
+            Self::Int(val) => val.checked_neg().map(Self::Int),
+            Self::I8(val) => val.checked_neg().map(Self::I8),
+            Self::I16(val) => val.checked_neg().map(Self::I16),
+            Self::I32(val) => val.checked_neg().map(Self::I32),
+            _ => return Err(OpError::InvalidTypeForOp),
+        };
+        result.ok_or(OpError::Overflow)
+    }
+}
+
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Int(lhs), Self::Int(rhs)) => lhs == rhs,
+            (Self::I8(lhs), Self::I8(rhs)) => lhs == rhs,
+            (Self::I16(lhs), Self::I16(rhs)) => lhs == rhs,
+            (Self::I32(lhs), Self::I32(rhs)) => lhs == rhs,
+            (Self::U8(lhs), Self::U8(rhs)) => lhs == rhs,
+            (Self::U16(lhs), Self::U16(rhs)) => lhs == rhs,
+            (Self::U32(lhs), Self::U32(rhs)) => lhs == rhs,
+            (Self::U64(lhs), Self::U64(rhs)) => lhs == rhs,
+            // NaN is not equal to any float, including itself.
+            (Self::Float(lhs), Self::Float(rhs)) => lhs == rhs,
             (Self::Bool(lhs), Self::Bool(rhs)) => lhs == rhs,
+            (Self::Str(lhs), Self::Str(rhs)) => lhs == rhs,
+            (Self::Struct(lhs), Self::Struct(rhs)) => lhs == rhs,
+            (Self::Enum(lhs_tag, lhs), Self::Enum(rhs_tag, rhs)) => {
+                lhs_tag == rhs_tag && lhs == rhs
+            }
+            (Self::Array(lhs), Self::Array(rhs)) => lhs == rhs,
+            (Self::Vec(lhs), Self::Vec(rhs)) => lhs == rhs,
             _ => false,
         }
     }
@@ -41,6 +481,14 @@ impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         match (self, other) {
             (Self::Int(lhs), Self::Int(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
+            (Self::I8(lhs), Self::I8(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
+            (Self::I16(lhs), Self::I16(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
+            (Self::I32(lhs), Self::I32(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
+            (Self::U8(lhs), Self::U8(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
+            (Self::U16(lhs), Self::U16(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
+            (Self::U32(lhs), Self::U32(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
+            (Self::U64(lhs), Self::U64(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
+            (Self::Float(lhs), Self::Float(rhs)) => PartialOrd::partial_cmp(lhs, rhs),
             _ => None,
         }
     }
@@ -51,43 +499,145 @@ impl From<i64> for Value {
         Self::Int(value)
     }
 }
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
 impl From<bool> for Value {
     fn from(value: bool) -> Self {
         Self::Bool(value)
     }
 }
 
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::Str(value.into())
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Value::Int(v) => write!(f, "{}", v),
+            // integers other than `int` carry the suffix of their type.
+            Value::I8(v) => write!(f, "{}i8", v),
+            Value::I16(v) => write!(f, "{}i16", v),
+            Value::I32(v) => write!(f, "{}i32", v),
+            Value::U8(v) => write!(f, "{}u8", v),
+            Value::U16(v) => write!(f, "{}u16", v),
+            Value::U32(v) => write!(f, "{}u32", v),
+            Value::U64(v) => write!(f, "{}u64", v),
+            // floats always have a point or an exponent, such as `1.0` and `1e20`.
+            Value::Float(v) => write!(f, "{:?}", v),
             Value::Bool(v) => write!(f, "{}", v),
+            Value::Str(v) => write!(f, "\"{}\"", v.escape_debug()),
+            Value::Struct(fields) => {
+                let fields: Vec<String> = fields.iter().map(|v| v.to_string()).collect();
+                write!(f, "{{{}}}", fields.join(", "))
+            }
+            Value::Enum(tag, payload) => {
+                let payload: Vec<String> = payload.iter().map(|v| v.to_string()).collect();
+                write!(f, "#{}{{{}}}", tag, payload.join(", "))
+            }
+            Value::Array(elements) => {
+                let elements: Vec<String> = elements.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", elements.join(", "))
+            }
+            Value::Vec(elements) => {
+                let elements: Vec<String> =
+                    elements.borrow().iter().map(|v| v.to_string()).collect();
+                write!(f, "vec[{}]", elements.join(", "))
+            }
         }
     }
 }
 
 mod impl_macros {
     macro_rules! impl_math_bin_op {
-    ($op:ident, $op_fn:ident, $sym:tt) => {
-        impl $op for Value {
-            type Output = Result<Self, OpError>;
+        ($op:ident, $op_fn:ident, $int_fn:ident, $sym:tt) => {
+            impl $op for Value {
+                type Output = Result<Self, OpError>;
 
-            fn $op_fn(self, rhs: Self) -> Self::Output {
-                if discriminant(&self) != discriminant(&rhs) {
-                    return Err(OpError::TypeMismatched);
+                fn $op_fn(self, rhs: Self) -> Self::Output {
+                    if discriminant(&self) != discriminant(&rhs) {
+                        return Err(OpError::TypeMismatched);
+                    }
+
+                    // the checked integer operations only fail by a zero divisor or by overflow.
+                    let is_zero = rhs.to_i128() == Some(0);
+                    let result = match (self, rhs) {
+                        (Self::Int(lhs), Self::Int(rhs)) => lhs.$int_fn(rhs).map(Self::Int),
+                        (Self::I8(lhs), Self::I8(rhs)) => lhs.$int_fn(rhs).map(Self::I8),
+                        (Self::I16(lhs), Self::I16(rhs)) => lhs.$int_fn(rhs).map(Self::I16),
+                        (Self::I32(lhs), Self::I32(rhs)) => lhs.$int_fn(rhs).map(Self::I32),
+                        (Self::U8(lhs), Self::U8(rhs)) => lhs.$int_fn(rhs).map(Self::U8),
+                        (Self::U16(lhs), Self::U16(rhs)) => lhs.$int_fn(rhs).map(Self::U16),
+                        (Self::U32(lhs), Self::U32(rhs)) => lhs.$int_fn(rhs).map(Self::U32),
+                        (Self::U64(lhs), Self::U64(rhs)) => lhs.$int_fn(rhs).map(Self::U64),
+                        (Self::Float(lhs), Self::Float(rhs)) => Some(Self::Float(lhs $sym rhs)),
+                        _ => return Err(OpError::InvalidTypeForOp),
+                    };
+                    result.ok_or(match is_zero {
+                        true => OpError::DivisionByZero,
+                        false => OpError::Overflow,
+                    })
                 }
+            }
+        };
+    }
+    /// Implements methods of integers named after the methods of Rust integers which never fail,
+    /// such as `wrapping_add`.
+    macro_rules! impl_int_bin_fn {
+        ($($int_fn:ident),*) => {
+            impl Value {
+                $(
+                    pub fn $int_fn(self, rhs: Self) -> Result<Self, OpError> {
+                        if discriminant(&self) != discriminant(&rhs) {
+                            return Err(OpError::TypeMismatched);
+                        }
 
-                Ok(match self {
-                    Self::Int(lhs) => {
-                        let Self::Int(rhs) = rhs else { unreachable!() };
-                        Self::Int(lhs $sym rhs)
+                        Ok(match (self, rhs) {
+                            (Self::Int(lhs), Self::Int(rhs)) => Self::Int(lhs.$int_fn(rhs)),
+                            (Self::I8(lhs), Self::I8(rhs)) => Self::I8(lhs.$int_fn(rhs)),
+                            (Self::I16(lhs), Self::I16(rhs)) => Self::I16(lhs.$int_fn(rhs)),
+                            (Self::I32(lhs), Self::I32(rhs)) => Self::I32(lhs.$int_fn(rhs)),
+                            (Self::U8(lhs), Self::U8(rhs)) => Self::U8(lhs.$int_fn(rhs)),
+                            (Self::U16(lhs), Self::U16(rhs)) => Self::U16(lhs.$int_fn(rhs)),
+                            (Self::U32(lhs), Self::U32(rhs)) => Self::U32(lhs.$int_fn(rhs)),
+                            (Self::U64(lhs), Self::U64(rhs)) => Self::U64(lhs.$int_fn(rhs)),
+                            _ => return Err(OpError::InvalidTypeForOp),
+                        })
                     }
-                    Self::Bool(_) => return Err(OpError::InvalidTypeForOp),
-                })
+                )*
             }
-        }
-    };
-}
+        };
+    }
+    /// Implements a method of integers applying the bitwise operator to operands of the same
+    /// type.
+    macro_rules! impl_bitwise_bin_fn {
+        ($fn:ident, $sym:tt) => {
+            impl Value {
+                pub fn $fn(self, rhs: Self) -> Result<Self, OpError> {
+                    if discriminant(&self) != discriminant(&rhs) {
+                        return Err(OpError::TypeMismatched);
+                    }
+
+                    Ok(match (self, rhs) {
+                        (Self::Int(lhs), Self::Int(rhs)) => Self::Int(lhs $sym rhs),
+                        (Self::I8(lhs), Self::I8(rhs)) => Self::I8(lhs $sym rhs),
+                        (Self::I16(lhs), Self::I16(rhs)) => Self::I16(lhs $sym rhs),
+                        (Self::I32(lhs), Self::I32(rhs)) => Self::I32(lhs $sym rhs),
+                        (Self::U8(lhs), Self::U8(rhs)) => Self::U8(lhs $sym rhs),
+                        (Self::U16(lhs), Self::U16(rhs)) => Self::U16(lhs $sym rhs),
+                        (Self::U32(lhs), Self::U32(rhs)) => Self::U32(lhs $sym rhs),
+                        (Self::U64(lhs), Self::U64(rhs)) => Self::U64(lhs $sym rhs),
+                        _ => return Err(OpError::InvalidTypeForOp),
+                    })
+                }
+            }
+        };
+    }
     macro_rules! impl_logic_bin_op {
     ($op:ident, $op_fn:ident, $sym:tt) => {
         impl $op for Value {
@@ -99,31 +649,17 @@ mod impl_macros {
                 }
 
                 Ok(match self {
-                    Self::Int(_) => return Err(OpError::InvalidTypeForOp),
                     Self::Bool(lhs) => {
                         let Self::Bool(rhs) = rhs else { unreachable!() };
                         Self::Bool(lhs $sym rhs)
                     }
+                    _ => return Err(OpError::InvalidTypeForOp),
                 })
             }
         }
     };
 }
 
-    macro_rules! impl_math_unary_op {
-    ($op:ident, $op_fn:ident, $sym:tt) => {
-        impl $op for Value {
-            type Output = Result<Self, OpError>;
-
-            fn $op_fn(self) -> Self::Output {
-                Ok(match self {
-                    Self::Int(val) => Self::Int($sym val),
-                    Self::Bool(_) => return Err(OpError::InvalidTypeForOp),
-                })
-            }
-        }
-    };
-}
     macro_rules! impl_logic_unary_op {
     ($op:ident, $op_fn:ident, $sym:tt) => {
         impl $op for Value {
@@ -131,16 +667,17 @@ mod impl_macros {
 
             fn $op_fn(self) -> Self::Output {
                 Ok(match self {
-                    Self::Int(_) => return Err(OpError::InvalidTypeForOp),
                     Self::Bool(val) => Self::Bool($sym val),
+                    _ => return Err(OpError::InvalidTypeForOp),
                 })
             }
         }
     };
 }
 
+    pub(super) use impl_bitwise_bin_fn;
+    pub(super) use impl_int_bin_fn;
     pub(super) use impl_logic_bin_op;
     pub(super) use impl_logic_unary_op;
     pub(super) use impl_math_bin_op;
-    pub(super) use impl_math_unary_op;
 }
@@ -0,0 +1,280 @@
+//! Static verification of programs, rejecting malformed bytecode before it is executed.
+
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt,
+};
+
+use crate::{
+    program::{Function, Program},
+    Inst,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyError {
+    /// index of the function, or `None` for errors of the program itself.
+    pub fi: Option<usize>,
+    /// index of the instruction within the function.
+    pub pc: Option<usize>,
+    pub kind: VerifyErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyErrorKind {
+    InvalidEntryPoint,
+    EntryPointWithParams,
+    JumpOutOfRange {
+        target: isize,
+    },
+    InvalidFunctionIndex(usize),
+    InvalidConstIndex(usize),
+    InvalidImportIndex(usize),
+    StackUnderflow {
+        depth: usize,
+        required: usize,
+    },
+    InconsistentStackDepth {
+        expected: usize,
+        found: usize,
+    },
+    LoadBeforeStore(usize),
+    /// the last instruction of a path is neither `ret` nor `halt`.
+    MissingReturn,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.fi, self.pc) {
+            (Some(fi), Some(pc)) => write!(f, "func ${} at {}: ", fi, pc)?,
+            (Some(fi), None) => write!(f, "func ${}: ", fi)?,
+            _ => (),
+        }
+        match &self.kind {
+            VerifyErrorKind::InvalidEntryPoint => write!(f, "entry point is not a function"),
+            VerifyErrorKind::EntryPointWithParams => {
+                write!(f, "entry point must not take parameters")
+            }
+            VerifyErrorKind::JumpOutOfRange { target } => {
+                write!(f, "jump target {} is out of the function", target)
+            }
+            VerifyErrorKind::InvalidFunctionIndex(fi) => write!(f, "call to unknown func ${}", fi),
+            VerifyErrorKind::InvalidConstIndex(idx) => write!(f, "unknown constant #{}", idx),
+            VerifyErrorKind::InvalidImportIndex(idx) => write!(f, "unknown import {}", idx),
+            VerifyErrorKind::StackUnderflow { depth, required } => write!(
+                f,
+                "stack underflow, {} values required but {} available",
+                required, depth
+            ),
+            VerifyErrorKind::InconsistentStackDepth { expected, found } => write!(
+                f,
+                "stack depth {} differs from {} on another path",
+                found, expected
+            ),
+            VerifyErrorKind::LoadBeforeStore(idx) => {
+                write!(f, "r{} may be loaded before it is stored", idx)
+            }
+            VerifyErrorKind::MissingReturn => write!(f, "execution runs past the last instruction"),
+        }
+    }
+}
+
+/// Checks that every function of the program is well-formed:
+/// - jumps land within the function, and calls and constants refer to existing entries.
+/// - the stack depth is the same on all paths reaching an instruction, and never goes below the
+///   start of the function's frame. The frame starts with the parameters, which the function may
+///   pop, such as to store them in locals.
+/// - locals are stored on every path before they are loaded.
+/// - every path ends with `ret` or `halt`, and `ret` leaves the function's return values.
+pub fn verify(prog: &Program) -> Result<(), VerifyError> {
+    let entry = prog.get(prog.get_entry_point()).ok_or(VerifyError {
+        fi: None,
+        pc: None,
+        kind: VerifyErrorKind::InvalidEntryPoint,
+    })?;
+    if entry.get_arity().params != 0 {
+        return Err(VerifyError {
+            fi: Some(prog.get_entry_point()),
+            pc: None,
+            kind: VerifyErrorKind::EntryPointWithParams,
+        });
+    }
+
+    for (fi, func) in prog.get_funcs().iter().enumerate() {
+        verify_func(prog, func).map_err(|(pc, kind)| VerifyError {
+            fi: Some(fi),
+            pc: Some(pc),
+            kind,
+        })?;
+    }
+    Ok(())
+}
+
+/// Abstract state before executing an instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct State {
+    /// number of values on the stack, including the parameters.
+    depth: usize,
+    /// locals which are stored on every path reaching the instruction.
+    stored: BTreeSet<usize>,
+}
+
+fn verify_func(prog: &Program, func: &Function) -> Result<(), (usize, VerifyErrorKind)> {
+    let insts = func.get_insts();
+    let arity = func.get_arity();
+    let mut states: Vec<Option<State>> = vec![None; insts.len()];
+    let mut worklist = VecDeque::new();
+
+    if insts.is_empty() {
+        return Err((0, VerifyErrorKind::MissingReturn));
+    }
+    states[0] = Some(State {
+        depth: arity.params,
+        stored: BTreeSet::new(),
+    });
+    worklist.push_back(0);
+
+    while let Some(pc) = worklist.pop_front() {
+        let Some(state) = states[pc].clone() else {
+            continue;
+        };
+        let inst = &insts[pc];
+        let err = |kind| (pc, kind);
+
+        let (pops, pushes) = stack_effect(prog, inst).map_err(err)?;
+        if state.depth < pops {
+            return Err(err(VerifyErrorKind::StackUnderflow {
+                depth: state.depth,
+                required: pops,
+            }));
+        }
+        let mut next = State {
+            depth: state.depth - pops + pushes,
+            stored: state.stored,
+        };
+        match inst {
+            Inst::Load(idx) if !next.stored.contains(idx) => {
+                return Err(err(VerifyErrorKind::LoadBeforeStore(*idx)));
+            }
+            Inst::Store(idx) => {
+                next.stored.insert(*idx);
+            }
+            _ => (),
+        }
+
+        let jump_target = |offset: isize| {
+            pc.checked_add_signed(offset)
+                .filter(|target| *target < insts.len())
+                .ok_or(err(VerifyErrorKind::JumpOutOfRange {
+                    target: pc as isize + offset,
+                }))
+        };
+        let successors = match inst {
+            Inst::Halt => vec![],
+            Inst::Ret => {
+                if next.depth < arity.rets {
+                    return Err(err(VerifyErrorKind::StackUnderflow {
+                        depth: next.depth,
+                        required: arity.rets,
+                    }));
+                }
+                vec![]
+            }
+            Inst::Jmp(offset) => vec![jump_target(*offset)?],
+            Inst::JmpTrue(offset) | Inst::JmpFalse(offset) => vec![pc + 1, jump_target(*offset)?],
+            _ => vec![pc + 1],
+        };
+
+        for succ in successors {
+            if succ >= insts.len() {
+                return Err(err(VerifyErrorKind::MissingReturn));
+            }
+            match &mut states[succ] {
+                Some(prev) => {
+                    if prev.depth != next.depth {
+                        return Err((
+                            succ,
+                            VerifyErrorKind::InconsistentStackDepth {
+                                expected: prev.depth,
+                                found: next.depth,
+                            },
+                        ));
+                    }
+                    let stored: BTreeSet<usize> =
+                        prev.stored.intersection(&next.stored).copied().collect();
+                    if stored.len() != prev.stored.len() {
+                        prev.stored = stored;
+                        worklist.push_back(succ);
+                    }
+                }
+                state @ None => {
+                    *state = Some(next.clone());
+                    worklist.push_back(succ);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the number of values the instruction pops and pushes.
+fn stack_effect(prog: &Program, inst: &Inst) -> Result<(usize, usize), VerifyErrorKind> {
+    Ok(match inst {
+        Inst::Halt | Inst::Ret | Inst::Jmp(_) => (0, 0),
+        Inst::Push(_) | Inst::Load(_) => (0, 1),
+        Inst::PushConst(idx) => {
+            if prog.get_const(*idx).is_none() {
+                return Err(VerifyErrorKind::InvalidConstIndex(*idx));
+            }
+            (0, 1)
+        }
+        Inst::Pop | Inst::Store(_) | Inst::JmpTrue(_) | Inst::JmpFalse(_) => (1, 0),
+        Inst::Add
+        | Inst::Sub
+        | Inst::Mul
+        | Inst::Div
+        | Inst::Mod
+        | Inst::And
+        | Inst::Or
+        | Inst::Cmp(_)
+        | Inst::Wrapping(_)
+        | Inst::Saturating(_)
+        | Inst::BitAnd
+        | Inst::BitOr
+        | Inst::BitXor
+        | Inst::Shl
+        | Inst::Shr
+        | Inst::Concat
+        | Inst::StrIndex => (2, 1),
+        Inst::Neg | Inst::Not | Inst::BitNot | Inst::StrLen | Inst::GetField(_) => (1, 1),
+        Inst::MakeStruct(cnt) => (*cnt, 1),
+        Inst::SetField(_) => (2, 1),
+        Inst::MakeEnum(_, cnt) => (*cnt, 1),
+        Inst::GetTag => (1, 1),
+        Inst::MakeArray(cnt) => (*cnt, 1),
+        Inst::RepeatArray(_) => (1, 1),
+        Inst::GetIndex => (2, 1),
+        Inst::SetIndex => (3, 1),
+        Inst::ToVec | Inst::VecPop | Inst::VecLen => (1, 1),
+        Inst::VecPush => (2, 0),
+        Inst::VecGet => (2, 1),
+        Inst::VecSet => (3, 0),
+        Inst::IntToBool
+        | Inst::BoolToInt
+        | Inst::IntCast(_)
+        | Inst::IntToFloat
+        | Inst::FloatToInt(_) => (1, 1),
+        Inst::Call(fi) => {
+            let callee = prog
+                .get(*fi)
+                .ok_or(VerifyErrorKind::InvalidFunctionIndex(*fi))?;
+            let arity = callee.get_arity();
+            (arity.params, arity.rets)
+        }
+        Inst::CallNative(idx) => {
+            let import = prog
+                .get_import(*idx)
+                .ok_or(VerifyErrorKind::InvalidImportIndex(*idx))?;
+            (import.sig.params.len(), import.sig.ret.is_some() as usize)
+        }
+    })
+}
@@ -2,26 +2,37 @@ use std::collections::HashMap;
 
 use crate::{
     inst::{RunError, RunInst},
-    program::Program,
-    value::Value,
+    native::{Import, NativeError, NativeFunc, NativeSig, ValueType},
+    program::{Arity, Program},
+    value::{OpError, Value},
 };
 
 #[derive(Debug, Default)]
 pub struct VM {
+    natives: Vec<NativeFunc>,
+    /// index of the native function bound to each import of the program.
+    bindings: Vec<usize>,
+    consts: Vec<Value>,
+    arities: Vec<Arity>,
     stack: Vec<Value>,
     frames: Vec<Frame>,
     status: VMStatus,
+    exit_status: Option<i64>,
 }
 impl VM {
     pub fn reset(&mut self, entry_point: usize) {
         self.stack.clear();
         self.frames.clear();
-        self.push_frame(entry_point);
+        self.push_frame(entry_point, 0);
         self.status = VMStatus::default();
+        self.exit_status = None;
     }
 
     pub fn execute(&mut self, program: Program) -> Result<(), RunError> {
         self.reset(program.get_entry_point());
+        self.bind_imports(program.get_imports())?;
+        self.consts = program.get_consts().to_vec();
+        self.arities = program.get_funcs().iter().map(|v| v.get_arity()).collect();
 
         while !self.is_halted() {
             let Frame { fi, pc, .. } = self.get_frame();
@@ -32,7 +43,7 @@ impl VM {
                 return Err(VMError::InstReadOutOfBound.into());
             };
 
-            inst.run(self)?;
+            inst.clone().run(self)?;
 
             if self.is_skipped() {
                 self.status.skip = false;
@@ -44,8 +55,80 @@ impl VM {
         Ok(())
     }
 
-    pub fn push_frame(&mut self, fi: usize) {
-        self.frames.push(Frame::new(fi));
+    /// Registers the host function, replacing the one with the same name.
+    pub fn register_native(
+        &mut self,
+        name: impl Into<String>,
+        sig: NativeSig,
+        func: impl FnMut(&[Value]) -> Result<Option<Value>, NativeError> + 'static,
+    ) {
+        let native = NativeFunc {
+            name: name.into(),
+            sig,
+            func: Box::new(func),
+        };
+        if let Some(v) = self.natives.iter_mut().find(|v| v.name == native.name) {
+            *v = native;
+        } else {
+            self.natives.push(native);
+        }
+    }
+
+    /// Binds each import to the registered host function with the same name and signature.
+    pub fn bind_imports(&mut self, imports: &[Import]) -> Result<(), VMError> {
+        self.bindings.clear();
+        for import in imports {
+            let idx = self
+                .natives
+                .iter()
+                .position(|v| v.name == import.name)
+                .ok_or_else(|| VMError::UnboundImport(import.name.clone()))?;
+            if self.natives[idx].sig != import.sig {
+                return Err(VMError::ImportSignatureMismatch(import.name.clone()));
+            }
+            self.bindings.push(idx);
+        }
+        Ok(())
+    }
+
+    pub fn call_native(&mut self, index: usize) -> Result<(), RunError> {
+        let idx = *self
+            .bindings
+            .get(index)
+            .ok_or(VMError::InvalidImportIndex)?;
+        let native = &mut self.natives[idx];
+
+        let arg_start = self
+            .stack
+            .len()
+            .checked_sub(native.sig.params.len())
+            .ok_or(VMError::StackUnderflow)?;
+        let args = self.stack.split_off(arg_start);
+        if args
+            .iter()
+            .zip(&native.sig.params)
+            .any(|(arg, ty)| ValueType::of(arg) != Some(*ty))
+        {
+            return Err(OpError::InvalidTypeForOp.into());
+        }
+
+        let ret = match (native.func)(&args) {
+            Err(NativeError::Exit(status)) => {
+                self.exit_status = Some(status);
+                self.halt();
+                return Ok(());
+            }
+            ret => ret?,
+        };
+        if ret.as_ref().map(ValueType::of) != native.sig.ret.map(Some) {
+            return Err(VMError::InvalidNativeReturn(native.name.clone()).into());
+        }
+        self.stack.extend(ret);
+        Ok(())
+    }
+
+    pub fn push_frame(&mut self, fi: usize, base: usize) {
+        self.frames.push(Frame::new(fi, base));
     }
 
     pub fn pop_frame(&mut self) -> Result<(), VMError> {
@@ -72,6 +155,23 @@ impl VM {
         self.stack.pop().ok_or(VMError::StackUnderflow)
     }
 
+    /// Pops the number of values, returning them in the pushed order.
+    pub fn pop_n(&mut self, count: usize) -> Result<Vec<Value>, VMError> {
+        let start = self
+            .stack
+            .len()
+            .checked_sub(count)
+            .ok_or(VMError::StackUnderflow)?;
+        Ok(self.stack.split_off(start))
+    }
+
+    pub fn get_const(&self, index: usize) -> Result<Value, VMError> {
+        self.consts
+            .get(index)
+            .cloned()
+            .ok_or(VMError::InvalidConstIndex)
+    }
+
     pub fn store(&mut self, key: usize, value: Value) {
         self.get_frame_mut().store(key, value);
     }
@@ -85,12 +185,30 @@ impl VM {
         self.skip();
     }
 
-    pub fn call(&mut self, fi: usize) {
-        self.push_frame(fi);
+    pub fn call(&mut self, fi: usize) -> Result<(), VMError> {
+        let arity = self.arities.get(fi).ok_or(VMError::InvalidFunctionIndex)?;
+        let base = self
+            .stack
+            .len()
+            .checked_sub(arity.params)
+            .ok_or(VMError::StackUnderflow)?;
+        self.push_frame(fi, base);
         self.skip();
+        Ok(())
     }
 
+    /// Returns to the caller, leaving only the return values of the callee on top of the
+    /// caller's stack.
     pub fn ret(&mut self) -> Result<(), RunError> {
+        let Frame { fi, base, .. } = *self.get_frame();
+        let rets = self.arities.get(fi).map(|v| v.rets).unwrap_or_default();
+        let ret_start = self
+            .stack
+            .len()
+            .checked_sub(rets)
+            .filter(|v| *v >= base)
+            .ok_or(VMError::StackUnderflow)?;
+        self.stack.drain(base..ret_start);
         self.pop_frame()?;
         Ok(())
     }
@@ -99,6 +217,15 @@ impl VM {
         self.status.halt = true;
     }
 
+    /// Returns the exit status of the halted program, which is the status given to the `exit`
+    /// host function, or else the integer left on the top of the stack, or else 0.
+    pub fn exit_status(&self) -> i64 {
+        self.exit_status.unwrap_or(match self.stack.last() {
+            Some(Value::Int(v)) => *v,
+            _ => 0,
+        })
+    }
+
     pub fn is_halted(&self) -> bool {
         self.status.halt
     }
@@ -121,6 +248,13 @@ pub enum VMError {
     StackReadOutOfBound,
     StackWriteOutOfBound,
     InvalidLocalId,
+    InvalidConstIndex,
+    InvalidImportIndex,
+    /// no host function is registered with the name of the import.
+    UnboundImport(String),
+    ImportSignatureMismatch(String),
+    /// the host function returned a value of a type different from its signature.
+    InvalidNativeReturn(String),
 }
 
 #[derive(Debug, Default)]
@@ -133,13 +267,16 @@ pub struct VMStatus {
 pub struct Frame {
     fi: usize,
     pc: usize,
+    /// stack length of the caller before the arguments were pushed.
+    base: usize,
     locals: HashMap<usize, Value>,
 }
 impl Frame {
-    pub fn new(fi: usize) -> Self {
+    pub fn new(fi: usize, base: usize) -> Self {
         Self {
             fi,
             pc: 0,
+            base,
             locals: HashMap::new(),
         }
     }
@@ -155,7 +292,7 @@ impl Frame {
     pub fn load(&self, key: usize) -> Result<Value, VMError> {
         self.locals
             .get(&key)
-            .copied()
+            .cloned()
             .ok_or(VMError::InvalidLocalId)
     }
 
@@ -0,0 +1,72 @@
+use wsk_vm::{
+    asm::{assemble, disassemble},
+    builtins::register_builtins,
+    program::Program,
+    verify::verify,
+    VM,
+};
+
+const SOURCE: &str = r#"
+entry $2
+
+const #0 "sum: "
+
+import print_str(str)
+import println_int(int)
+
+func add(2) -> 1:
+    store r0
+    store r1
+    load r1
+    load r0
+    add
+    ret
+
+func main() -> 1:
+    pushc #0
+    calln print_str
+    push 240u8
+    bnot
+    push 2
+    shl
+    icast int
+    push 20
+    call add
+    store r0
+    load r0
+    calln println_int
+    load r0
+    push 80
+    cmp equ
+    jfl L15
+    push 7
+    ret
+L15:
+    push 1
+    ret
+
+func $2():
+    call main
+    halt
+"#;
+
+#[test]
+fn assembled_program_roundtrips_through_binary() {
+    let prog = assemble(SOURCE).unwrap();
+    let bin = prog.to_bin();
+    let decoded = Program::from_bytes(&bin).unwrap();
+
+    assert_eq!(disassemble(&decoded), disassemble(&prog));
+    assert_eq!(assemble(&disassemble(&decoded)).unwrap().to_bin(), bin);
+}
+
+#[test]
+fn assembled_program_runs() {
+    let prog = Program::from_bytes(&assemble(SOURCE).unwrap().to_bin()).unwrap();
+    verify(&prog).unwrap();
+
+    let mut vm = VM::default();
+    register_builtins(&mut vm);
+    vm.execute(prog).unwrap();
+    assert_eq!(vm.exit_status(), 7);
+}
@@ -0,0 +1,99 @@
+use wsk_vm::{
+    asm::assemble,
+    program::{crc32, Program, ProgramParseError, SectionKind, FORMAT_VERSION},
+};
+
+const SOURCE: &str = r#"
+entry $1
+
+const #0 "hi"
+
+import print_str(str)
+
+func main() -> 1:
+    pushc #0
+    calln print_str
+    push 0
+    ret
+
+func $1():
+    call main
+    halt
+"#;
+
+/// offset of the checksum in the header, which covers every byte after it.
+const CHECKSUM_OFFSET: usize = 8;
+
+fn binary() -> Vec<u8> {
+    assemble(SOURCE).unwrap().to_bin()
+}
+
+/// Replaces the checksum of the binary with the one of its current bytes.
+fn fix_checksum(bin: &mut [u8]) {
+    let checksum = crc32(&bin[CHECKSUM_OFFSET + 4..]);
+    bin[CHECKSUM_OFFSET..CHECKSUM_OFFSET + 4].copy_from_slice(&checksum.to_le_bytes());
+}
+
+#[test]
+fn intact_binary_is_accepted() {
+    assert!(Program::from_bytes(&binary()).is_ok());
+}
+
+#[test]
+fn bad_magic() {
+    let mut bin = binary();
+    bin[0] = b'X';
+    assert!(matches!(
+        Program::from_bytes(&bin),
+        Err(ProgramParseError::BadMagic)
+    ));
+}
+
+#[test]
+fn unsupported_version() {
+    let mut bin = binary();
+    bin[4..6].copy_from_slice(&(FORMAT_VERSION - 1).to_le_bytes());
+    assert!(matches!(
+        Program::from_bytes(&bin),
+        Err(ProgramParseError::UnsupportedVersion(v)) if v == FORMAT_VERSION - 1
+    ));
+}
+
+#[test]
+fn checksum_mismatch() {
+    let mut bin = binary();
+    let last = bin.len() - 1;
+    bin[last] ^= 0xff;
+    assert!(matches!(
+        Program::from_bytes(&bin),
+        Err(ProgramParseError::ChecksumMismatch)
+    ));
+
+    let bin = binary();
+    assert!(matches!(
+        Program::from_bytes(&bin[..bin.len() - 1]),
+        Err(ProgramParseError::ChecksumMismatch)
+    ));
+}
+
+#[test]
+fn truncated_section() {
+    // the sections follow the table in the order code, consts, names and imports, so cutting
+    // the end truncates the imports.
+    let mut bin = binary();
+    bin.truncate(bin.len() - 1);
+    fix_checksum(&mut bin);
+    assert!(matches!(
+        Program::from_bytes(&bin),
+        Err(ProgramParseError::TruncatedSection(SectionKind::Imports))
+    ));
+}
+
+#[test]
+fn truncated_header() {
+    let bin = binary();
+    assert!(matches!(
+        Program::from_bytes(&bin[..3]),
+        Err(ProgramParseError::InsufficientBytes)
+    ));
+}
@@ -0,0 +1,166 @@
+use wsk_vm::{
+    asm::assemble,
+    inst::RunError,
+    value::{OpError, MAX_ARRAY_LEN},
+    verify::verify,
+    VM,
+};
+
+/// Assembles and runs a program whose `main` returns an int, returning the value it returns.
+fn run(main: &str) -> Result<i64, RunError> {
+    let source = format!(
+        "entry $1\n\nfunc main() -> 1:\n{}\n\nfunc $1():\n    call main\n    halt\n",
+        main
+    );
+    let prog = assemble(&source).unwrap();
+    verify(&prog).unwrap();
+
+    let mut vm = VM::default();
+    vm.execute(prog)?;
+    Ok(vm.exit_status())
+}
+
+#[test]
+fn repeated_array_past_the_limit_fails() {
+    let result = run("
+    push 1
+    reparray 9223372036854775807
+    pop
+    push 0
+    ret");
+    assert!(matches!(
+        result,
+        Err(RunError::OpError(OpError::ArrayTooLong(
+            9223372036854775807
+        )))
+    ));
+
+    let result = run(&format!(
+        "
+    push 1
+    reparray {}
+    pop
+    push 0
+    ret",
+        MAX_ARRAY_LEN + 1
+    ));
+    assert!(matches!(
+        result,
+        Err(RunError::OpError(OpError::ArrayTooLong(_)))
+    ));
+}
+
+#[test]
+fn repeated_array_runs() {
+    let result = run("
+    push 7
+    reparray 3
+    push 2
+    getidx
+    ret");
+    assert_eq!(result.unwrap(), 7);
+}
+
+/// Returns the body of a `main` which applies the operation to the operands, returning the result
+/// as an int.
+fn bin_op(lhs: &str, rhs: &str, op: &str) -> String {
+    format!(
+        "
+    push {}
+    push {}
+    {}
+    icast int
+    ret",
+        lhs, rhs, op
+    )
+}
+
+#[test]
+fn overflowing_arithmetic_fails() {
+    for (lhs, rhs, op) in [
+        ("127i8", "1i8", "add"),
+        ("-128i8", "1i8", "sub"),
+        ("64i8", "2i8", "mul"),
+        ("0u8", "1u8", "sub"),
+        ("-128i8", "-1i8", "div"),
+        ("-128i8", "-1i8", "mod"),
+    ] {
+        let result = run(&bin_op(lhs, rhs, op));
+        assert!(
+            matches!(result, Err(RunError::OpError(OpError::Overflow))),
+            "{} {} {}",
+            lhs,
+            op,
+            rhs
+        );
+    }
+}
+
+#[test]
+fn division_by_zero_fails() {
+    for op in ["div", "mod"] {
+        let result = run(&bin_op("7i8", "0i8", op));
+        assert!(matches!(
+            result,
+            Err(RunError::OpError(OpError::DivisionByZero))
+        ));
+    }
+}
+
+#[test]
+fn wrapping_and_saturating_arithmetic() {
+    for (lhs, rhs, op, expected) in [
+        ("127i8", "1i8", "wrap add", -128),
+        ("-128i8", "1i8", "wrap sub", 127),
+        ("64i8", "2i8", "wrap mul", -128),
+        ("0u8", "1u8", "wrap sub", 255),
+        ("127i8", "1i8", "sat add", 127),
+        ("-128i8", "1i8", "sat sub", -128),
+        ("64i8", "-4i8", "sat mul", -128),
+        ("0u8", "1u8", "sat sub", 0),
+        ("100i8", "20i8", "add", 120),
+        ("-128i8", "2i8", "div", -64),
+        ("-7i8", "2i8", "mod", -1),
+    ] {
+        assert_eq!(
+            run(&bin_op(lhs, rhs, op)).unwrap(),
+            expected,
+            "{} {} {}",
+            lhs,
+            op,
+            rhs
+        );
+    }
+}
+
+#[test]
+fn shifts_past_the_bit_width() {
+    for (lhs, rhs, op, expected) in [
+        ("1i8", "8u8", "shl", 0),
+        ("1u64", "64", "shl", 0),
+        ("255u8", "200", "shr", 0),
+        ("-100i8", "8i8", "shr", -1),
+        ("100i8", "9223372036854775807", "shr", 0),
+        ("1i16", "15u8", "shl", i16::MIN as i64),
+    ] {
+        assert_eq!(
+            run(&bin_op(lhs, rhs, op)).unwrap(),
+            expected,
+            "{} {} {}",
+            lhs,
+            op,
+            rhs
+        );
+    }
+}
+
+#[test]
+fn negative_shift_fails() {
+    for op in ["shl", "shr"] {
+        let result = run(&bin_op("1", "-1i8", op));
+        assert!(matches!(
+            result,
+            Err(RunError::OpError(OpError::NegativeShift))
+        ));
+    }
+}
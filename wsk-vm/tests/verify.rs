@@ -0,0 +1,182 @@
+use wsk_vm::{
+    native::{Import, NativeSig},
+    program::{Arity, Function, Program},
+    verify::{verify, VerifyError, VerifyErrorKind},
+    Inst, Value,
+};
+
+/// Returns a program of the functions, each with its number of parameters and return values and
+/// its instructions, whose entry point is the first one.
+fn program(funcs: Vec<(usize, usize, Vec<Inst>)>) -> Program {
+    let mut prog = Program::new(0);
+    for (params, rets, insts) in funcs {
+        let mut func = Function::from_insts(insts);
+        func.set_arity(Arity::new(params, rets));
+        prog.add_func(func);
+    }
+    prog
+}
+
+/// Returns the error of verifying a program whose entry point runs the instructions.
+fn verify_main(insts: Vec<Inst>) -> VerifyError {
+    verify(&program(vec![(0, 0, insts)])).unwrap_err()
+}
+
+#[test]
+fn well_formed_program() {
+    let prog = program(vec![
+        (
+            0,
+            0,
+            vec![
+                Inst::Push(Value::Int(2)),
+                Inst::Call(1),
+                Inst::Pop,
+                Inst::Halt,
+            ],
+        ),
+        // the parameter is popped into a local.
+        (
+            1,
+            1,
+            vec![
+                Inst::Store(0),
+                Inst::Load(0),
+                Inst::Load(0),
+                Inst::Add,
+                Inst::Ret,
+            ],
+        ),
+    ]);
+    assert_eq!(verify(&prog), Ok(()));
+}
+
+#[test]
+fn load_before_store() {
+    let err = verify_main(vec![
+        Inst::Push(Value::Bool(true)),
+        Inst::JmpTrue(3),
+        Inst::Push(Value::Int(1)),
+        Inst::Store(0),
+        Inst::Load(0),
+        Inst::Pop,
+        Inst::Halt,
+    ]);
+    assert_eq!(err.kind, VerifyErrorKind::LoadBeforeStore(0));
+    assert_eq!((err.fi, err.pc), (Some(0), Some(4)));
+}
+
+#[test]
+fn stack_underflow() {
+    let err = verify_main(vec![Inst::Push(Value::Int(1)), Inst::Add, Inst::Halt]);
+    assert_eq!(
+        err.kind,
+        VerifyErrorKind::StackUnderflow {
+            depth: 1,
+            required: 2
+        }
+    );
+    assert_eq!(err.pc, Some(1));
+
+    // popping past the parameters underflows the frame.
+    let prog = program(vec![
+        (
+            0,
+            0,
+            vec![Inst::Push(Value::Int(1)), Inst::Call(1), Inst::Halt],
+        ),
+        (1, 0, vec![Inst::Pop, Inst::Pop, Inst::Ret]),
+    ]);
+    let err = verify(&prog).unwrap_err();
+    assert_eq!((err.fi, err.pc), (Some(1), Some(1)));
+
+    // `ret` without the return values.
+    let prog = program(vec![
+        (0, 0, vec![Inst::Call(1), Inst::Pop, Inst::Halt]),
+        (0, 1, vec![Inst::Ret]),
+    ]);
+    let err = verify(&prog).unwrap_err();
+    assert_eq!(
+        err.kind,
+        VerifyErrorKind::StackUnderflow {
+            depth: 0,
+            required: 1
+        }
+    );
+}
+
+#[test]
+fn inconsistent_stack_depth() {
+    let err = verify_main(vec![
+        Inst::Push(Value::Bool(true)),
+        Inst::JmpTrue(2),
+        Inst::Push(Value::Int(1)),
+        Inst::Halt,
+    ]);
+    assert_eq!(
+        err.kind,
+        VerifyErrorKind::InconsistentStackDepth {
+            expected: 0,
+            found: 1
+        }
+    );
+    assert_eq!(err.pc, Some(3));
+}
+
+#[test]
+fn missing_return() {
+    let err = verify_main(vec![Inst::Push(Value::Int(1)), Inst::Pop]);
+    assert_eq!(err.kind, VerifyErrorKind::MissingReturn);
+
+    let err = verify_main(vec![]);
+    assert_eq!(err.kind, VerifyErrorKind::MissingReturn);
+}
+
+#[test]
+fn jump_out_of_range() {
+    let err = verify_main(vec![Inst::Jmp(5), Inst::Halt]);
+    assert_eq!(err.kind, VerifyErrorKind::JumpOutOfRange { target: 5 });
+
+    let err = verify_main(vec![
+        Inst::Push(Value::Bool(true)),
+        Inst::JmpFalse(-3),
+        Inst::Halt,
+    ]);
+    assert_eq!(err.kind, VerifyErrorKind::JumpOutOfRange { target: -2 });
+}
+
+#[test]
+fn invalid_const_index() {
+    let err = verify_main(vec![Inst::PushConst(0), Inst::Pop, Inst::Halt]);
+    assert_eq!(err.kind, VerifyErrorKind::InvalidConstIndex(0));
+}
+
+#[test]
+fn invalid_import_index() {
+    let mut prog = program(vec![(0, 0, vec![Inst::CallNative(1), Inst::Halt])]);
+    prog.add_import(Import::new("f", NativeSig::new(vec![], None)));
+    let err = verify(&prog).unwrap_err();
+    assert_eq!(err.kind, VerifyErrorKind::InvalidImportIndex(1));
+}
+
+#[test]
+fn invalid_function_index() {
+    let err = verify_main(vec![Inst::Call(3), Inst::Halt]);
+    assert_eq!(err.kind, VerifyErrorKind::InvalidFunctionIndex(3));
+}
+
+#[test]
+fn invalid_entry_point() {
+    let mut prog = Program::new(1);
+    prog.add_func(Function::from_insts([Inst::Halt]));
+    let err = verify(&prog).unwrap_err();
+    assert_eq!(err.kind, VerifyErrorKind::InvalidEntryPoint);
+}
+
+#[test]
+fn entry_point_with_params() {
+    let prog = program(vec![(1, 0, vec![Inst::Pop, Inst::Halt])]);
+    let err = verify(&prog).unwrap_err();
+    assert_eq!(err.kind, VerifyErrorKind::EntryPointWithParams);
+    assert_eq!((err.fi, err.pc), (Some(0), None));
+}